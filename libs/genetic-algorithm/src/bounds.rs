@@ -0,0 +1,361 @@
+use crate::*;
+
+/// Brings children back within the problem's bounds, after crossover
+/// and mutation (see `GeneticAlgorithm::with_bounds()`).
+///
+/// Implemented by `Bounds` for `f32` genes; for other genes it's easy
+/// enough to write one by hand.
+pub trait RepairMethod<G = f32>: Debug + Send + Sync {
+    fn repair(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+}
+
+/// How genes that ended up outside of their `Bounds` get brought back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Repair {
+    /// Gene is moved onto the bound it crossed.
+    #[default]
+    Clamp,
+
+    /// Gene bounces off the bound it crossed - e.g. for `[0, 1]`, 1.25
+    /// becomes 0.75.
+    Reflect,
+
+    /// Gene re-enters from the other side - e.g. for `[0, 1]`, 1.25
+    /// becomes 0.25; handy for genes such as angles.
+    Wrap,
+
+    /// Gene is replaced by a random value within the bounds.
+    Resample,
+}
+
+/// Allowed range of each gene, together with the way of repairing genes
+/// that fall outside it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bounds {
+    ranges: Ranges,
+    repair: Repair,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Ranges {
+    Uniform(f32, f32),
+    PerGene(Vec<(f32, f32)>),
+}
+
+impl Bounds {
+    /// Bounds all genes within `[min, max]`; the range must be finite.
+    pub fn uniform(min: f32, max: f32) -> Self {
+        assert!(Self::is_valid((min, max)), "got invalid bounds");
+
+        Self {
+            ranges: Ranges::Uniform(min, max),
+            repair: Repair::default(),
+        }
+    }
+
+    /// Bounds each gene within its own `(min, max)`; genes past the last
+    /// range are left unbounded. Ranges must be finite.
+    pub fn per_gene(ranges: impl IntoIterator<Item = (f32, f32)>) -> Self {
+        let ranges: Vec<_> = ranges.into_iter().collect();
+
+        assert!(
+            ranges.iter().copied().all(Self::is_valid),
+            "got invalid bounds"
+        );
+
+        Self {
+            ranges: Ranges::PerGene(ranges),
+            repair: Repair::default(),
+        }
+    }
+
+    /// Changes how genes outside the bounds get repaired; by default
+    /// they are clamped.
+    pub fn with_repair(mut self, repair: Repair) -> Self {
+        self.repair = repair;
+        self
+    }
+
+    /// Returns whether genes can be kept (and resampled) within given
+    /// range - i.e. whether it's not reversed, nor infinite.
+    fn is_valid((min, max): (f32, f32)) -> bool {
+        min <= max && (max - min).is_finite()
+    }
+
+    /// Returns range of given gene, or `None` if it's unbounded.
+    pub fn range(&self, gene: usize) -> Option<(f32, f32)> {
+        match &self.ranges {
+            Ranges::Uniform(min, max) => Some((*min, *max)),
+            Ranges::PerGene(ranges) => ranges.get(gene).copied(),
+        }
+    }
+
+    fn repair_gene(&self, rng: &mut dyn RngCore, gene: f32, (min, max): (f32, f32)) -> f32 {
+        if (min..=max).contains(&gene) {
+            return gene;
+        }
+
+        let width = max - min;
+
+        if width == 0.0 {
+            return min;
+        }
+
+        if self.repair != Repair::Resample {
+            // NaNs lie on neither side of the range, so they can't get
+            // clamped, reflected nor wrapped - they land in its middle
+            if gene.is_nan() {
+                return min + width / 2.0;
+            }
+
+            // `rem_euclid()` turns infinities into NaNs, so they can't
+            // get reflected nor wrapped - they get clamped instead
+            if gene.is_infinite() {
+                return gene.clamp(min, max);
+            }
+        }
+
+        match self.repair {
+            Repair::Clamp => gene.clamp(min, max),
+
+            Repair::Reflect => {
+                // Bouncing back and forth between bounds is periodic
+                // with twice the width
+                let offset = (gene - min).rem_euclid(2.0 * width);
+
+                if offset > width {
+                    max - (offset - width)
+                } else {
+                    min + offset
+                }
+            }
+
+            Repair::Wrap => min + (gene - min).rem_euclid(width),
+            Repair::Resample => rng.gen_range(min..=max),
+        }
+    }
+}
+
+impl RepairMethod for Bounds {
+    fn repair(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        child.repair(rng, self);
+    }
+}
+
+impl Chromosome {
+    /// Returns whether all genes lie within given bounds.
+    pub fn is_within(&self, bounds: &Bounds) -> bool {
+        self.violation(bounds) == 0.0
+    }
+
+    /// Returns how far genes lie outside given bounds, summed over all
+    /// genes; useful for implementing `Individual::violation()`.
+    ///
+    /// NaN genes lie infinitely far away.
+    pub fn violation(&self, bounds: &Bounds) -> f32 {
+        self.iter()
+            .enumerate()
+            .filter_map(|(index, &gene)| {
+                let (min, max) = bounds.range(index)?;
+
+                if gene.is_nan() {
+                    return Some(f32::INFINITY);
+                }
+
+                Some((min - gene).max(0.0) + (gene - max).max(0.0))
+            })
+            .sum()
+    }
+
+    /// Brings genes lying outside given bounds back within them, as
+    /// dictated by the bounds' `Repair`.
+    pub fn repair(&mut self, rng: &mut dyn RngCore, bounds: &Bounds) {
+        for (index, gene) in self.iter_mut().enumerate() {
+            if let Some(range) = bounds.range(index) {
+                *gene = bounds.repair_gene(rng, *gene, range);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn chromosome() -> Chromosome {
+        [-2.5, -0.5, 0.0, 0.5, 1.25, 3.5].into_iter().collect()
+    }
+
+    mod uniform {
+        use super::*;
+
+        #[test]
+        #[should_panic(expected = "got invalid bounds")]
+        fn given_infinite_range() {
+            Bounds::uniform(0.0, f32::INFINITY);
+        }
+
+        #[test]
+        #[should_panic(expected = "got invalid bounds")]
+        fn given_too_wide_range() {
+            Bounds::uniform(f32::MIN, f32::MAX);
+        }
+    }
+
+    mod per_gene {
+        use super::*;
+
+        #[test]
+        #[should_panic(expected = "got invalid bounds")]
+        fn given_infinite_range() {
+            Bounds::per_gene([(0.0, 1.0), (f32::NEG_INFINITY, 0.0)]);
+        }
+
+        #[test]
+        #[should_panic(expected = "got invalid bounds")]
+        fn given_reversed_range() {
+            Bounds::per_gene([(1.0, 0.0)]);
+        }
+    }
+
+    mod range {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let uniform = Bounds::uniform(-1.0, 1.0);
+            let per_gene = Bounds::per_gene([(0.0, 1.0), (2.0, 3.0)]);
+
+            assert_eq!(uniform.range(100), Some((-1.0, 1.0)));
+            assert_eq!(per_gene.range(1), Some((2.0, 3.0)));
+            assert_eq!(per_gene.range(2), None);
+        }
+    }
+
+    mod violation {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let bounds = Bounds::uniform(-1.0, 1.0);
+
+            // Genes -2.5, 1.25 and 3.5 lie 1.5, 0.25 and 2.5 outside
+            approx::assert_relative_eq!(chromosome().violation(&bounds), 4.25);
+            assert!(!chromosome().is_within(&bounds));
+        }
+
+        #[test]
+        fn given_nan_gene() {
+            let chromosome: Chromosome = [0.0, f32::NAN].into_iter().collect();
+            let bounds = Bounds::uniform(-1.0, 1.0);
+
+            assert_eq!(chromosome.violation(&bounds), f32::INFINITY);
+            assert!(!chromosome.is_within(&bounds));
+        }
+
+        #[test]
+        fn given_unbounded_genes() {
+            let bounds = Bounds::per_gene([(-3.0, 0.0), (-1.0, 1.0)]);
+
+            approx::assert_relative_eq!(chromosome().violation(&bounds), 0.0);
+            assert!(chromosome().is_within(&bounds));
+        }
+    }
+
+    mod repair {
+        use super::*;
+
+        fn actual(repair: Repair) -> Chromosome {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut chromosome = chromosome();
+
+            chromosome.repair(&mut rng, &Bounds::uniform(-1.0, 1.0).with_repair(repair));
+            chromosome
+        }
+
+        #[test]
+        fn clamp() {
+            let expected = [-1.0, -0.5, 0.0, 0.5, 1.0, 1.0].into_iter().collect();
+
+            assert_eq!(actual(Repair::Clamp), expected);
+        }
+
+        #[test]
+        fn reflect() {
+            // 3.5 bounces off 1.0 (to -1.5), and then off -1.0
+            let expected = [0.5, -0.5, 0.0, 0.5, 0.75, -0.5].into_iter().collect();
+
+            assert_eq!(actual(Repair::Reflect), expected);
+        }
+
+        #[test]
+        fn wrap() {
+            let expected = [-0.5, -0.5, 0.0, 0.5, -0.75, -0.5].into_iter().collect();
+
+            assert_eq!(actual(Repair::Wrap), expected);
+        }
+
+        #[test]
+        fn resample() {
+            let actual = actual(Repair::Resample);
+
+            assert!(actual.is_within(&Bounds::uniform(-1.0, 1.0)));
+
+            // Genes that were within bounds are left untouched
+            assert_eq!((actual[1], actual[2], actual[3]), (-0.5, 0.0, 0.5));
+        }
+
+        #[test]
+        fn given_infinite_genes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            for repair in [Repair::Clamp, Repair::Reflect, Repair::Wrap] {
+                let mut chromosome: Chromosome =
+                    [f32::NEG_INFINITY, f32::INFINITY].into_iter().collect();
+
+                chromosome.repair(&mut rng, &Bounds::uniform(-1.0, 1.0).with_repair(repair));
+
+                let expected = [-1.0, 1.0].into_iter().collect();
+
+                assert_eq!(chromosome, expected, "{:?}", repair);
+            }
+        }
+
+        #[test]
+        fn given_nan_genes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let bounds = Bounds::uniform(-1.0, 3.0);
+
+            for repair in [
+                Repair::Clamp,
+                Repair::Reflect,
+                Repair::Wrap,
+                Repair::Resample,
+            ] {
+                let mut chromosome: Chromosome = [f32::NAN].into_iter().collect();
+
+                chromosome.repair(&mut rng, &bounds.clone().with_repair(repair));
+
+                assert!(chromosome.is_within(&bounds), "{:?}", repair);
+
+                if repair != Repair::Resample {
+                    assert_eq!(chromosome[0], 1.0, "{:?}", repair);
+                }
+            }
+        }
+
+        #[test]
+        fn given_unbounded_genes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut chromosome = chromosome();
+
+            chromosome.repair(&mut rng, &Bounds::per_gene([(-2.0, 2.0)]));
+
+            let expected = [-2.0, -0.5, 0.0, 0.5, 1.25, 3.5].into_iter().collect();
+
+            assert_eq!(chromosome, expected);
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as ThisError;
+
+#[derive(ThisError, Debug)]
+pub enum CheckpointError {
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("checkpoint was made with different operators: {checkpoint} (expected {actual})")]
+    OperatorMismatch { checkpoint: String, actual: String },
+}
+
+/// Snapshot of a `GeneticAlgorithm` run, taken right before evolving
+/// the current population - see `GeneticAlgorithm::checkpoint()`.
+///
+/// Resuming from it (see `GeneticAlgorithm::resume()`) continues the run
+/// exactly as if it had never been interrupted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Checkpoint<G = f32> {
+    /// The current population, together with fitnesses
+    pub population: Vec<Evaluated<G>>,
+
+    /// Random number generator, in the state it was at the moment of
+    /// taking the checkpoint
+    pub rng: ChaCha8Rng,
+
+    /// Statistics of each generation evolved so far
+    pub statistics: Vec<Statistics<G>>,
+
+    /// Configuration of the operators (selection, crossover etc.), so
+    /// that the run isn't accidentally resumed with different ones
+    operators: String,
+}
+
+impl<G> Checkpoint<G> {
+    /// Number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.statistics.len()
+    }
+
+    pub fn to_json(&self) -> String
+    where
+        G: Serialize,
+    {
+        serde_json::to_string(self).expect("checkpoint is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, CheckpointError>
+    where
+        G: for<'de> Deserialize<'de>,
+    {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+impl<S, G> GeneticAlgorithm<S, G>
+where
+    S: SelectionMethod,
+    G: Gene,
+{
+    /// Takes a checkpoint of a run: `population` must be already
+    /// evaluated (i.e. about to be passed into `Self::evolve()`), and
+    /// `statistics` must contain statistics returned by all the previous
+    /// calls to `Self::evolve()`.
+    pub fn checkpoint<I>(
+        &self,
+        rng: &ChaCha8Rng,
+        population: &[I],
+        statistics: &[Statistics<G>],
+    ) -> Checkpoint<G>
+    where
+        I: Individual<Gene = G>,
+    {
+        Checkpoint {
+            population: population
+                .iter()
+                .map(|individual| {
+                    Evaluated::new(individual.chromosome().clone(), individual.fitness())
+                })
+                .collect(),
+            rng: rng.clone(),
+            statistics: statistics.to_vec(),
+            operators: self.operators(),
+        }
+    }
+
+    /// Prepares the genetic algorithm for resuming from given checkpoint
+    /// (e.g. brings `AnnealedMutation` back to the checkpoint's
+    /// generation); the run then continues by evolving the checkpoint's
+    /// population with its random number generator.
+    ///
+    /// Fails if the checkpoint has been taken with differently
+    /// configured operators.
+    pub fn resume(&self, checkpoint: &Checkpoint<G>) -> Result<(), CheckpointError> {
+        let operators = self.operators();
+
+        if checkpoint.operators != operators {
+            return Err(CheckpointError::OperatorMismatch {
+                checkpoint: checkpoint.operators.clone(),
+                actual: operators,
+            });
+        }
+
+        self.mutation_method.restore(checkpoint.generation());
+
+        Ok(())
+    }
+
+    /// Describes configuration of the operators, leaving out their
+    /// runtime state (see `MutationMethod::configuration()`), so that it
+    /// doesn't matter how far the genetic algorithm has gotten.
+    fn operators(&self) -> String {
+        format!(
+            "{:?}, {:?}, {}, {:?}, {:?}, {:?}, {:?}",
+            self.selection_method,
+            self.crossover_method,
+            self.mutation_method.configuration(),
+            self.replacement,
+            self.niching,
+            self.repair_method,
+            self.penalty,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn ga() -> GeneticAlgorithm<TournamentSelection> {
+        GeneticAlgorithm::new(
+            TournamentSelection::new(2),
+            UniformCrossover::new(),
+            AnnealedMutation::new(0.5, 0.5, 0.9),
+        )
+        .with_replacement(Replacement::Elitist { elites: 1 })
+    }
+
+    fn evaluate(population: Vec<Evaluated>) -> Vec<Evaluated> {
+        population
+            .into_iter()
+            .map(|individual| {
+                let fitness = individual.chromosome().iter().sum();
+
+                Evaluated::new(individual.chromosome().clone(), fitness)
+            })
+            .collect()
+    }
+
+    fn population() -> Vec<Evaluated> {
+        evaluate(vec![
+            Evaluated::create([0.0, 0.0, 0.0].into_iter().collect()),
+            Evaluated::create([1.0, 1.0, 1.0].into_iter().collect()),
+            Evaluated::create([1.0, 2.0, 1.0].into_iter().collect()),
+            Evaluated::create([1.0, 2.0, 4.0].into_iter().collect()),
+        ])
+    }
+
+    /// Evolves given population for given number of generations.
+    fn run(
+        ga: &GeneticAlgorithm<TournamentSelection>,
+        rng: &mut ChaCha8Rng,
+        mut population: Vec<Evaluated>,
+        statistics: &mut Vec<Statistics>,
+        generations: usize,
+    ) -> Vec<Evaluated> {
+        for _ in 0..generations {
+            let (children, stats) = ga.evolve(rng, &population);
+
+            population = evaluate(children);
+            statistics.push(stats);
+        }
+
+        population
+    }
+
+    mod resume {
+        use super::*;
+
+        #[test]
+        fn test() {
+            // Uninterrupted run
+            let expected = {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let mut statistics = Vec::new();
+
+                run(&ga(), &mut rng, population(), &mut statistics, 20)
+            };
+
+            // Interrupted run
+            let checkpoint = {
+                let ga = ga();
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let mut statistics = Vec::new();
+                let population = run(&ga, &mut rng, population(), &mut statistics, 8);
+
+                ga.checkpoint(&rng, &population, &statistics).to_json()
+            };
+
+            // Resumed run
+            let actual = {
+                let ga = ga();
+                let checkpoint = Checkpoint::from_json(&checkpoint).unwrap();
+
+                assert_eq!(checkpoint.generation(), 8);
+                ga.resume(&checkpoint).unwrap();
+
+                let Checkpoint {
+                    population,
+                    mut rng,
+                    mut statistics,
+                    ..
+                } = checkpoint;
+
+                let population = run(&ga, &mut rng, population, &mut statistics, 12);
+
+                assert_eq!(statistics.len(), 20);
+                population
+            };
+
+            let chromosomes = |population: Vec<Evaluated>| -> Vec<_> {
+                population
+                    .into_iter()
+                    .map(|individual| individual.chromosome().clone())
+                    .collect()
+            };
+
+            assert_eq!(chromosomes(actual), chromosomes(expected));
+        }
+
+        #[test]
+        fn given_different_operators() {
+            let rng = ChaCha8Rng::from_seed(Default::default());
+            let checkpoint = ga().checkpoint(&rng, &population(), &[]);
+
+            let ga = GeneticAlgorithm::new(
+                TournamentSelection::new(3),
+                UniformCrossover::new(),
+                AnnealedMutation::new(0.5, 0.5, 0.9),
+            )
+            .with_replacement(Replacement::Elitist { elites: 1 });
+
+            assert!(matches!(
+                ga.resume(&checkpoint),
+                Err(CheckpointError::OperatorMismatch { .. })
+            ));
+        }
+
+        #[test]
+        fn given_different_operators_leaves_ga_untouched() {
+            let checkpoint = {
+                let ga = ga();
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let mut statistics = Vec::new();
+                let population = run(&ga, &mut rng, population(), &mut statistics, 8);
+
+                ga.checkpoint(&rng, &population, &statistics)
+            };
+
+            let ga = || {
+                GeneticAlgorithm::new(
+                    TournamentSelection::new(3),
+                    UniformCrossover::new(),
+                    AnnealedMutation::new(0.5, 0.5, 0.9),
+                )
+            };
+
+            let evolve = |ga: &GeneticAlgorithm<TournamentSelection>| {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let (children, _) = ga.evolve(&mut rng, &population());
+
+                children
+                    .into_iter()
+                    .map(|child| child.chromosome().clone())
+                    .collect::<Vec<_>>()
+            };
+
+            let actual = ga();
+
+            assert!(actual.resume(&checkpoint).is_err());
+            assert_eq!(evolve(&actual), evolve(&ga()));
+        }
+    }
+
+    mod from_json {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+            let checkpoint = ga().checkpoint(&rng, &population, &[]);
+            let actual = Checkpoint::<f32>::from_json(&checkpoint.to_json()).unwrap();
+
+            assert_eq!(actual.generation(), 0);
+            assert_eq!(actual.rng, rng);
+            assert_eq!(actual.population.len(), 4);
+            approx::assert_relative_eq!(actual.population[3].fitness(), 7.0);
+        }
+
+        #[test]
+        fn given_invalid_json() {
+            assert!(matches!(
+                Checkpoint::<f32>::from_json("{"),
+                Err(CheckpointError::Json(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Genes of a single individual; `f32` by default, but any `Gene` works -
+/// see `Gene` for a few examples.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
+}
+
+impl<G> Chromosome<G>
+where
+    G: Gene,
+{
+    pub fn len(&self) -> usize {
+        self.genes.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &G> {
+        self.genes.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
+        self.genes.iter_mut()
+    }
+
+    /// Mean distance between genes of both chromosomes (see
+    /// `Gene::distance()`); chromosomes of different lengths are compared
+    /// only on the genes they both have.
+    pub fn distance(&self, other: &Self) -> f32 {
+        let genes = self.len().min(other.len());
+
+        if genes == 0 {
+            return 0.0;
+        }
+
+        let distance: f32 = self
+            .iter()
+            .zip(other.iter())
+            .map(|(a, b)| a.distance(b))
+            .sum();
+
+        distance / genes as f32
+    }
+
+    /// Swaps two genes; handy for permutations, which mustn't get their
+    /// genes overwritten.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.genes.swap(a, b);
+    }
+}
+
+impl<G> std::ops::Index<usize> for Chromosome<G> {
+    type Output = G;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.genes[index]
+    }
+}
+
+impl<G> std::ops::IndexMut<usize> for Chromosome<G> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.genes[index]
+    }
+}
+
+impl<G> std::iter::FromIterator<G> for Chromosome<G> {
+    fn from_iter<T: IntoIterator<Item = G>>(iter: T) -> Self {
+        Self {
+            genes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<G> std::iter::IntoIterator for Chromosome<G> {
+    type Item = G;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+    // type IntoIter = impl Iterator<Item = f32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.genes.into_iter()
+    }
+}
+
+#[cfg(test)]
+impl PartialEq for Chromosome {
+    fn eq(&self, other: &Self) -> bool {
+        approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice(),)
+    }
+}
+
+#[cfg(test)]
+macro_rules! impl_exact_partial_eq {
+    ($($ty:ty),*) => {
+        $(
+            impl PartialEq for Chromosome<$ty> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.genes == other.genes
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+impl_exact_partial_eq!(bool, i32, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chromosome() -> Chromosome {
+        Chromosome {
+            genes: vec![3.0, 1.0, 2.0],
+        }
+    }
+
+    mod len {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(chromosome().len(), 3);
+        }
+    }
+
+    mod iter {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let chromosome = chromosome();
+            let genes: Vec<_> = chromosome.iter().collect();
+
+            assert_eq!(genes.len(), 3);
+            assert_eq!(genes[0], &3.0);
+            assert_eq!(genes[1], &1.0);
+            assert_eq!(genes[2], &2.0);
+        }
+    }
+
+    mod iter_mut {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut chromosome = chromosome();
+
+            chromosome.iter_mut().for_each(|gene| {
+                *gene *= 10.0;
+            });
+
+            let genes: Vec<_> = chromosome.iter().collect();
+
+            assert_eq!(genes.len(), 3);
+            assert_eq!(genes[0], &30.0);
+            assert_eq!(genes[1], &10.0);
+            assert_eq!(genes[2], &20.0);
+        }
+    }
+
+    mod index {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let chromosome = chromosome();
+
+            assert_eq!(chromosome[0], 3.0);
+            assert_eq!(chromosome[1], 1.0);
+            assert_eq!(chromosome[2], 2.0);
+        }
+    }
+
+    mod distance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let other = vec![1.0, 1.0, 2.0].into_iter().collect();
+
+            approx::assert_relative_eq!(chromosome().distance(&other), 2.0 / 3.0);
+            approx::assert_relative_eq!(chromosome().distance(&chromosome()), 0.0);
+        }
+
+        #[test]
+        fn compares_only_common_genes() {
+            let other = vec![5.0].into_iter().collect();
+
+            approx::assert_relative_eq!(chromosome().distance(&other), 2.0);
+        }
+    }
+
+    mod swap {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut chromosome = chromosome();
+
+            chromosome.swap(0, 2);
+
+            assert_eq!(chromosome, vec![2.0, 1.0, 3.0].into_iter().collect());
+        }
+    }
+
+    mod index_mut {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut chromosome = chromosome();
+
+            chromosome[1] = 5.0;
+
+            assert_eq!(chromosome, vec![3.0, 5.0, 2.0].into_iter().collect());
+        }
+    }
+
+    mod from_iterator {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let chromosome: Chromosome = vec![3.0, 1.0, 2.0].into_iter().collect();
+
+            assert_eq!(chromosome[0], 3.0);
+            assert_eq!(chromosome[1], 1.0);
+            assert_eq!(chromosome[2], 2.0);
+        }
+    }
+
+    mod given_other_genes {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let bits: Chromosome<bool> = vec![true, false].into_iter().collect();
+            let route: Chromosome<usize> = (0..4).rev().collect();
+
+            assert_eq!(bits.len(), 2);
+            assert!(bits[0]);
+            assert_eq!(route.iter().copied().collect::<Vec<_>>(), [3, 2, 1, 0]);
+        }
+    }
+
+    mod into_iterator {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let chromosome = Chromosome {
+                genes: vec![3.0, 1.0, 2.0],
+            };
+
+            let genes: Vec<_> = chromosome.into_iter().collect();
+
+            assert_eq!(genes.len(), 3);
+            assert_eq!(genes[0], 3.0);
+            assert_eq!(genes[1], 1.0);
+            assert_eq!(genes[2], 2.0);
+        }
+    }
+}
@@ -0,0 +1,490 @@
+use crate::*;
+
+/// Covariance matrix adaptation evolution strategy, CMA-ES (Hansen,
+/// 2016): samples each generation from a multivariate normal
+/// distribution, whose mean, step size and covariance matrix follow the
+/// best individuals of the previous generation.
+///
+/// Learning the covariance allows it to pick up correlations between
+/// genes (such as weights of the same neuron), which crossover mostly
+/// shuffles around - so it tends to do better on neural network weights,
+/// as long as there aren't too many of them (the covariance matrix grows
+/// with the square of the chromosome's length).
+///
+/// The first call to `Self::evolve()` only centers the distribution on
+/// the given population; adaptation starts with the second call.
+///
+/// Unlike other optimizers, it doesn't support `Bounds` - the mean and
+/// the covariance get learned from the very samples it has drawn, so
+/// repairing them would skew the distribution; penalize violations in
+/// `Individual::fitness()` instead.
+#[derive(Clone, Debug)]
+pub struct CmaEs {
+    /// Initial step size, i.e. the standard deviation of genes in the
+    /// first sampled generation
+    sigma: f32,
+
+    state: Option<CmaEsState>,
+}
+
+#[derive(Clone, Debug)]
+struct CmaEsState {
+    mean: Vec<f64>,
+    sigma: f64,
+
+    /// Covariance matrix, row by row
+    covariance: Vec<f64>,
+
+    /// Lower-triangular Cholesky factor of `covariance`, row by row;
+    /// used for sampling
+    factor: Vec<f64>,
+
+    /// Evolution path of `covariance`
+    path_c: Vec<f64>,
+
+    /// Evolution path of `sigma`
+    path_sigma: Vec<f64>,
+
+    generation: usize,
+}
+
+/// Learning rates and other constants, as recommended by Hansen (2016)
+/// for given number of genes and population's size.
+#[derive(Clone, Debug)]
+struct Parameters {
+    /// Weights of the best individuals, which are the parents
+    weights: Vec<f64>,
+
+    /// Variance effective selection mass
+    mu_eff: f64,
+
+    c_sigma: f64,
+    d_sigma: f64,
+    c_c: f64,
+    c_1: f64,
+    c_mu: f64,
+
+    /// Expected length of a vector sampled from `N(0, I)`
+    chi_n: f64,
+}
+
+impl CmaEs {
+    pub fn new(sigma: f32) -> Self {
+        assert!(sigma > 0.0);
+
+        Self { sigma, state: None }
+    }
+
+    /// Current step size; `None` before the first generation.
+    pub fn sigma(&self) -> Option<f32> {
+        self.state.as_ref().map(|state| state.sigma as f32)
+    }
+
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    where
+        I: Individual<Gene = f32>,
+    {
+        assert!(!population.is_empty());
+
+        if let Some(state) = &mut self.state {
+            state.update(population);
+        } else {
+            self.state = Some(CmaEsState::new(population, self.sigma as f64));
+        }
+
+        let state = self.state.as_ref().unwrap();
+
+        let new_population = population
+            .iter()
+            .map(|_| I::create(state.sample(rng)))
+            .collect();
+
+        (new_population, Statistics::new(population))
+    }
+}
+
+impl<I> Optimizer<I> for CmaEs
+where
+    I: Individual<Gene = f32>,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics) {
+        CmaEs::evolve(self, rng, population)
+    }
+}
+
+impl CmaEsState {
+    fn new<I>(population: &[I], sigma: f64) -> Self
+    where
+        I: Individual<Gene = f32>,
+    {
+        let genes = population[0].chromosome().len();
+        let mut mean = vec![0.0; genes];
+
+        for individual in population {
+            assert_eq!(individual.chromosome().len(), genes);
+
+            for (mean, &gene) in mean.iter_mut().zip(individual.chromosome().iter()) {
+                *mean += gene as f64 / population.len() as f64;
+            }
+        }
+
+        Self {
+            mean,
+            sigma,
+            covariance: identity(genes),
+            factor: identity(genes),
+            path_c: vec![0.0; genes],
+            path_sigma: vec![0.0; genes],
+            generation: 0,
+        }
+    }
+
+    /// Moves the distribution towards the best individuals of given
+    /// population, which is assumed to be sampled from it.
+    fn update<I>(&mut self, population: &[I])
+    where
+        I: Individual<Gene = f32>,
+    {
+        let genes = self.mean.len();
+        let params = Parameters::new(genes, population.len());
+
+        let mut best: Vec<_> = population.iter().collect();
+
+        best.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        // Steps from the current mean to each parent, scaled by sigma
+        let steps: Vec<Vec<f64>> = best[..params.weights.len()]
+            .iter()
+            .map(|individual| {
+                assert_eq!(individual.chromosome().len(), genes);
+
+                individual
+                    .chromosome()
+                    .iter()
+                    .zip(&self.mean)
+                    .map(|(&gene, mean)| (gene as f64 - mean) / self.sigma)
+                    .collect()
+            })
+            .collect();
+
+        let mut step = vec![0.0; genes];
+
+        for (weight, parent_step) in params.weights.iter().zip(&steps) {
+            for (step, parent_step) in step.iter_mut().zip(parent_step) {
+                *step += weight * parent_step;
+            }
+        }
+
+        for (mean, step) in self.mean.iter_mut().zip(&step) {
+            *mean += self.sigma * step;
+        }
+
+        self.generation += 1;
+
+        // Step size adaptation
+        let whitened = solve_lower(&self.factor, &step);
+        let coeff = (params.c_sigma * (2.0 - params.c_sigma) * params.mu_eff).sqrt();
+
+        for (path, whitened) in self.path_sigma.iter_mut().zip(&whitened) {
+            *path = (1.0 - params.c_sigma) * *path + coeff * whitened;
+        }
+
+        let path_sigma_len = norm(&self.path_sigma);
+
+        let stalled = path_sigma_len
+            / (1.0 - (1.0 - params.c_sigma).powi(2 * self.generation as i32)).sqrt()
+            >= (1.4 + 2.0 / (genes as f64 + 1.0)) * params.chi_n;
+
+        // Covariance adaptation; while `path_sigma` is long (i.e. sigma
+        // is about to grow quickly), `path_c` is put on hold
+        let h_sigma = if stalled { 0.0 } else { 1.0 };
+        let coeff = h_sigma * (params.c_c * (2.0 - params.c_c) * params.mu_eff).sqrt();
+
+        for (path, step) in self.path_c.iter_mut().zip(&step) {
+            *path = (1.0 - params.c_c) * *path + coeff * step;
+        }
+
+        let delta = (1.0 - h_sigma) * params.c_c * (2.0 - params.c_c);
+
+        for row in 0..genes {
+            for col in 0..=row {
+                let rank_mu: f64 = params
+                    .weights
+                    .iter()
+                    .zip(&steps)
+                    .map(|(weight, step)| weight * step[row] * step[col])
+                    .sum();
+
+                let old = self.covariance[row * genes + col];
+
+                let new = (1.0 - params.c_1 - params.c_mu) * old
+                    + params.c_1 * (self.path_c[row] * self.path_c[col] + delta * old)
+                    + params.c_mu * rank_mu;
+
+                self.covariance[row * genes + col] = new;
+                self.covariance[col * genes + row] = new;
+            }
+        }
+
+        self.sigma *=
+            ((params.c_sigma / params.d_sigma) * (path_sigma_len / params.chi_n - 1.0)).exp();
+
+        // Numerical errors can (very rarely) make the covariance matrix
+        // lose its positive definiteness - in that case we start over
+        // with the current mean and step size
+        if let Some(factor) = cholesky(&self.covariance) {
+            self.factor = factor;
+        } else {
+            self.covariance = identity(genes);
+            self.factor = identity(genes);
+            self.path_c.fill(0.0);
+            self.path_sigma.fill(0.0);
+        }
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> Chromosome {
+        let genes = self.mean.len();
+
+        let noise: Vec<f64> = (0..genes)
+            .map(|_| rng.sample::<f64, _>(StandardNormal))
+            .collect();
+
+        (0..genes)
+            .map(|row| {
+                let step: f64 = self.factor[row * genes..][..=row]
+                    .iter()
+                    .zip(&noise)
+                    .map(|(factor, noise)| factor * noise)
+                    .sum();
+
+                (self.mean[row] + self.sigma * step) as f32
+            })
+            .collect()
+    }
+}
+
+impl Parameters {
+    fn new(genes: usize, population: usize) -> Self {
+        let n = genes as f64;
+        let lambda = population as f64;
+        let parents = (population / 2).max(1);
+
+        let mut weights: Vec<f64> = (1..=parents)
+            .map(|rank| ((lambda + 1.0) / 2.0).ln() - (rank as f64).ln())
+            .collect();
+
+        let sum: f64 = weights.iter().sum();
+
+        if sum > 0.0 {
+            weights.iter_mut().for_each(|weight| *weight /= sum);
+        } else {
+            weights.fill(1.0 / parents as f64);
+        }
+
+        let mu_eff = 1.0 / weights.iter().map(|weight| weight * weight).sum::<f64>();
+
+        let c_sigma = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let d_sigma = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma;
+        let c_c = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let c_1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+
+        let c_mu =
+            (2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff)).min(1.0 - c_1);
+
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        Self {
+            weights,
+            mu_eff,
+            c_sigma,
+            d_sigma,
+            c_c,
+            c_1,
+            c_mu,
+            chi_n,
+        }
+    }
+}
+
+fn identity(size: usize) -> Vec<f64> {
+    let mut matrix = vec![0.0; size * size];
+
+    matrix
+        .iter_mut()
+        .step_by(size + 1)
+        .for_each(|value| *value = 1.0);
+    matrix
+}
+
+fn norm(vector: &[f64]) -> f64 {
+    vector.iter().map(|value| value * value).sum::<f64>().sqrt()
+}
+
+/// Returns lower-triangular `L` such that `L * L^T = matrix`, or `None`
+/// if `matrix` is not positive definite.
+fn cholesky(matrix: &[f64]) -> Option<Vec<f64>> {
+    let size = (matrix.len() as f64).sqrt() as usize;
+    let mut factor = vec![0.0; matrix.len()];
+
+    for row in 0..size {
+        for col in 0..=row {
+            let sum: f64 = (0..col)
+                .map(|k| factor[row * size + k] * factor[col * size + k])
+                .sum();
+
+            let value = matrix[row * size + col] - sum;
+
+            factor[row * size + col] = if row == col {
+                if value <= 0.0 || !value.is_finite() {
+                    return None;
+                }
+
+                value.sqrt()
+            } else {
+                value / factor[col * size + col]
+            };
+        }
+    }
+
+    Some(factor)
+}
+
+/// Solves `factor * x = vector` for lower-triangular `factor`.
+fn solve_lower(factor: &[f64], vector: &[f64]) -> Vec<f64> {
+    let size = vector.len();
+    let mut solution = vec![0.0; size];
+
+    for row in 0..size {
+        let sum: f64 = (0..row)
+            .map(|col| factor[row * size + col] * solution[col])
+            .sum();
+
+        solution[row] = (vector[row] - sum) / factor[row * size + row];
+    }
+
+    solution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Rotated ellipsoid: the sphere function stretched along a diagonal,
+    /// so that genes are strongly correlated - a bit like weights of a
+    /// neural network.
+    #[derive(Clone, Debug)]
+    struct Ellipsoid(Chromosome);
+
+    impl Individual for Ellipsoid {
+        type Gene = f32;
+
+        fn create(chromosome: Chromosome) -> Self {
+            Self(chromosome)
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.0
+        }
+
+        fn fitness(&self) -> f32 {
+            let genes: Vec<_> = self.0.iter().collect();
+
+            -genes
+                .windows(2)
+                .map(|pair| 100.0 * (pair[0] - pair[1]).powi(2) + pair[0].powi(2))
+                .sum::<f32>()
+        }
+    }
+
+    mod cholesky {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let actual = cholesky(&[4.0, 2.0, 2.0, 5.0]).unwrap();
+            let expected = [2.0, 0.0, 1.0, 2.0];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+            approx::assert_relative_eq!(
+                solve_lower(&actual, &[2.0, 5.0]).as_slice(),
+                [1.0, 2.0].as_slice()
+            );
+        }
+
+        #[test]
+        fn given_indefinite_matrix() {
+            assert!(cholesky(&[1.0, 2.0, 2.0, 1.0]).is_none());
+        }
+    }
+
+    mod parameters {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let params = Parameters::new(10, 10);
+
+            assert_eq!(params.weights.len(), 5);
+            approx::assert_relative_eq!(params.weights.iter().sum::<f64>(), 1.0);
+            assert!(params.weights.windows(2).all(|pair| pair[0] > pair[1]));
+            approx::assert_relative_eq!(params.mu_eff, 3.1672, epsilon = 1e-4);
+        }
+    }
+
+    mod evolve {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut cma_es = CmaEs::new(1.0);
+            let population = spheres(&mut rng, 10, 10);
+
+            assert_eq!(cma_es.sigma(), None);
+
+            let actual = optimize(&mut rng, &mut cma_es, population, 200);
+
+            assert!(actual > -1e-6, "{}", actual);
+            assert!(cma_es.sigma().unwrap() < 0.01);
+        }
+
+        #[test]
+        fn beats_genetic_algorithm_on_correlated_genes() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population: Vec<_> = spheres(&mut rng, 20, 10)
+                .into_iter()
+                .map(|sphere| Ellipsoid::create(sphere.chromosome().clone()))
+                .collect();
+
+            let mut ga = GeneticAlgorithm::new(
+                TournamentSelection::new(2),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.2, 0.1),
+            )
+            .with_replacement(Replacement::Elitist { elites: 1 });
+
+            let mut cma_es = CmaEs::new(1.0);
+
+            let ga = optimize(&mut rng, &mut ga, population.clone(), 150);
+            let cma_es = optimize(&mut rng, &mut cma_es, population, 150);
+
+            assert!(cma_es > ga, "{} <= {}", cma_es, ga);
+            assert!(cma_es > -1e-3, "{}", cma_es);
+        }
+
+        #[test]
+        fn is_reproducible() {
+            let run = || {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let population = spheres(&mut rng, 8, 4);
+
+                optimize(&mut rng, &mut CmaEs::new(0.5), population, 20)
+            };
+
+            assert_eq!(run(), run());
+        }
+    }
+}
@@ -0,0 +1,80 @@
+pub use self::{
+    arithmetic::*, blend::*, k_point::*, order::*, partially_mapped::*, simulated_binary::*,
+    single_point::*, uniform::*,
+};
+
+use crate::*;
+
+mod arithmetic;
+mod blend;
+mod k_point;
+mod order;
+mod partially_mapped;
+mod simulated_binary;
+mod single_point;
+mod uniform;
+
+pub trait CrossoverMethod<G = f32>: Debug + Send + Sync {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G>;
+}
+
+/// Allows choosing the crossover at runtime (e.g. from a config), by
+/// passing `Box<dyn CrossoverMethod>` into `GeneticAlgorithm::new()`.
+impl<C, G> CrossoverMethod<G> for Box<C>
+where
+    C: CrossoverMethod<G> + ?Sized,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        (**self).crossover(rng, parent_a, parent_b)
+    }
+}
+
+/// Picks a random, non-empty segment of a chromosome with `len` genes;
+/// used by the operators that work on permutations.
+pub(crate) fn segment(rng: &mut dyn RngCore, len: usize) -> std::ops::Range<usize> {
+    assert!(len > 0);
+
+    let mut points = rand::seq::index::sample(rng, len + 1, 2).into_vec();
+
+    points.sort_unstable();
+
+    points[0]..points[1]
+}
+
+/// Parents used by tests of all the crossovers.
+#[cfg(test)]
+pub(crate) fn parents() -> (Chromosome, Chromosome) {
+    let parent_a = (1..=5).map(|n| n as f32).collect();
+    let parent_b = (1..=5).map(|n| -n as f32).collect();
+
+    (parent_a, parent_b)
+}
+
+/// Parents used by tests of the crossovers that work on permutations.
+#[cfg(test)]
+pub(crate) fn permutations() -> (Chromosome<usize>, Chromosome<usize>) {
+    let parent_a = (0..8).collect();
+    let parent_b = [3, 7, 5, 1, 6, 0, 2, 4].into_iter().collect();
+
+    (parent_a, parent_b)
+}
+
+/// Returns whether given chromosome contains each of `0..len` exactly
+/// once.
+#[cfg(test)]
+pub(crate) fn is_permutation(chromosome: &Chromosome<usize>) -> bool {
+    let mut genes: Vec<_> = chromosome.iter().copied().collect();
+
+    genes.sort_unstable();
+    genes.into_iter().eq(0..chromosome.len())
+}
@@ -0,0 +1,78 @@
+use crate::*;
+
+/// Creates child as a weighted average of its parents:
+///
+/// `child = alpha * parent_a + (1 - alpha) * parent_b`
+#[derive(Clone, Debug)]
+pub struct ArithmeticCrossover {
+    /// How much of the child comes from `parent_a`:
+    /// - 0.0 = child is a copy of `parent_b`
+    /// - 0.5 = child lies right between its parents
+    /// - 1.0 = child is a copy of `parent_a`
+    alpha: f32,
+}
+
+impl ArithmeticCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!((0.0..=1.0).contains(&alpha));
+
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| self.alpha * a + (1.0 - self.alpha) * b)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(alpha: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = parents();
+
+        ArithmeticCrossover::new(alpha)
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test() {
+        let actual = actual(0.75);
+        let expected = vec![0.5, 1.0, 1.5, 2.0, 2.5];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_extreme_alpha_copies_a_parent() {
+        let (parent_a, parent_b) = parents();
+
+        approx::assert_relative_eq!(
+            actual(1.0).as_slice(),
+            parent_a.into_iter().collect::<Vec<_>>().as_slice()
+        );
+
+        approx::assert_relative_eq!(
+            actual(0.0).as_slice(),
+            parent_b.into_iter().collect::<Vec<_>>().as_slice()
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+/// Blend crossover, also known as BLX-α (Eshelman & Schaffer, 1993):
+/// each gene of the child is sampled uniformly from the range spanned
+/// by the parents' genes, extended by `alpha` of its width on each side.
+///
+/// ```text
+///      alpha * d        d        alpha * d
+/// ---[===========a-----------b===========]---
+/// ```
+///
+/// Contrary to `ArithmeticCrossover`, this can create genes outside of
+/// what the parents have, which helps to keep the population diverse.
+#[derive(Clone, Debug)]
+pub struct BlendCrossover {
+    /// How far beyond its parents the child can reach:
+    /// - 0.0 = child's genes always lie between the parents' genes
+    /// - 0.5 = the usual choice
+    alpha: f32,
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Self {
+        assert!(alpha >= 0.0);
+
+        Self { alpha }
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let extension = self.alpha * (a - b).abs();
+                let min = a.min(b) - extension;
+                let max = a.max(b) + extension;
+
+                rng.gen_range(min..=max)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(alpha: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = parents();
+
+        BlendCrossover::new(alpha)
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test() {
+        let actual = actual(0.5);
+        let expected = vec![-1.2510376, 2.6953583, 4.908757, 2.1027918, 5.2388067];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_zero_alpha_stays_between_parents() {
+        for (n, gene) in actual(0.0).into_iter().enumerate() {
+            let n = (n + 1) as f32;
+
+            assert!((-n..=n).contains(&gene));
+        }
+    }
+
+    #[test]
+    fn given_identical_parents_copies_them() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent, _) = parents();
+
+        let actual: Vec<_> = BlendCrossover::new(0.5)
+            .crossover(&mut rng, &parent, &parent)
+            .into_iter()
+            .collect();
+
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+/// Cuts both parents at `points` random places, and then builds the
+/// child out of the pieces, alternating between the parents:
+///
+/// ```text
+/// parent a: a a a|a a|a a a
+/// parent b: b b b|b b|b b b
+///    child: a a a|b b|a a a
+/// ```
+///
+/// Contrary to `UniformCrossover`, genes that lie next to each other
+/// tend to get inherited together.
+#[derive(Clone, Debug)]
+pub struct KPointCrossover {
+    points: usize,
+}
+
+impl KPointCrossover {
+    pub fn new(points: usize) -> Self {
+        assert!(points > 0);
+
+        Self { points }
+    }
+}
+
+impl<G> CrossoverMethod<G> for KPointCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        // Cutting happens between genes, so there are `len - 1` places
+        // to choose from
+        let places = parent_a.len().saturating_sub(1);
+
+        let mut points = rand::seq::index::sample(rng, places, self.points.min(places)).into_vec();
+
+        points.sort_unstable();
+
+        let mut points = points.into_iter().map(|point| point + 1).peekable();
+        let mut from_a = true;
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .enumerate()
+            .map(|(index, (a, b))| {
+                if points.next_if_eq(&index).is_some() {
+                    from_a = !from_a;
+                }
+
+                if from_a {
+                    a.clone()
+                } else {
+                    b.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(points: usize) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = parents();
+
+        KPointCrossover::new(points)
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test() {
+        let actual = actual(2);
+        let expected = vec![1.0, -2.0, -3.0, -4.0, 5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_more_points_than_genes_alternates_every_gene() {
+        let actual = actual(10);
+        let expected = vec![1.0, -2.0, 3.0, -4.0, 5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::*;
+
+/// Order crossover (OX1) for permutations: copies a random segment of
+/// parent a, and fills the remaining places with genes of parent b, in
+/// the order they appear there (starting right after the segment):
+///
+/// ```text
+/// parent a: 0 1 2|3 4 5|6 7
+/// parent b: 3 7 5|1 6 0|2 4
+///    child: 1 6 0|3 4 5|2 7
+/// ```
+///
+/// Each gene ends up in the child exactly once, which makes it suitable
+/// for e.g. routing problems, where the chromosome is the order of
+/// visited places; both parents must be permutations of the same genes.
+#[derive(Clone, Debug, Default)]
+pub struct OrderCrossover;
+
+impl OrderCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for OrderCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let len = parent_a.len();
+
+        if len < 2 {
+            return parent_a.clone();
+        }
+
+        let segment = segment(rng, len);
+        let copied: Vec<_> = segment.clone().map(|index| &parent_a[index]).collect();
+
+        let mut rest = (0..len)
+            .map(|offset| &parent_b[(segment.end + offset) % len])
+            .filter(|gene| !copied.contains(gene));
+
+        let mut child = vec![None; len];
+
+        for offset in 0..len {
+            let index = (segment.end + offset) % len;
+
+            child[index] = if segment.contains(&index) {
+                Some(parent_a[index].clone())
+            } else {
+                rest.next().cloned()
+            };
+        }
+
+        child
+            .into_iter()
+            .map(|gene| gene.expect("parents should be permutations of the same genes"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = permutations();
+
+        let actual: Vec<Vec<_>> = (0..3)
+            .map(|_| {
+                OrderCrossover::new()
+                    .crossover(&mut rng, &parent_a, &parent_b)
+                    .into_iter()
+                    .collect()
+            })
+            .collect();
+
+        let expected = vec![
+            vec![0, 1, 2, 3, 4, 5, 6, 7],
+            vec![1, 0, 2, 3, 4, 5, 6, 7],
+            vec![5, 1, 2, 3, 6, 0, 4, 7],
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn returns_permutations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = permutations();
+
+        for _ in 0..100 {
+            let child = OrderCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+
+            assert!(is_permutation(&child), "{:?}", child);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::*;
+
+/// Partially mapped crossover (PMX) for permutations: copies a random
+/// segment of parent a, and takes the remaining genes from parent b at
+/// their original places - unless a gene is already used by the segment,
+/// in which case it's replaced following the mapping between both
+/// parents' segments:
+///
+/// ```text
+/// parent a: 0 1 2|3 4 5|6 7
+/// parent b: 3 7 5|1 6 0|2 4
+///    child: 1 7 0|3 4 5|2 6
+/// ```
+///
+/// Compared to `OrderCrossover`, it preserves more of the absolute
+/// positions of genes (rather than their relative order); both parents
+/// must be permutations of the same genes.
+#[derive(Clone, Debug, Default)]
+pub struct PartiallyMappedCrossover;
+
+impl PartiallyMappedCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for PartiallyMappedCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let len = parent_a.len();
+
+        if len < 2 {
+            return parent_a.clone();
+        }
+
+        let segment = segment(rng, len);
+        let mut child = parent_b.clone();
+
+        for index in segment.clone() {
+            child[index] = parent_a[index].clone();
+        }
+
+        let position_in_b = |gene: &G| {
+            (0..len)
+                .find(|&index| parent_b[index] == *gene)
+                .expect("parents should be permutations of the same genes")
+        };
+
+        // Genes of parent b's segment that didn't make it into the child
+        // get moved to where the mapping leads them outside the segment
+        for index in segment.clone() {
+            let gene = &parent_b[index];
+
+            if segment.clone().any(|index| parent_a[index] == *gene) {
+                continue;
+            }
+
+            let mut position = index;
+
+            while segment.contains(&position) {
+                position = position_in_b(&parent_a[position]);
+            }
+
+            child[position] = gene.clone();
+        }
+
+        child
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = permutations();
+
+        let actual: Vec<Vec<_>> = (0..3)
+            .map(|_| {
+                PartiallyMappedCrossover::new()
+                    .crossover(&mut rng, &parent_a, &parent_b)
+                    .into_iter()
+                    .collect()
+            })
+            .collect();
+
+        let expected = vec![
+            vec![7, 1, 2, 3, 4, 5, 6, 0],
+            vec![1, 7, 2, 3, 4, 5, 6, 0],
+            vec![7, 1, 2, 3, 6, 0, 5, 4],
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn returns_permutations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = permutations();
+
+        for _ in 0..100 {
+            let child = PartiallyMappedCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+
+            assert!(is_permutation(&child), "{:?}", child);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::*;
+
+/// Simulated binary crossover, SBX (Deb & Agrawal, 1995): mimics how
+/// single-point crossover of binary-encoded numbers spreads children
+/// around their parents, but works on real numbers directly.
+///
+/// For each gene a spread factor `beta` is sampled, such that children
+/// mostly land close to their parents:
+///
+/// `child = ((1 + beta) * a + (1 - beta) * b) / 2`
+///
+/// SBX creates two children, mirrored around the middle of the parents;
+/// this returns the one that's on the side of `parent_a`.
+#[derive(Clone, Debug)]
+pub struct SimulatedBinaryCrossover {
+    /// Distribution index:
+    /// - small values (e.g. 2.0) = children can land far from parents
+    /// - large values (e.g. 20.0) = children stay close to parents
+    eta: f32,
+}
+
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Self {
+        assert!(eta >= 0.0);
+
+        Self { eta }
+    }
+
+    fn beta(&self, rng: &mut dyn RngCore) -> f32 {
+        let u: f32 = rng.gen();
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        if u <= 0.5 {
+            (2.0 * u).powf(exponent)
+        } else {
+            (1.0 / (2.0 * (1.0 - u))).powf(exponent)
+        }
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(&a, &b)| {
+                let beta = self.beta(rng);
+
+                0.5 * ((1.0 + beta) * a + (1.0 - beta) * b)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(eta: f32) -> Vec<f32> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = parents();
+
+        SimulatedBinaryCrossover::new(eta)
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test() {
+        let actual = actual(2.0);
+        let expected = vec![0.72079206, 2.9054909, 5.294982, 4.4280024, 6.4032135];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_large_eta_stays_close_to_parent() {
+        let (parent_a, _) = parents();
+
+        for (actual, expected) in actual(1000.0).into_iter().zip(parent_a) {
+            approx::assert_abs_diff_eq!(actual, expected, epsilon = 0.1);
+        }
+    }
+
+    #[test]
+    fn lands_around_parent_a() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let method = SimulatedBinaryCrossover::new(2.0);
+        let parent_a: Chromosome = [1.0].into_iter().collect();
+        let parent_b: Chromosome = [3.0].into_iter().collect();
+
+        let mut children: Vec<_> = (0..10_000)
+            .map(|_| method.crossover(&mut rng, &parent_a, &parent_b)[0])
+            .collect();
+
+        children.sort_by(f32::total_cmp);
+
+        // Median of `beta` is 1, so half of the children land between
+        // `parent_a` and the midpoint, and the other half lands beyond
+        // `parent_a`
+        let median = children[children.len() / 2];
+
+        approx::assert_abs_diff_eq!(median, 1.0, epsilon = 0.05);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::*;
+
+/// Cuts both parents at a single random place, and glues the head of
+/// one parent to the tail of the other one; see `KPointCrossover`.
+#[derive(Clone, Debug, Default)]
+pub struct SinglePointCrossover;
+
+impl SinglePointCrossover {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<G> CrossoverMethod<G> for SinglePointCrossover
+where
+    G: Gene,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        KPointCrossover::new(1).crossover(rng, parent_a, parent_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let (parent_a, parent_b) = parents();
+
+        let actual: Vec<_> = SinglePointCrossover::new()
+            .crossover(&mut rng, &parent_a, &parent_b)
+            .into_iter()
+            .collect();
+
+        let expected = vec![1.0, 2.0, 3.0, 4.0, -5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
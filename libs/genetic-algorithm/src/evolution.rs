@@ -0,0 +1,330 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Runs an optimizer end to end: evaluates each generation with given
+/// fitness function, evolves it, and repeats until `Termination` says
+/// so - so that there's no need to write that loop by hand.
+///
+/// With the `parallel` feature enabled, individuals get evaluated on
+/// many threads (via rayon).
+pub struct Evolution<O, F> {
+    optimizer: O,
+    evaluate: F,
+    termination: Termination,
+}
+
+/// Individual created by `Evolution`, together with its fitness.
+///
+/// Until it gets evaluated, its fitness is `NaN`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Evaluated<G = f32> {
+    chromosome: Chromosome<G>,
+    fitness: f32,
+}
+
+/// Result of `Evolution::run()`.
+#[derive(Clone, Debug)]
+pub struct Outcome<G = f32> {
+    /// The last generation, evaluated
+    pub population: Vec<Evaluated<G>>,
+
+    /// Statistics of each generation, starting with the initial one
+    pub statistics: Vec<Statistics<G>>,
+
+    pub reason: StopReason,
+}
+
+impl<O, F> Evolution<O, F> {
+    pub fn new(optimizer: O, evaluate: F) -> Self {
+        Self {
+            optimizer,
+            evaluate,
+            termination: Termination::default(),
+        }
+    }
+
+    pub fn with_termination(mut self, termination: Termination) -> Self {
+        self.termination = termination;
+        self
+    }
+
+    pub fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    /// Evolves given initial population until any of the termination
+    /// criteria is met.
+    pub fn run<G>(&mut self, rng: &mut dyn RngCore, population: Vec<Chromosome<G>>) -> Outcome<G>
+    where
+        G: Gene,
+        O: Optimizer<Evaluated<G>>,
+        F: Fn(&Chromosome<G>) -> f32 + Sync,
+    {
+        assert!(!population.is_empty());
+        assert!(self.termination.is_set(), "no termination criteria set");
+
+        let mut progress = Progress::new();
+        let mut statistics = Vec::new();
+        let mut population = self.evaluate(population);
+
+        loop {
+            let max_fitness = population
+                .iter()
+                .map(|individual| individual.fitness)
+                .fold(f32::NEG_INFINITY, f32::max);
+
+            progress.record(max_fitness);
+
+            // Termination is checked before evolving, so that the last
+            // generation doesn't needlessly advance optimizer's state
+            // (or consume `rng`) - which is also why its statistics
+            // don't contain optimizer-specific bits (e.g. species)
+            if let Some(reason) = self.termination.check(&progress) {
+                statistics.push(Statistics::new(&population));
+
+                return Outcome {
+                    population,
+                    statistics,
+                    reason,
+                };
+            }
+
+            let (new_population, stats) = self.optimizer.evolve(rng, &population);
+
+            statistics.push(stats);
+
+            let chromosomes = new_population
+                .into_iter()
+                .map(|individual| individual.chromosome)
+                .collect();
+
+            population = self.evaluate(chromosomes);
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    fn evaluate<G>(&self, population: Vec<Chromosome<G>>) -> Vec<Evaluated<G>>
+    where
+        G: Gene,
+        F: Fn(&Chromosome<G>) -> f32 + Sync,
+    {
+        let evaluate = &self.evaluate;
+
+        population
+            .into_par_iter()
+            .map(|chromosome| Evaluated {
+                fitness: evaluate(&chromosome),
+                chromosome,
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn evaluate<G>(&self, population: Vec<Chromosome<G>>) -> Vec<Evaluated<G>>
+    where
+        G: Gene,
+        F: Fn(&Chromosome<G>) -> f32 + Sync,
+    {
+        let evaluate = &self.evaluate;
+
+        population
+            .into_iter()
+            .map(|chromosome| Evaluated {
+                fitness: evaluate(&chromosome),
+                chromosome,
+            })
+            .collect()
+    }
+}
+
+impl<G> Evaluated<G> {
+    pub fn new(chromosome: Chromosome<G>, fitness: f32) -> Self {
+        Self {
+            chromosome,
+            fitness,
+        }
+    }
+}
+
+impl<G> Individual for Evaluated<G>
+where
+    G: Gene,
+{
+    type Gene = G;
+
+    fn create(chromosome: Chromosome<G>) -> Self {
+        Self {
+            chromosome,
+            fitness: f32::NAN,
+        }
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        &self.chromosome
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+}
+
+impl<G> Outcome<G>
+where
+    G: Gene,
+{
+    /// The fittest individual of the last generation.
+    pub fn best(&self) -> &Evaluated<G> {
+        self.population
+            .iter()
+            .max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+            .unwrap()
+    }
+
+    /// Number of evaluated generations.
+    pub fn generations(&self) -> usize {
+        self.statistics.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::time::Duration;
+
+    fn sphere(chromosome: &Chromosome) -> f32 {
+        -chromosome.iter().map(|x| x.powi(2)).sum::<f32>()
+    }
+
+    fn population(rng: &mut dyn RngCore) -> Vec<Chromosome> {
+        spheres(rng, 20, 3)
+            .into_iter()
+            .map(|sphere| sphere.chromosome().clone())
+            .collect()
+    }
+
+    fn run(termination: Termination) -> Outcome {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population(&mut rng);
+
+        Evolution::new(CmaEs::new(1.0), sphere)
+            .with_termination(termination)
+            .run(&mut rng, population)
+    }
+
+    mod run {
+        use super::*;
+
+        #[test]
+        fn max_generations() {
+            let termination = Termination::new().with_max_generations(10);
+            let outcome = run(termination);
+
+            assert_eq!(outcome.reason, StopReason::MaxGenerations);
+            assert_eq!(outcome.generations(), 10);
+            assert_eq!(outcome.population.len(), 20);
+            assert!(outcome
+                .population
+                .iter()
+                .all(|individual| !individual.fitness().is_nan()));
+
+            approx::assert_relative_eq!(
+                outcome.best().fitness(),
+                outcome.statistics[9].max_fitness()
+            );
+        }
+
+        #[test]
+        fn target_fitness() {
+            let termination = Termination::new()
+                .with_target_fitness(-1e-3)
+                .with_max_generations(1000);
+
+            let outcome = run(termination);
+
+            assert_eq!(outcome.reason, StopReason::TargetFitness);
+            assert!(outcome.generations() < 1000);
+            assert!(outcome.best().fitness() >= -1e-3);
+        }
+
+        #[test]
+        fn stagnation() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            // Without mutation, uniform crossover of identical parents
+            // can't ever produce anything new
+            let ga = GeneticAlgorithm::new(
+                TournamentSelection::new(2),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.0, 0.0),
+            );
+
+            let population = vec![[1.0, 2.0].into_iter().collect(); 10];
+
+            let outcome = Evolution::new(ga, sphere)
+                .with_termination(Termination::new().with_stagnation(5))
+                .run(&mut rng, population);
+
+            assert_eq!(outcome.reason, StopReason::Stagnation);
+            assert_eq!(outcome.generations(), 6);
+        }
+
+        #[test]
+        fn time_budget() {
+            let termination = Termination::new().with_time_budget(Duration::ZERO);
+            let outcome = run(termination);
+
+            assert_eq!(outcome.reason, StopReason::TimeBudget);
+            assert_eq!(outcome.generations(), 1);
+        }
+
+        #[test]
+        #[should_panic(expected = "no termination criteria set")]
+        fn given_no_termination_criteria() {
+            run(Termination::new());
+        }
+
+        #[test]
+        fn doesnt_evolve_last_generation() {
+            /// Counts how many times it's been asked to evolve.
+            struct Counting(usize);
+
+            impl Optimizer<Evaluated> for Counting {
+                fn evolve(
+                    &mut self,
+                    _: &mut dyn RngCore,
+                    population: &[Evaluated],
+                ) -> (Vec<Evaluated>, Statistics) {
+                    self.0 += 1;
+
+                    (population.to_vec(), Statistics::new(population))
+                }
+            }
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population(&mut rng);
+
+            let mut evolution = Evolution::new(Counting(0), sphere)
+                .with_termination(Termination::new().with_max_generations(10));
+
+            let outcome = evolution.run(&mut rng, population);
+
+            assert_eq!(outcome.generations(), 10);
+            assert_eq!(evolution.optimizer().0, 9);
+        }
+
+        #[test]
+        fn is_reproducible() {
+            let termination = Termination::new().with_max_generations(20);
+
+            assert_eq!(
+                run(termination).best().chromosome(),
+                run(termination).best().chromosome()
+            );
+        }
+    }
+}
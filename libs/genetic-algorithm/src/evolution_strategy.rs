@@ -0,0 +1,211 @@
+use crate::*;
+
+/// Evolution strategy: each generation, the best `parents` individuals
+/// get mutated into children - there's no crossover at all.
+///
+/// Comes in two flavours (with μ being `parents`, and λ the number of
+/// children):
+///
+/// - (μ,λ) - `Self::comma()` - children replace the entire population,
+///   so the best individual can get lost (which makes it easier to
+///   escape local optima),
+///
+/// - (μ+λ) - `Self::plus()` - parents survive next to their children,
+///   so the best individual is never lost; λ is then the population's
+///   size minus μ.
+///
+/// For `f32` genes, `SelfAdaptiveMutation` is the classic choice of
+/// mutation, as it lets step sizes evolve together with the genes.
+pub struct EvolutionStrategy<G = f32> {
+    parents: usize,
+    plus: bool,
+    mutation_method: Box<dyn MutationMethod<G>>,
+    repair_method: Option<Box<dyn RepairMethod<G>>>,
+}
+
+impl<G> EvolutionStrategy<G>
+where
+    G: Gene,
+{
+    /// Creates a (μ,λ) strategy; see `EvolutionStrategy`.
+    pub fn comma(parents: usize, mutation_method: impl MutationMethod<G> + 'static) -> Self {
+        Self::new(parents, false, mutation_method)
+    }
+
+    /// Creates a (μ+λ) strategy; see `EvolutionStrategy`.
+    pub fn plus(parents: usize, mutation_method: impl MutationMethod<G> + 'static) -> Self {
+        Self::new(parents, true, mutation_method)
+    }
+
+    fn new(parents: usize, plus: bool, mutation_method: impl MutationMethod<G> + 'static) -> Self {
+        assert!(parents > 0);
+
+        Self {
+            parents,
+            plus,
+            mutation_method: Box::new(mutation_method),
+            repair_method: None,
+        }
+    }
+
+    /// Makes each child (and, for the plus strategy, each parent carried
+    /// over) get repaired after mutation; see `GeneticAlgorithm::with_bounds()`.
+    pub fn with_bounds(mut self, repair_method: impl RepairMethod<G> + 'static) -> Self {
+        self.repair_method = Some(Box::new(repair_method));
+        self
+    }
+
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<G>)
+    where
+        I: Individual<Gene = G>,
+    {
+        assert!(!population.is_empty());
+
+        let mut best: Vec<_> = population.iter().collect();
+
+        best.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+
+        let parents = &best[..self.parents.min(population.len())];
+
+        let mut new_population: Vec<_> = if self.plus {
+            parents
+                .iter()
+                .map(|individual| {
+                    let mut parent = individual.chromosome().clone();
+
+                    if let Some(repair_method) = &self.repair_method {
+                        repair_method.repair(rng, &mut parent);
+                    }
+
+                    I::create(parent)
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        while new_population.len() < population.len() {
+            let parent = parents.choose(rng).unwrap();
+            let mut child = parent.chromosome().clone();
+
+            self.mutation_method.mutate(rng, &mut child);
+
+            if let Some(repair_method) = &self.repair_method {
+                repair_method.repair(rng, &mut child);
+            }
+
+            new_population.push(I::create(child));
+        }
+
+        self.mutation_method.advance();
+
+        let mut stats = Statistics::new(population);
+
+        if self.plus {
+            stats.elites_survived = parents.len();
+        }
+
+        (new_population, stats)
+    }
+}
+
+impl<I> Optimizer<I> for EvolutionStrategy<I::Gene>
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<I::Gene>) {
+        EvolutionStrategy::evolve(self, rng, population)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().copied().collect())
+    }
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            individual(&[1.0, 1.0]), // fitness = 2.0
+            individual(&[0.0, 0.0]), // fitness = 0.0
+            individual(&[2.0, 3.0]), // fitness = 5.0
+            individual(&[1.0, 2.0]), // fitness = 3.0
+        ]
+    }
+
+    mod evolve {
+        use super::*;
+
+        #[test]
+        fn comma() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let es = EvolutionStrategy::comma(2, GaussianMutation::new(1.0, 0.1));
+            let (actual, stats) = es.evolve(&mut rng, &population());
+
+            assert_eq!(actual.len(), 4);
+            assert_eq!(stats.elites_survived(), 0);
+
+            // All children are mutated copies of the two best parents
+            for child in &actual {
+                let parent_distance = |parent: &[f32]| {
+                    child
+                        .chromosome()
+                        .iter()
+                        .zip(parent)
+                        .map(|(a, b)| (a - b).abs())
+                        .fold(0.0, f32::max)
+                };
+
+                assert!(child != &individual(&[2.0, 3.0]));
+                assert!(parent_distance(&[2.0, 3.0]) < 0.5 || parent_distance(&[1.0, 2.0]) < 0.5);
+            }
+        }
+
+        #[test]
+        fn plus() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let es = EvolutionStrategy::plus(2, GaussianMutation::new(1.0, 0.1));
+            let (actual, stats) = es.evolve(&mut rng, &population());
+
+            assert_eq!(actual.len(), 4);
+            assert_eq!(stats.elites_survived(), 2);
+            assert_eq!(actual[0], individual(&[2.0, 3.0]));
+            assert_eq!(actual[1], individual(&[1.0, 2.0]));
+        }
+
+        #[test]
+        fn converges() {
+            for plus in [false, true] {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let population = spheres(&mut rng, 30, 3);
+
+                let mut es = if plus {
+                    EvolutionStrategy::plus(5, GaussianMutation::new(1.0, 0.05))
+                } else {
+                    EvolutionStrategy::comma(5, GaussianMutation::new(1.0, 0.05))
+                };
+
+                let actual = optimize(&mut rng, &mut es, population, 100);
+
+                assert!(actual > -0.05, "plus = {}: {}", plus, actual);
+            }
+        }
+
+        #[test]
+        fn honours_bounds() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let bounds = Bounds::uniform(-0.5, 0.5);
+            let es = EvolutionStrategy::comma(2, GaussianMutation::new(1.0, 2.0))
+                .with_bounds(bounds.clone());
+            let (actual, _) = es.evolve(&mut rng, &population());
+
+            assert!(actual
+                .iter()
+                .all(|individual| individual.chromosome().is_within(&bounds)));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt::Debug;
+
+/// Type that can be stored inside a `Chromosome` - e.g. `f32` for neural
+/// network weights, `bool` for bit strings, `i32` for integer parameters
+/// or `usize` for permutations (such as the order of visited cities).
+pub trait Gene: Clone + Debug + PartialEq + Send + Sync + 'static {
+    /// Returns where the gene lies on the number line - e.g. 0 or 1 for
+    /// `bool`; genes get sorted by it when measuring diversity of the
+    /// population (see `Statistics::diversity()`).
+    fn position(&self) -> f64;
+
+    /// Returns how far apart both genes are.
+    fn distance(&self, other: &Self) -> f32 {
+        (self.position() - other.position()).abs() as f32
+    }
+}
+
+impl Gene for bool {
+    fn position(&self) -> f64 {
+        if *self {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+macro_rules! impl_numeric_gene {
+    ($($ty:ty),*) => {
+        $(
+            impl Gene for $ty {
+                fn position(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_gene!(f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod distance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(1.5f32.distance(&-1.0), 2.5);
+            assert_eq!(3u8.distance(&7), 4.0);
+            assert_eq!((-2i32).distance(&2), 4.0);
+            assert_eq!(true.distance(&true), 0.0);
+            assert_eq!(true.distance(&false), 1.0);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::*;
+
+pub trait Individual {
+    /// Type of genes inside the chromosome; that's `f32` for most
+    /// problems, see `Gene` for others.
+    type Gene: Gene;
+
+    fn create(chromosome: Chromosome<Self::Gene>) -> Self;
+    fn chromosome(&self) -> &Chromosome<Self::Gene>;
+    fn fitness(&self) -> f32;
+
+    /// How much the individual violates the problem's constraints - zero
+    /// when it satisfies all of them (the default); see `Penalty`.
+    fn violation(&self) -> f32 {
+        0.0
+    }
+}
+
+#[cfg(test)]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TestIndividual {
+    /// For tests that require access to chromosome
+    WithChromosome { chromosome: Chromosome },
+
+    /// For tests that don't require access to chromosome
+    WithFitness { fitness: f32 },
+}
+
+#[cfg(test)]
+impl TestIndividual {
+    pub fn new(fitness: f32) -> Self {
+        Self::WithFitness { fitness }
+    }
+}
+
+#[cfg(test)]
+impl Individual for TestIndividual {
+    type Gene = f32;
+
+    fn create(chromosome: Chromosome) -> Self {
+        Self::WithChromosome { chromosome }
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        match self {
+            Self::WithChromosome { chromosome } => chromosome,
+
+            Self::WithFitness { .. } => {
+                panic!("not supported for TestIndividual::WithFitness")
+            }
+        }
+    }
+
+    fn fitness(&self) -> f32 {
+        match self {
+            Self::WithChromosome { chromosome } => {
+                chromosome.iter().sum()
+
+                // ^ the simplest fitness function ever - we're just
+                // summing all the genes together
+            }
+
+            Self::WithFitness { fitness } => *fitness,
+        }
+    }
+}
@@ -0,0 +1,355 @@
+use crate::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Which islands of `IslandModel` send their migrants where.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrationTopology {
+    /// Island `n` sends its migrants to island `n + 1` (and the last
+    /// island sends them back to the first one)
+    #[default]
+    Ring,
+
+    /// Each island sends its migrants to all the other islands
+    FullyConnected,
+}
+
+/// Evolves a few separate populations (islands) with the same genetic
+/// algorithm; every `interval` generations the best `migrants`
+/// individuals of each island get copied over to its neighbours,
+/// replacing their children.
+///
+/// Since islands evolve independently, each of them can explore a
+/// different part of the search space - migration then spreads good
+/// solutions around, without letting a single one take over everything
+/// right away.
+///
+/// With the `parallel` feature enabled, each island evolves on its own
+/// thread (via rayon); either way each island gets its own random
+/// number generator, seeded from the one passed into `Self::evolve()` -
+/// so a run is reproducible from a single seed, no matter how many
+/// threads there are.
+pub struct IslandModel<S, G = f32> {
+    ga: GeneticAlgorithm<S, G>,
+    topology: MigrationTopology,
+    interval: usize,
+    migrants: usize,
+    generation: usize,
+}
+
+impl<S, G> IslandModel<S, G>
+where
+    S: SelectionMethod + Sync,
+    G: Gene,
+{
+    pub fn new(
+        ga: GeneticAlgorithm<S, G>,
+        topology: MigrationTopology,
+        interval: usize,
+        migrants: usize,
+    ) -> Self {
+        assert!(interval > 0);
+
+        Self {
+            ga,
+            topology,
+            interval,
+            migrants,
+            generation: 0,
+        }
+    }
+
+    /// Number of generations evolved so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Evolves each island once (and then migrates individuals between
+    /// them, if it's time to); returns the new islands, together with
+    /// their statistics.
+    pub fn evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        islands: &[Vec<I>],
+    ) -> (Vec<Vec<I>>, Vec<Statistics<G>>)
+    where
+        I: Individual<Gene = G> + Send + Sync,
+    {
+        assert!(!islands.is_empty());
+
+        let seeds: Vec<u64> = islands.iter().map(|_| rng.gen()).collect();
+
+        let (mut new_islands, stats): (Vec<_>, Vec<_>) =
+            self.evolve_islands(islands, seeds).into_iter().unzip();
+
+        // Islands share the mutation method, so it's advanced here (once
+        // per generation) instead of by each island - otherwise e.g.
+        // `AnnealedMutation` would anneal faster the more islands there
+        // are, and (with `parallel`) depend on the order threads run in
+        self.ga.mutation_method.advance();
+        self.generation += 1;
+
+        if self.generation % self.interval == 0 {
+            self.migrate(islands, &mut new_islands);
+        }
+
+        (new_islands, stats)
+    }
+
+    #[cfg(feature = "parallel")]
+    fn evolve_islands<I>(&self, islands: &[Vec<I>], seeds: Vec<u64>) -> Vec<(Vec<I>, Statistics<G>)>
+    where
+        I: Individual<Gene = G> + Send + Sync,
+    {
+        islands
+            .par_iter()
+            .zip(seeds)
+            .map(|(island, seed)| self.ga.breed(&mut ChaCha8Rng::seed_from_u64(seed), island))
+            .collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    fn evolve_islands<I>(&self, islands: &[Vec<I>], seeds: Vec<u64>) -> Vec<(Vec<I>, Statistics<G>)>
+    where
+        I: Individual<Gene = G> + Send + Sync,
+    {
+        islands
+            .iter()
+            .zip(seeds)
+            .map(|(island, seed)| self.ga.breed(&mut ChaCha8Rng::seed_from_u64(seed), island))
+            .collect()
+    }
+
+    /// Copies the best individuals of `islands` (which have already been
+    /// evaluated) into `new_islands`, replacing individuals from the end
+    /// - that is children, since elites (if any) come first.
+    fn migrate<I>(&self, islands: &[Vec<I>], new_islands: &mut [Vec<I>])
+    where
+        I: Individual,
+    {
+        let count = islands.len();
+        let mut replaced = vec![0; count];
+
+        for (source, island) in islands.iter().enumerate() {
+            let mut best: Vec<_> = island.iter().collect();
+
+            best.sort_by(|a, b| b.fitness().total_cmp(&a.fitness()));
+            best.truncate(self.migrants);
+
+            let destinations: Vec<_> = match self.topology {
+                MigrationTopology::Ring => vec![(source + 1) % count],
+                MigrationTopology::FullyConnected => (0..count).collect(),
+            };
+
+            for destination in destinations {
+                if destination == source {
+                    continue;
+                }
+
+                for migrant in &best {
+                    let island = &mut new_islands[destination];
+
+                    if replaced[destination] >= island.len() {
+                        break;
+                    }
+
+                    let index = island.len() - 1 - replaced[destination];
+
+                    island[index] = I::create(migrant.chromosome().clone());
+                    replaced[destination] += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().copied().collect())
+    }
+
+    fn islands() -> Vec<Vec<TestIndividual>> {
+        vec![
+            vec![individual(&[1.0, 1.0]), individual(&[9.0, 9.0])],
+            vec![individual(&[2.0, 2.0]), individual(&[3.0, 3.0])],
+            vec![individual(&[4.0, 0.0]), individual(&[5.0, 0.0])],
+        ]
+    }
+
+    fn model(topology: MigrationTopology, interval: usize) -> IslandModel<RouletteWheelSelection> {
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            GaussianMutation::new(0.5, 0.1),
+        );
+
+        IslandModel::new(ga, topology, interval, 1)
+    }
+
+    mod evolve {
+        use super::*;
+
+        #[test]
+        fn ring() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut model = model(MigrationTopology::Ring, 1);
+            let (islands, stats) = model.evolve(&mut rng, &islands());
+
+            assert_eq!(islands.len(), 3);
+            assert_eq!(stats.len(), 3);
+            assert_eq!(model.generation(), 1);
+
+            // Each island's best individual lands at the end of the next
+            // island
+            assert_eq!(islands[1][1], individual(&[9.0, 9.0]));
+            assert_eq!(islands[2][1], individual(&[3.0, 3.0]));
+            assert_eq!(islands[0][1], individual(&[5.0, 0.0]));
+        }
+
+        #[test]
+        fn fully_connected() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut model = model(MigrationTopology::FullyConnected, 1);
+            let (islands, _) = model.evolve(&mut rng, &islands());
+
+            // Each island receives the best individuals of both other
+            // islands, which replaces all of its children
+            assert_eq!(
+                islands[0],
+                vec![individual(&[5.0, 0.0]), individual(&[3.0, 3.0])]
+            );
+
+            assert_eq!(
+                islands[1],
+                vec![individual(&[5.0, 0.0]), individual(&[9.0, 9.0])]
+            );
+
+            assert_eq!(
+                islands[2],
+                vec![individual(&[3.0, 3.0]), individual(&[9.0, 9.0])]
+            );
+        }
+
+        #[test]
+        fn migrates_only_every_interval() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut model = model(MigrationTopology::Ring, 2);
+            let (islands, _) = model.evolve(&mut rng, &islands());
+
+            assert!(!islands[1].contains(&individual(&[9.0, 9.0])));
+
+            let best = islands[0]
+                .iter()
+                .max_by(|a, b| a.fitness().total_cmp(&b.fitness()))
+                .unwrap()
+                .clone();
+
+            let (islands, _) = model.evolve(&mut rng, &islands);
+
+            assert_eq!(model.generation(), 2);
+            assert_eq!(islands[1][1], best);
+        }
+
+        #[test]
+        fn is_reproducible() {
+            let run = || {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+                let mut model = model(MigrationTopology::Ring, 3);
+                let mut islands = islands();
+
+                for _ in 0..10 {
+                    (islands, _) = model.evolve(&mut rng, &islands);
+                }
+
+                islands
+            };
+
+            assert_eq!(run(), run());
+        }
+
+        #[test]
+        fn is_reproducible_with_annealed_mutation() {
+            let run = || {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+                let ga = GeneticAlgorithm::new(
+                    TournamentSelection::new(2),
+                    UniformCrossover::new(),
+                    AnnealedMutation::new(0.5, 1.0, 0.9),
+                );
+
+                let mut model = IslandModel::new(ga, MigrationTopology::Ring, 3, 1);
+
+                let mut islands: Vec<_> = (0..8)
+                    .map(|_| {
+                        (0..20)
+                            .map(|_| individual(&[rng.gen_range(0.0..=1.0); 4]))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+
+                for _ in 0..10 {
+                    (islands, _) = model.evolve(&mut rng, &islands);
+                }
+
+                islands
+            };
+
+            let expected = run();
+
+            #[cfg(feature = "parallel")]
+            for threads in [1, 2, 4] {
+                let pool = rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .unwrap();
+
+                assert_eq!(pool.install(run), expected, "threads = {}", threads);
+            }
+
+            #[cfg(not(feature = "parallel"))]
+            assert_eq!(run(), expected);
+        }
+
+        #[test]
+        fn advances_mutation_once_per_generation() {
+            use std::sync::atomic::{AtomicUsize, Ordering};
+            use std::sync::Arc;
+
+            #[derive(Debug)]
+            struct CountingMutation(Arc<AtomicUsize>);
+
+            impl MutationMethod for CountingMutation {
+                fn mutate(&self, _: &mut dyn RngCore, _: &mut Chromosome) {}
+
+                fn advance(&self) {
+                    self.0.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let advances = Arc::new(AtomicUsize::new(0));
+
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                CountingMutation(advances.clone()),
+            );
+
+            let mut model = IslandModel::new(ga, MigrationTopology::Ring, 3, 1);
+            let mut islands = islands();
+
+            for _ in 0..5 {
+                (islands, _) = model.evolve(&mut rng, &islands);
+            }
+
+            assert_eq!(advances.load(Ordering::Relaxed), 5);
+        }
+    }
+}
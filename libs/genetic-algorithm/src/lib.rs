@@ -1,251 +1,182 @@
-// TODO implement rank selection roulette wheel selection is fine now
+pub use self::{
+    bounds::*, checkpoint::*, chromosome::*, cma_es::*, crossover::*, evolution::*,
+    evolution_strategy::*, gene::*, individual::*, islands::*, mutation::*, niching::*, nsga2::*,
+    optimizer::*, penalty::*, replacement::*, selection::*, statistics::*, termination::*,
+};
+
+use rand::distributions::WeightedError;
 use rand::seq::SliceRandom;
 use rand::Rng;
 use rand::RngCore;
-
-// TRAITS
-pub trait CrossoverMethod {
-    fn crossover(
-        &self,
-        rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome;
-}
-
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
-}
-
-pub trait Individual {
-    fn create(chromosome: Chromosome) -> Self;
-    fn chromosome(&self) -> &Chromosome;
-    fn fitness(&self) -> f32;
-}
-
-pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual;
-}
-
-// STRUCTS
-#[derive(Clone, Debug)]
-pub struct Chromosome {
-    genes: Vec<f32>,
-}
-
-impl Chromosome {
-    pub fn len(&self) -> usize {
-        self.genes.len()
-    }
-
-    pub fn iter(&self) -> impl Iterator<Item = &f32> {
-        self.genes.iter()
-    }
-
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
-        self.genes.iter_mut()
-    }
-}
-
-impl std::ops::Index<usize> for Chromosome {
-    type Output = f32;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.genes[index]
-    }
-}
-
-impl std::iter::FromIterator<f32> for Chromosome {
-    fn from_iter<T: IntoIterator<Item = f32>>(iter: T) -> Self {
-        Self {
-            genes: iter.into_iter().collect(),
-        }
-    }
-}
-
-impl std::iter::IntoIterator for Chromosome {
-    type Item = f32;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
-    // type IntoIter = impl Iterator<Item = f32>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.genes.into_iter()
-    }
-}
-
-pub struct RouletteWheelSelection;
-
-impl RouletteWheelSelection {
-    pub fn new() -> Self {
-        Self
-    }
-}
-
-impl SelectionMethod for RouletteWheelSelection {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
-    where
-        I: Individual,
-    {
-        population
-            .choose_weighted(rng, |individual| individual.fitness())
-            .expect("got an empty population")
-    }
-}
-
-pub struct GeneticAlgorithm<S> {
+use rand_distr::{Cauchy, StandardNormal};
+use std::fmt::Debug;
+
+mod bounds;
+mod checkpoint;
+mod chromosome;
+mod cma_es;
+mod crossover;
+mod evolution;
+mod evolution_strategy;
+mod gene;
+mod individual;
+mod islands;
+mod mutation;
+mod niching;
+mod nsga2;
+mod optimizer;
+mod penalty;
+mod replacement;
+mod selection;
+mod statistics;
+mod termination;
+
+pub struct GeneticAlgorithm<S, G = f32> {
     selection_method: S,
-    crossover_method: Box<dyn CrossoverMethod>,
-    mutation_method: Box<dyn MutationMethod>,
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    mutation_method: Box<dyn MutationMethod<G>>,
+    replacement: Replacement,
+    niching: Niching,
+    repair_method: Option<Box<dyn RepairMethod<G>>>,
+    penalty: Penalty,
 }
 
-impl<S> GeneticAlgorithm<S>
+impl<S, G> GeneticAlgorithm<S, G>
 where
     S: SelectionMethod,
+    G: Gene,
 {
     pub fn new(
         selection_method: S,
-        crossover_method: impl CrossoverMethod + 'static,
-        mutation_method: impl MutationMethod + 'static,
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
     ) -> Self {
         Self {
             selection_method,
             crossover_method: Box::new(crossover_method),
             mutation_method: Box::new(mutation_method),
+            replacement: Replacement::default(),
+            niching: Niching::default(),
+            repair_method: None,
+            penalty: Penalty::default(),
         }
     }
 
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
-    where
-        I: Individual,
-    {
-        assert!(!population.is_empty());
-
-        let new_population = (0..population.len())
-            .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
-
-                let parent_b = self.selection_method.select(rng, population).chromosome();
-
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
-
-                self.mutation_method.mutate(rng, &mut child);
+    /// Changes how the next generation gets built; by default the
+    /// entire population is replaced by children.
+    pub fn with_replacement(mut self, replacement: Replacement) -> Self {
+        self.replacement = replacement;
+        self
+    }
 
-                I::create(child)
-            })
-            .collect();
+    /// Makes individuals share their fitness within niches before
+    /// parents get selected, which keeps the population diverse; by
+    /// default parents are selected by their raw fitness.
+    pub fn with_niching(mut self, niching: Niching) -> Self {
+        self.niching = niching;
+        self
+    }
 
-        let stats = Statistics::new(population);
+    /// Makes each child get repaired after crossover and mutation, so
+    /// that it stays within the problem's bounds no matter which
+    /// operators are used (see `Bounds`); by default genes are unbounded.
+    ///
+    /// Elites get repaired, too - in case the initial population didn't
+    /// start within the bounds.
+    pub fn with_bounds(mut self, repair_method: impl RepairMethod<G> + 'static) -> Self {
+        self.repair_method = Some(Box::new(repair_method));
+        self
+    }
 
-        (new_population, stats)
+    /// Makes individuals that violate the problem's constraints get
+    /// penalized before elites and parents are picked; by default
+    /// constraints are ignored.
+    pub fn with_penalty(mut self, penalty: Penalty) -> Self {
+        self.penalty = penalty;
+        self
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct UniformCrossover;
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<G>)
+    where
+        I: Individual<Gene = G>,
+    {
+        let result = self.breed(rng, population);
 
-impl UniformCrossover {
-    pub fn new() -> Self {
-        Self
+        self.mutation_method.advance();
+
+        result
     }
-}
 
-impl CrossoverMethod for UniformCrossover {
-    fn crossover(
+    /// Same as `Self::evolve()`, but doesn't advance the mutation method
+    /// - so that `IslandModel` can advance it once for all islands.
+    pub(crate) fn breed<I>(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome {
-        assert_eq!(parent_a.len(), parent_b.len());
-
-        let parent_a = parent_a.iter();
-        let parent_b = parent_b.iter();
-
-        parent_a
-            .zip(parent_b)
-            .map(|(&a, &b)| if rng.gen_bool(0.5) { a } else { b })
-            .collect()
-    }
-}
+        population: &[I],
+    ) -> (Vec<I>, Statistics<G>)
+    where
+        I: Individual<Gene = G>,
+    {
+        assert!(!population.is_empty());
 
-#[derive(Clone, Debug)]
-pub struct GaussianMutation {
-    /// Probability of changing a gene:
-    /// - 0.0 = no genes will be touched
-    /// - 1.0 = all genes will be touched
-    chance: f32,
-
-    /// Magnitude of that change:
-    /// - 0.0 = touched genes will not be modified
-    /// - 3.0 = touched genes will be += or -= by at most 3.0
-    coeff: f32,
-}
+        let survivors = self.replacement.survivors(population.len());
+        let children = population.len() - survivors;
 
-impl GaussianMutation {
-    pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!(chance >= 0.0 && chance <= 1.0);
+        let fitnesses: Vec<_> = population
+            .iter()
+            .map(|individual| self.penalty.apply(individual))
+            .collect();
 
-        Self { chance, coeff }
-    }
-}
+        let mut best: Vec<_> = (0..population.len()).collect();
 
-impl MutationMethod for GaussianMutation {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
-        for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+        best.sort_by(|&a, &b| fitnesses[b].total_cmp(&fitnesses[a]));
 
-            if rng.gen_bool(self.chance as _) {
-                *gene += sign * self.coeff * rng.gen::<f32>();
-            }
-        }
-    }
-}
+        let mut new_population: Vec<_> = best[..survivors]
+            .iter()
+            .map(|&individual| {
+                let mut elite = population[individual].chromosome().clone();
 
-#[derive(Clone, Debug)]
-pub struct Statistics {
-    min_fitness: f32,
-    max_fitness: f32,
-    avg_fitness: f32,
-}
+                if let Some(repair_method) = &self.repair_method {
+                    repair_method.repair(rng, &mut elite);
+                }
 
-impl Statistics {
-    fn new<I>(population: &[I]) -> Self
-    where
-        I: Individual,
-    {
-        assert!(!population.is_empty());
+                I::create(elite)
+            })
+            .collect();
 
-        let mut min_fitness = population[0].fitness();
-        let mut max_fitness = min_fitness;
-        let mut sum_fitness = 0.0;
+        let species = self.niching.species(population, &fitnesses);
 
-        for individual in population {
-            let fitness = individual.fitness();
+        let shared_fitnesses = self
+            .niching
+            .shared_fitnesses(population, &fitnesses, &species);
 
-            min_fitness = min_fitness.min(fitness);
-            max_fitness = max_fitness.max(fitness);
-            sum_fitness += fitness;
-        }
+        // Parents are picked one by one, but the selection method knows
+        // how many of them there'll be, so that methods such as
+        // `StochasticUniversalSampling` can see the entire batch
+        let mut select = self
+            .selection_method
+            .sampler(&shared_fitnesses, 2 * children);
 
-        Self {
-            min_fitness,
-            max_fitness,
-            avg_fitness: sum_fitness / (population.len() as f32),
-        }
-    }
+        new_population.extend((0..children).map(|_| {
+            let parent_a = population[select(rng)].chromosome();
+            let parent_b = population[select(rng)].chromosome();
 
-    pub fn min_fitness(&self) -> f32 {
-        self.min_fitness
-    }
+            let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
 
-    pub fn max_fitness(&self) -> f32 {
-        self.max_fitness
-    }
+            self.mutation_method.mutate(rng, &mut child);
+
+            if let Some(repair_method) = &self.repair_method {
+                repair_method.repair(rng, &mut child);
+            }
+
+            I::create(child)
+        }));
 
-    pub fn avg_fitness(&self) -> f32 {
-        self.avg_fitness
+        let mut stats = Statistics::new(population);
+        stats.elites_survived = survivors;
+        stats.species = species.iter().map(|species| species.len()).collect();
+
+        (new_population, stats)
     }
 }
 
@@ -254,393 +185,478 @@ mod tests {
     use super::*;
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
-    use std::collections::BTreeMap;
 
-    fn actual(chance: f32, coeff: f32) -> Vec<f32> {
-        let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+    fn individual(genes: &[f32]) -> TestIndividual {
+        let chromosome = genes.iter().cloned().collect();
+
+        TestIndividual::create(chromosome)
+    }
 
+    #[test]
+    fn test() {
         let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        GaussianMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection::new(),
+            UniformCrossover::new(),
+            UniformMutation::new(0.5, 0.5),
+        );
+
+        let mut population = vec![
+            individual(&[0.0, 0.0, 0.0]), // fitness = 0.0
+            individual(&[1.0, 1.0, 1.0]), // fitness = 3.0
+            individual(&[1.0, 2.0, 1.0]), // fitness = 4.0
+            individual(&[1.0, 2.0, 4.0]), // fitness = 7.0
+        ];
+
+        // We're running `.evolve()` a few times, so that the
+        // differences between initial and output population are
+        // easier to spot.
+        //
+        // No particular reason for a number of 10 - this test would
+        // be fine for 5, 20 or even 1000 generations; the only thing
+        // that'd change is the *magnitude* of difference between
+        // initial and output population.
+        for _ in 0..10 {
+            (population, _) = ga.evolve(&mut rng, &population);
+        }
 
-        child.into_iter().collect()
-    }
+        let expected_population = vec![
+            individual(&[0.44769490, 2.0648358, 4.3058133]),
+            individual(&[1.21268670, 1.5538777, 2.8869110]),
+            individual(&[1.06176780, 2.2657390, 4.4287640]),
+            individual(&[0.95909685, 2.4618788, 4.0247330]),
+        ];
 
-    //#[cfg(test)]
-    //#[derive(Clone, Debug)]
-    //pub struct TestIndividual {
-    //    fitness: f32,
-    //}
-
-    // #[cfg(test)]
-    // impl TestIndividual {
-    //     pub fn new(fitness: f32) -> Self {
-    //         Self { fitness }
-    //     }
-    // }
-
-    // #[cfg(test)]
-    // impl Individual for TestIndividual {
-    //     fn fitness(&self) -> f32 {
-    //         self.fitness
-    //     }
-
-    //     fn chromosome(&self) -> &Chromosome {
-    //         panic!("not supported for TestIndividual")
-    //     }
-
-    //     fn create(chromosome: Chromosome) -> Self {
-    //         todo!()
-    //     }
-    // }
-    #[cfg(test)]
-    #[derive(Clone, Debug, PartialEq)]
-    pub enum TestIndividual {
-        /// For tests that require access to chromosome
-        WithChromosome { chromosome: Chromosome },
-
-        /// For tests that don't require access to chromosome
-        WithFitness { fitness: f32 },
+        assert_eq!(population, expected_population);
     }
 
-    #[cfg(test)]
-    impl PartialEq for Chromosome {
-        fn eq(&self, other: &Self) -> bool {
-            approx::relative_eq!(self.genes.as_slice(), other.genes.as_slice(),)
+    mod replacement {
+        use super::*;
+
+        fn ga(replacement: Replacement) -> GeneticAlgorithm<RouletteWheelSelection> {
+            GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(1.0, 0.5),
+            )
+            .with_replacement(replacement)
         }
-    }
 
-    #[cfg(test)]
-    impl TestIndividual {
-        pub fn new(fitness: f32) -> Self {
-            Self::WithFitness { fitness }
+        fn population() -> Vec<TestIndividual> {
+            vec![
+                individual(&[1.0, 1.0, 1.0]), // fitness = 3.0
+                individual(&[0.0, 0.0, 0.0]), // fitness = 0.0
+                individual(&[1.0, 2.0, 4.0]), // fitness = 7.0
+                individual(&[1.0, 2.0, 1.0]), // fitness = 4.0
+            ]
         }
-    }
 
-    #[cfg(test)]
-    impl Individual for TestIndividual {
-        fn create(chromosome: Chromosome) -> Self {
-            Self::WithChromosome { chromosome }
+        #[test]
+        fn generational() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let (population, stats) = ga(Replacement::Generational).evolve(&mut rng, &population());
+
+            assert_eq!(population.len(), 4);
+            assert_eq!(stats.elites_survived(), 0);
+            assert!(!population.contains(&individual(&[1.0, 2.0, 4.0])));
         }
 
-        fn chromosome(&self) -> &Chromosome {
-            match self {
-                Self::WithChromosome { chromosome } => chromosome,
+        #[test]
+        fn elitist() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let ga = ga(Replacement::Elitist { elites: 1 });
+            let (population, stats) = ga.evolve(&mut rng, &population());
 
-                Self::WithFitness { .. } => {
-                    panic!("not supported for TestIndividual::WithFitness")
-                }
-            }
+            assert_eq!(population.len(), 4);
+            assert_eq!(population[0], individual(&[1.0, 2.0, 4.0]));
+            assert_eq!(stats.elites_survived(), 1);
         }
 
-        fn fitness(&self) -> f32 {
-            match self {
-                Self::WithChromosome { chromosome } => {
-                    chromosome.iter().sum()
+        #[test]
+        fn elitist_never_loses_the_best_individual() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let ga = ga(Replacement::Elitist { elites: 1 });
+            let mut population = population();
+            let mut best_fitness = 0.0;
 
-                    // ^ the simplest fitness function ever - we're just
-                    // summing all the genes together
-                }
+            for _ in 0..20 {
+                let stats;
+                (population, stats) = ga.evolve(&mut rng, &population);
 
-                Self::WithFitness { fitness } => *fitness,
+                assert!(stats.max_fitness() >= best_fitness);
+                best_fitness = stats.max_fitness();
             }
         }
-    }
 
-    mod selection {
-        use super::*;
+        #[test]
+        fn steady_state() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let ga = ga(Replacement::SteadyState { replaced: 1 });
+            let (population, stats) = ga.evolve(&mut rng, &population());
+
+            assert_eq!(population.len(), 4);
+            assert_eq!(
+                &population[..3],
+                &[
+                    individual(&[1.0, 2.0, 4.0]),
+                    individual(&[1.0, 2.0, 1.0]),
+                    individual(&[1.0, 1.0, 1.0]),
+                ]
+            );
+            assert_eq!(stats.elites_survived(), 3);
+        }
 
         #[test]
-        fn wheel() {
-            let method = RouletteWheelSelection::new();
+        fn steady_state_replacing_nobody() {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-            let population = vec![
-                TestIndividual::new(2.0),
-                TestIndividual::new(1.0),
-                TestIndividual::new(4.0),
-                TestIndividual::new(3.0),
-            ];
-
-            let actual_histogram: BTreeMap<i32, _> = (0..1000)
-                .map(|_| method.select(&mut rng, &population))
-                .fold(Default::default(), |mut histogram, individual| {
-                    // as _ means "compiler, pretty please infer what type is required and cast this value into it".
-                    // since we've declared the btreemap key to be i32 the type is i32
-                    *histogram.entry(individual.fitness() as _).or_default() += 1;
-
-                    histogram
-                });
-
-            let expected_histogram = maplit::btreemap! {
-                // fitness => how many times this fitness has been chosen
-                1 => 98,
-                2 => 202,
-                3 => 278,
-                4 => 422,
-            };
+            // Selects no parents at all, which `StochasticUniversalSampling`
+            // has to put up with
+            let ga = GeneticAlgorithm::new(
+                StochasticUniversalSampling::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(1.0, 0.5),
+            )
+            .with_replacement(Replacement::SteadyState { replaced: 0 });
 
-            assert_eq!(actual_histogram, expected_histogram);
-        }
-    }
+            let (population, _) = ga.evolve(&mut rng, &population());
 
-    fn chromosome() -> Chromosome {
-        Chromosome {
-            genes: vec![3.0, 1.0, 2.0],
+            assert_eq!(population.len(), 4);
+            assert_eq!(population[0], individual(&[1.0, 2.0, 4.0]));
         }
     }
 
-    mod len {
+    mod niching {
         use super::*;
 
-        #[test]
-        fn test() {
-            assert_eq!(chromosome().len(), 3);
-        }
-    }
+        /// Two peaks: a lower one at `x = -1`, and a higher one at `x = 1`.
+        #[derive(Clone, Debug)]
+        struct Peaks(Chromosome);
 
-    mod iter {
-        use super::*;
+        impl Individual for Peaks {
+            type Gene = f32;
 
-        #[test]
-        fn test() {
-            let chromosome = chromosome();
-            let genes: Vec<_> = chromosome.iter().collect();
+            fn create(chromosome: Chromosome) -> Self {
+                Self(chromosome)
+            }
+
+            fn chromosome(&self) -> &Chromosome {
+                &self.0
+            }
+
+            fn fitness(&self) -> f32 {
+                let x = self.0[0];
+                let peak =
+                    |center: f32, height: f32| height * (1.0 - (x - center).powi(2)).max(0.0);
 
-            assert_eq!(genes.len(), 3);
-            assert_eq!(genes[0], &3.0);
-            assert_eq!(genes[1], &1.0);
-            assert_eq!(genes[2], &2.0);
+                peak(-1.0, 0.9).max(peak(1.0, 1.0)) + 0.01
+            }
         }
-    }
 
-    mod iter_mut {
-        use super::*;
+        /// Evolves the population for a while, and returns how many
+        /// individuals ended up around the lower and the higher peak.
+        fn actual(niching: Niching) -> (usize, usize, Statistics) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        #[test]
-        fn test() {
-            let mut chromosome = chromosome();
+            let ga = GeneticAlgorithm::new(
+                RouletteWheelSelection::new(),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.05),
+            )
+            .with_niching(niching);
 
-            chromosome.iter_mut().for_each(|gene| {
-                *gene *= 10.0;
-            });
+            let mut population: Vec<_> = (0..20)
+                .map(|_| Peaks::create([rng.gen_range(-2.0..=2.0)].into_iter().collect()))
+                .collect();
 
-            let genes: Vec<_> = chromosome.iter().collect();
+            for _ in 0..100 {
+                (population, _) = ga.evolve(&mut rng, &population);
+            }
 
-            assert_eq!(genes.len(), 3);
-            assert_eq!(genes[0], &30.0);
-            assert_eq!(genes[1], &10.0);
-            assert_eq!(genes[2], &20.0);
-        }
-    }
+            let (_, stats) = ga.evolve(&mut rng, &population);
+            let count = |center: f32| {
+                population
+                    .iter()
+                    .filter(|p| (p.0[0] - center).abs() < 0.5)
+                    .count()
+            };
 
-    mod index {
-        use super::*;
+            (count(-1.0), count(1.0), stats)
+        }
 
         #[test]
-        fn test() {
-            let chromosome = chromosome();
+        fn given_no_niching_converges_onto_a_single_peak() {
+            let (lower, higher, stats) = actual(Niching::None);
 
-            assert_eq!(chromosome[0], 3.0);
-            assert_eq!(chromosome[1], 1.0);
-            assert_eq!(chromosome[2], 2.0);
+            assert_eq!((lower, higher), (0, 20));
+            assert!(stats.species().is_empty());
         }
-    }
-
-    mod from_iterator {
-        use super::*;
 
         #[test]
-        fn test() {
-            let chromosome: Chromosome = vec![3.0, 1.0, 2.0].into_iter().collect();
+        fn given_fitness_sharing_keeps_both_peaks() {
+            let (lower, higher, _) = actual(Niching::FitnessSharing {
+                radius: 1.0,
+                alpha: 1.0,
+            });
 
-            assert_eq!(chromosome[0], 3.0);
-            assert_eq!(chromosome[1], 1.0);
-            assert_eq!(chromosome[2], 2.0);
+            assert_eq!((lower, higher), (8, 12));
         }
-    }
-
-    mod into_iterator {
-        use super::*;
 
         #[test]
-        fn test() {
-            let chromosome = Chromosome {
-                genes: vec![3.0, 1.0, 2.0],
-            };
-
-            let genes: Vec<_> = chromosome.into_iter().collect();
+        fn given_speciation_keeps_both_peaks() {
+            let (lower, higher, stats) = actual(Niching::Speciation { threshold: 0.5 });
 
-            assert_eq!(genes.len(), 3);
-            assert_eq!(genes[0], 3.0);
-            assert_eq!(genes[1], 1.0);
-            assert_eq!(genes[2], 2.0);
+            assert_eq!((lower, higher), (9, 7));
+            assert_eq!(stats.species(), [7, 6, 7]);
         }
     }
 
-    mod crossover {
+    mod bounds {
         use super::*;
 
         #[test]
         fn test() {
-            let mut rng = ChaCha8Rng::from_seed(Default::default());
-            let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
-
-            let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+            let bounds = Bounds::per_gene([(0.0, 1.0), (-1.0, 0.0), (0.0, 0.0)]);
+
+            for repair in [
+                Repair::Clamp,
+                Repair::Reflect,
+                Repair::Wrap,
+                Repair::Resample,
+            ] {
+                let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+                // Blend crossover and large mutations push genes far
+                // outside the bounds
+                let ga = GeneticAlgorithm::new(
+                    TournamentSelection::new(2),
+                    BlendCrossover::new(1.0),
+                    GaussianMutation::new(1.0, 5.0),
+                )
+                .with_bounds(bounds.clone().with_repair(repair));
+
+                let mut population = vec![individual(&[0.5, -0.5, 0.0]); 10];
+
+                for _ in 0..20 {
+                    (population, _) = ga.evolve(&mut rng, &population);
+
+                    assert!(
+                        population
+                            .iter()
+                            .all(|individual| individual.chromosome().is_within(&bounds)),
+                        "{:?}",
+                        repair
+                    );
+                }
+            }
+        }
 
-            let child = UniformCrossover::new().crossover(&mut rng, &parent_a, &parent_b);
+        #[test]
+        fn repairs_elites() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let bounds = Bounds::uniform(-1.0, 1.0);
 
-            // Number of genes different between `child` and `parent_a`
-            let diff_a = child.iter().zip(parent_a).filter(|(c, p)| *c != p).count();
+            let ga = GeneticAlgorithm::new(
+                TournamentSelection::new(2),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_replacement(Replacement::Elitist { elites: 2 })
+            .with_bounds(bounds.clone());
 
-            // Number of genes different between `child` and `parent_b`
-            let diff_b = child.iter().zip(parent_b).filter(|(c, p)| *c != p).count();
+            // Initial population starts outside of the bounds
+            let population = vec![individual(&[3.0, -2.0]); 4];
+            let (population, _) = ga.evolve(&mut rng, &population);
 
-            assert_eq!(diff_a, 49);
-            assert_eq!(diff_b, 51);
+            assert!(population
+                .iter()
+                .all(|individual| individual.chromosome().is_within(&bounds)));
         }
     }
 
-    mod given_zero_chance {
-        fn actual(coeff: f32) -> Vec<f32> {
-            super::actual(0.0, coeff)
-        }
+    mod penalty {
+        use super::*;
 
-        mod and_zero_coefficient {
-            use super::*;
+        /// Maximizes the sum of genes, which must not exceed 5.0.
+        #[derive(Clone, Debug)]
+        struct Capped(Chromosome);
 
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.0);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        impl Individual for Capped {
+            type Gene = f32;
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(),);
+            fn create(chromosome: Chromosome) -> Self {
+                Self(chromosome)
             }
-        }
 
-        mod and_nonzero_coefficient {
-            use super::*;
+            fn chromosome(&self) -> &Chromosome {
+                &self.0
+            }
 
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.5);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+            fn fitness(&self) -> f32 {
+                self.0.iter().sum()
+            }
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(),);
+            fn violation(&self) -> f32 {
+                (self.fitness() - 5.0).max(0.0)
             }
         }
-    }
 
-    mod given_fifty_fifty_chance {
-        fn actual(coeff: f32) -> Vec<f32> {
-            super::actual(0.5, coeff)
-        }
+        /// Evolves the population for a while, and returns the sum of
+        /// genes of its best (penalized) individual.
+        fn actual(penalty: Penalty) -> f32 {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        mod and_zero_coefficient {
-            use super::*;
+            let ga = GeneticAlgorithm::new(
+                TournamentSelection::new(2),
+                UniformCrossover::new(),
+                GaussianMutation::new(0.5, 0.5),
+            )
+            .with_replacement(Replacement::Elitist { elites: 1 })
+            .with_penalty(penalty);
 
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.0);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+            let mut population = vec![Capped::create([0.0, 0.0].into_iter().collect()); 20];
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(),);
+            for _ in 0..100 {
+                (population, _) = ga.evolve(&mut rng, &population);
             }
+
+            population[0].fitness()
         }
 
-        mod and_nonzero_coefficient {
-            use super::*;
+        #[test]
+        fn given_no_penalty_violates_constraint() {
+            assert!(actual(Penalty::None) > 10.0);
+        }
 
-            #[test]
-            fn slightly_changes_the_original_chromosome() {
-                let actual = actual(0.5);
-                let expected = vec![1.0, 1.7756249, 3.0, 4.1596804, 5.0];
+        #[test]
+        fn given_static_penalty() {
+            approx::assert_abs_diff_eq!(
+                actual(Penalty::Static { coeff: 10.0 }),
+                5.0,
+                epsilon = 0.1
+            );
+        }
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(),);
-            }
+        #[test]
+        fn given_proportional_penalty() {
+            approx::assert_abs_diff_eq!(
+                actual(Penalty::Proportional { coeff: 10.0 }),
+                5.0,
+                epsilon = 0.1
+            );
         }
     }
 
-    mod given_max_chance {
-        fn actual(coeff: f32) -> Vec<f32> {
-            super::actual(1.0, coeff)
-        }
+    mod given_other_genes {
+        use super::*;
 
-        mod and_zero_coefficient {
-            use super::*;
+        /// OneMax: the more `true`s, the better.
+        #[derive(Clone, Debug)]
+        struct Bits(Chromosome<bool>);
 
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.0);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        impl Individual for Bits {
+            type Gene = bool;
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(),);
+            fn create(chromosome: Chromosome<bool>) -> Self {
+                Self(chromosome)
             }
-        }
 
-        mod and_nonzero_coefficient {
-            use super::*;
+            fn chromosome(&self) -> &Chromosome<bool> {
+                &self.0
+            }
+
+            fn fitness(&self) -> f32 {
+                self.0.iter().filter(|&&bit| bit).count() as f32
+            }
+        }
 
-            #[test]
-            fn entirely_changes_the_original_chromosome() {
-                let actual = actual(0.5);
+        /// Order of visiting cities lying on a line - the shorter the
+        /// route, the better.
+        #[derive(Clone, Debug)]
+        struct Route(Chromosome<usize>);
 
-                let expected = vec![1.4545316, 2.1162078, 2.7756248, 3.9505124, 4.638691];
+        impl Individual for Route {
+            type Gene = usize;
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(),);
+            fn create(chromosome: Chromosome<usize>) -> Self {
+                Self(chromosome)
             }
-        }
-    }
 
-    #[cfg(test)]
-    mod genetic_algo {
-        use super::*;
-        use rand::SeedableRng;
-        use rand_chacha::ChaCha8Rng;
+            fn chromosome(&self) -> &Chromosome<usize> {
+                &self.0
+            }
 
-        fn individual(genes: &[f32]) -> TestIndividual {
-            let chromosome = genes.iter().cloned().collect();
+            fn fitness(&self) -> f32 {
+                let genes: Vec<_> = self.0.iter().collect();
 
-            TestIndividual::create(chromosome)
+                -genes
+                    .windows(2)
+                    .map(|cities| cities[0].distance(cities[1]))
+                    .sum::<f32>()
+            }
         }
 
         #[test]
-        fn test() {
+        fn binary() {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
 
             let ga = GeneticAlgorithm::new(
-                RouletteWheelSelection::new(),
+                TournamentSelection::new(3),
                 UniformCrossover::new(),
-                GaussianMutation::new(0.5, 0.5),
-            );
+                BitFlipMutation::new(1.0 / 16.0),
+            )
+            .with_replacement(Replacement::Elitist { elites: 2 });
 
-            let mut population = vec![
-                individual(&[0.0, 0.0, 0.0]), // fitness = 0.0
-                individual(&[1.0, 1.0, 1.0]), // fitness = 3.0
-                individual(&[1.0, 2.0, 1.0]), // fitness = 4.0
-                individual(&[1.0, 2.0, 4.0]), // fitness = 7.0
-            ];
-
-            // We're running `.evolve()` a few times, so that the
-            // differences between initial and output population are
-            // easier to spot.
-            //
-            // No particular reason for a number of 10 - this test would
-            // be fine for 5, 20 or even 1000 generations; the only thing
-            // that'd change is the *magnitude* of difference between
-            // initial and output population.
-            for _ in 0..10 {
-                (population, _) = ga.evolve(&mut rng, &population);
+            let mut population: Vec<_> = (0..20)
+                .map(|_| Bits::create((0..16).map(|_| rng.gen_bool(0.5)).collect()))
+                .collect();
+
+            let mut stats = None;
+
+            for _ in 0..50 {
+                let new_stats;
+                (population, new_stats) = ga.evolve(&mut rng, &population);
+                stats = Some(new_stats);
+            }
+
+            assert_eq!(stats.unwrap().max_fitness(), 16.0);
+        }
+
+        #[test]
+        fn permutation() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let ga = GeneticAlgorithm::new(
+                TournamentSelection::new(3),
+                OrderCrossover::new(),
+                InversionMutation::new(0.5),
+            )
+            .with_replacement(Replacement::Elitist { elites: 2 });
+
+            let mut population: Vec<_> = (0..30)
+                .map(|_| {
+                    let mut cities: Vec<_> = (0..10).collect();
+
+                    cities.shuffle(&mut rng);
+                    Route::create(cities.into_iter().collect())
+                })
+                .collect();
+
+            let mut stats = None;
+
+            for _ in 0..100 {
+                let new_stats;
+                (population, new_stats) = ga.evolve(&mut rng, &population);
+                stats = Some(new_stats);
             }
 
-            let expected_population = vec![
-                individual(&[0.44769490, 2.0648358, 4.3058133]),
-                individual(&[1.21268670, 1.5538777, 2.8869110]),
-                individual(&[1.06176780, 2.2657390, 4.4287640]),
-                individual(&[0.95909685, 2.4618788, 4.0247330]),
-            ];
+            // Visiting cities in order (either way) is the shortest route
+            assert_eq!(stats.unwrap().max_fitness(), -9.0);
 
-            assert_eq!(population, expected_population);
+            assert!(population
+                .iter()
+                .all(|route| is_permutation(route.chromosome())));
         }
     }
 }
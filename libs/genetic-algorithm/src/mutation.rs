@@ -0,0 +1,78 @@
+pub use self::{
+    annealed::*, bit_flip::*, cauchy::*, creep::*, gaussian::*, inversion::*, self_adaptive::*,
+    swap::*, uniform::*,
+};
+
+use crate::*;
+
+mod annealed;
+mod bit_flip;
+mod cauchy;
+mod creep;
+mod gaussian;
+mod inversion;
+mod self_adaptive;
+mod swap;
+mod uniform;
+
+pub trait MutationMethod<G = f32>: Debug + Send + Sync {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+
+    /// Called by `GeneticAlgorithm::evolve()` after each generation;
+    /// allows methods to change over time (see `AnnealedMutation`).
+    fn advance(&self) {}
+
+    /// Called by `GeneticAlgorithm::resume()`; brings the method back to
+    /// where it was after given number of generations.
+    fn restore(&self, _generation: usize) {}
+
+    /// Describes how the method is configured, leaving out whatever
+    /// changes as it runs (e.g. generation of `AnnealedMutation`); used
+    /// by `GeneticAlgorithm::resume()` to tell whether a checkpoint fits.
+    ///
+    /// For methods without such state, that's just their `Debug`.
+    fn configuration(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+/// Allows choosing the mutation at runtime (e.g. from a config), by
+/// passing `Box<dyn MutationMethod>` into `GeneticAlgorithm::new()`.
+impl<M, G> MutationMethod<G> for Box<M>
+where
+    M: MutationMethod<G> + ?Sized,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        (**self).mutate(rng, child)
+    }
+
+    fn advance(&self) {
+        (**self).advance()
+    }
+
+    fn restore(&self, generation: usize) {
+        (**self).restore(generation)
+    }
+
+    fn configuration(&self) -> String {
+        (**self).configuration()
+    }
+}
+
+/// Returns mean and standard deviation of the changes given method
+/// applies to a chromosome full of zeros.
+#[cfg(test)]
+pub(crate) fn changes(method: &dyn MutationMethod) -> (f32, f32) {
+    use rand::SeedableRng;
+
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(Default::default());
+    let mut child: Chromosome = vec![0.0; 10_000].into_iter().collect();
+
+    method.mutate(&mut rng, &mut child);
+
+    let mean = child.iter().sum::<f32>() / child.len() as f32;
+
+    let variance = child.iter().map(|gene| (gene - mean).powi(2)).sum::<f32>() / child.len() as f32;
+
+    (mean, variance.sqrt())
+}
@@ -0,0 +1,166 @@
+use crate::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// `GaussianMutation` with a coefficient that shrinks each generation:
+///
+/// `coeff(generation) = max(coeff * decay^generation, min_coeff)`
+///
+/// Early generations explore with large changes, while the later ones
+/// fine-tune what's been found so far.
+///
+/// Generations are counted by `MutationMethod::advance()`, which
+/// `GeneticAlgorithm::evolve()` calls after each generation.
+#[derive(Debug)]
+pub struct AnnealedMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Initial magnitude of that change (see `GaussianMutation`)
+    coeff: f32,
+
+    /// How much of the coefficient remains after each generation:
+    /// - 0.5 = coefficient gets halved each generation
+    /// - 1.0 = coefficient never changes
+    decay: f32,
+
+    /// Coefficient never gets smaller than this
+    min_coeff: f32,
+
+    generation: AtomicUsize,
+}
+
+impl AnnealedMutation {
+    pub fn new(chance: f32, coeff: f32, decay: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(decay > 0.0 && decay <= 1.0);
+
+        Self {
+            chance,
+            coeff,
+            decay,
+            min_coeff: 0.0,
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn with_min_coeff(mut self, min_coeff: f32) -> Self {
+        self.min_coeff = min_coeff;
+        self
+    }
+
+    /// Number of generations seen so far.
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Coefficient used for the current generation.
+    pub fn coeff(&self) -> f32 {
+        let generation = self.generation().min(i32::MAX as usize) as i32;
+
+        (self.coeff * self.decay.powi(generation)).max(self.min_coeff)
+    }
+}
+
+impl MutationMethod for AnnealedMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        GaussianMutation::nudge(rng, child, self.chance, self.coeff());
+    }
+
+    fn advance(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn restore(&self, generation: usize) {
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+
+    fn configuration(&self) -> String {
+        format!(
+            "AnnealedMutation {{ chance: {:?}, coeff: {:?}, decay: {:?}, min_coeff: {:?} }}",
+            self.chance, self.coeff, self.decay, self.min_coeff
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod coeff {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let method = AnnealedMutation::new(1.0, 0.8, 0.5);
+
+            approx::assert_relative_eq!(method.coeff(), 0.8);
+
+            method.advance();
+            approx::assert_relative_eq!(method.coeff(), 0.4);
+
+            method.advance();
+            approx::assert_relative_eq!(method.coeff(), 0.2);
+            assert_eq!(method.generation(), 2);
+        }
+
+        #[test]
+        fn given_restored_generation() {
+            let method = AnnealedMutation::new(1.0, 0.8, 0.5);
+
+            method.advance();
+            method.restore(3);
+
+            assert_eq!(method.generation(), 3);
+            approx::assert_relative_eq!(method.coeff(), 0.1);
+        }
+
+        #[test]
+        fn never_drops_below_min_coeff() {
+            let method = AnnealedMutation::new(1.0, 0.8, 0.5).with_min_coeff(0.3);
+
+            for _ in 0..10 {
+                method.advance();
+            }
+
+            approx::assert_relative_eq!(method.coeff(), 0.3);
+        }
+    }
+
+    mod configuration {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let method = AnnealedMutation::new(1.0, 0.8, 0.5);
+            let expected = method.configuration();
+
+            method.advance();
+
+            assert_eq!(method.configuration(), expected);
+            assert_ne!(
+                AnnealedMutation::new(1.0, 0.8, 0.25).configuration(),
+                expected
+            );
+        }
+    }
+
+    mod mutate {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let method = AnnealedMutation::new(1.0, 0.8, 0.5);
+
+            let (_, std_dev) = changes(&method);
+            approx::assert_abs_diff_eq!(std_dev, 0.8, epsilon = 0.03);
+
+            method.advance();
+            method.advance();
+
+            let (_, std_dev) = changes(&method);
+            approx::assert_abs_diff_eq!(std_dev, 0.2, epsilon = 0.01);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::*;
+
+/// Flips bits of binary chromosomes.
+#[derive(Clone, Debug)]
+pub struct BitFlipMutation {
+    /// Probability of flipping a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be flipped
+    chance: f32,
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = !*gene;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<bool> {
+        let mut child = vec![true, false, true, false, true].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        BitFlipMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), [true, false, true, false, true]);
+    }
+
+    #[test]
+    fn given_fifty_fifty_chance_flips_some_genes() {
+        assert_eq!(actual(0.5), [true, false, false, true, false]);
+    }
+
+    #[test]
+    fn given_max_chance_flips_all_genes() {
+        assert_eq!(actual(1.0), [false, true, false, true, false]);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::*;
+
+/// Nudges genes by values sampled from the Cauchy distribution, centered
+/// at zero, with scale of `coeff`.
+///
+/// Most of the nudges are about as large as `GaussianMutation`'s, but
+/// the distribution has a much heavier tail - every now and then a gene
+/// gets changed a lot, which helps to escape local optima.
+#[derive(Clone, Debug)]
+pub struct CauchyMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Magnitude of that change:
+    /// - 0.0 = touched genes will not be modified
+    /// - 3.0 = touched genes will be += or -= by less than 3.0 half of
+    ///   the time
+    coeff: f32,
+}
+
+impl CauchyMutation {
+    pub fn new(chance: f32, coeff: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(coeff >= 0.0);
+
+        Self { chance, coeff }
+    }
+}
+
+impl MutationMethod for CauchyMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        if self.coeff == 0.0 {
+            return;
+        }
+
+        let distribution = Cauchy::new(0.0, self.coeff).unwrap();
+
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene += rng.sample::<f32, _>(distribution);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32, coeff: f32) -> Vec<f32> {
+        let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        CauchyMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        let actual = actual(0.0, 0.5);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_zero_coefficient_does_not_change_the_original_chromosome() {
+        let actual = actual(1.0, 0.0);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_max_chance_entirely_changes_the_original_chromosome() {
+        let actual = actual(1.0, 0.5);
+        let expected = vec![1.3335003, 1.718788, 2.8531392, 2.8586097, 4.5361714];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn has_quartiles_at_coefficient() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome = vec![0.0; 10_000].into_iter().collect();
+
+        CauchyMutation::new(1.0, 0.5).mutate(&mut rng, &mut child);
+
+        let mut changes: Vec<_> = child.into_iter().collect();
+        changes.sort_by(f32::total_cmp);
+
+        approx::assert_abs_diff_eq!(changes[2_500], -0.5, epsilon = 0.03);
+        approx::assert_abs_diff_eq!(changes[5_000], 0.0, epsilon = 0.03);
+        approx::assert_abs_diff_eq!(changes[7_500], 0.5, epsilon = 0.03);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::*;
+
+/// Creeps integer genes, i.e. nudges them by small values sampled
+/// uniformly from `[-step, step]`.
+#[derive(Clone, Debug)]
+pub struct CreepMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Magnitude of that change:
+    /// - 1 = touched genes will be += or -= by at most 1
+    step: i32,
+}
+
+impl CreepMutation {
+    pub fn new(chance: f32, step: i32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(step > 0);
+
+        Self { chance, step }
+    }
+}
+
+impl MutationMethod<i32> for CreepMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<i32>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = gene.saturating_add(rng.gen_range(-self.step..=self.step));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32, step: i32) -> Vec<i32> {
+        let mut child = vec![10, 20, 30, 40, 50].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        CreepMutation::new(chance, step).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0, 3), [10, 20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn given_fifty_fifty_chance_slightly_changes_the_original_chromosome() {
+        assert_eq!(actual(0.5, 3), [10, 20, 30, 38, 51]);
+    }
+
+    #[test]
+    fn given_max_chance_entirely_changes_the_original_chromosome() {
+        assert_eq!(actual(1.0, 3), [8, 22, 33, 41, 52]);
+    }
+
+    #[test]
+    fn stays_within_step() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome<i32> = vec![0; 1_000].into_iter().collect();
+
+        CreepMutation::new(1.0, 2).mutate(&mut rng, &mut child);
+
+        assert!(child.iter().all(|gene| (-2..=2).contains(gene)));
+        assert!(child.iter().any(|&gene| gene == -2));
+        assert!(child.iter().any(|&gene| gene == 2));
+    }
+}
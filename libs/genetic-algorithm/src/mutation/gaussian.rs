@@ -0,0 +1,104 @@
+use crate::*;
+
+/// Nudges genes by values sampled from `N(0, coeff^2)`.
+#[derive(Clone, Debug)]
+pub struct GaussianMutation {
+    /// Probability of changing a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be touched
+    chance: f32,
+
+    /// Magnitude of that change, i.e. its standard deviation:
+    /// - 0.0 = touched genes will not be modified
+    /// - 3.0 = touched genes will be += or -= by less than 3.0 about
+    ///   68% of the time
+    coeff: f32,
+}
+
+impl GaussianMutation {
+    pub fn new(chance: f32, coeff: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(coeff >= 0.0);
+
+        Self { chance, coeff }
+    }
+
+    /// Nudges each gene with given probability, by `coeff` times a
+    /// value sampled from `N(0, 1)`.
+    pub(crate) fn nudge(rng: &mut dyn RngCore, child: &mut Chromosome, chance: f32, coeff: f32) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(chance as _) {
+                *gene += coeff * rng.sample::<f32, _>(StandardNormal);
+            }
+        }
+    }
+}
+
+impl MutationMethod for GaussianMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        Self::nudge(rng, child, self.chance, self.coeff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32, coeff: f32) -> Vec<f32> {
+        let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        GaussianMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+
+        child.into_iter().collect()
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        let actual = actual(0.0, 0.5);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_zero_coefficient_does_not_change_the_original_chromosome() {
+        let actual = actual(1.0, 0.0);
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_fifty_fifty_chance_slightly_changes_the_original_chromosome() {
+        let actual = actual(0.5, 0.5);
+        let expected = vec![1.0, 2.0, 2.0324764, 3.467692, 4.4987187];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn given_max_chance_entirely_changes_the_original_chromosome() {
+        let actual = actual(1.0, 0.5);
+        let expected = vec![1.6888486, 2.2026734, 2.4018655, 3.0324764, 4.664113];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_negative_coefficient() {
+        GaussianMutation::new(0.5, -1.0);
+    }
+
+    #[test]
+    fn is_normally_distributed() {
+        let (mean, std_dev) = changes(&GaussianMutation::new(1.0, 0.5));
+
+        approx::assert_abs_diff_eq!(mean, 0.0, epsilon = 0.02);
+        approx::assert_abs_diff_eq!(std_dev, 0.5, epsilon = 0.02);
+    }
+}
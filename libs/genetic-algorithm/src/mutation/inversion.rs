@@ -0,0 +1,76 @@
+use crate::*;
+
+/// Reverses a random segment of the chromosome:
+///
+/// ```text
+///  before: 0 1|2 3 4|5 6 7
+///   after: 0 1|4 3 2|5 6 7
+/// ```
+///
+/// Like `SwapMutation`, it keeps permutations valid - but since it
+/// preserves adjacency of all genes except at the segment's edges, it's
+/// usually the gentler choice for routing problems.
+#[derive(Clone, Debug)]
+pub struct InversionMutation {
+    /// Probability of reversing a segment:
+    /// - 0.0 = no chromosomes will be touched
+    /// - 1.0 = all chromosomes will get a segment reversed
+    chance: f32,
+}
+
+impl InversionMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for InversionMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        if child.len() < 2 || !rng.gen_bool(self.chance as _) {
+            return;
+        }
+
+        let segment = segment(rng, child.len());
+        let (mut a, mut b) = (segment.start, segment.end - 1);
+
+        while a < b {
+            child.swap(a, b);
+            a += 1;
+            b -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Chromosome<usize> {
+        let mut child = (0..8).collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        InversionMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), (0..8).collect());
+    }
+
+    #[test]
+    fn given_max_chance_reverses_a_segment() {
+        let actual = actual(1.0);
+
+        assert_eq!(actual, [0, 6, 5, 4, 3, 2, 1, 7].into_iter().collect());
+        assert!(is_permutation(&actual));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::*;
+
+/// Self-adaptive mutation, as used by evolution strategies: each gene
+/// carries its own step size, which gets mutated (and inherited) along
+/// with the gene itself.
+///
+/// Chromosome is expected to contain the genes first and their step
+/// sizes next, i.e. `[x1, x2, ..., xn, σ1, σ2, ..., σn]` - see
+/// `Self::chromosome()` and `Self::genes()`.
+///
+/// Step sizes are mutated log-normally (Schwefel, 1977):
+///
+/// `σi' = σi * exp(τ' * N(0, 1) + τ * Ni(0, 1))`
+///
+/// ... where `τ' = 1 / sqrt(2n)` is shared by all the genes and
+/// `τ = 1 / sqrt(2 * sqrt(n))` is per gene; each gene is then nudged by
+/// `σi' * Ni(0, 1)`. Step sizes that lead to fit children survive, so
+/// the algorithm tunes its own mutation strength.
+#[derive(Clone, Debug)]
+pub struct SelfAdaptiveMutation {
+    /// Step sizes never get smaller than this, so that the evolution
+    /// doesn't stall
+    min_step: f32,
+}
+
+impl SelfAdaptiveMutation {
+    pub fn new(min_step: f32) -> Self {
+        assert!(min_step >= 0.0);
+
+        Self { min_step }
+    }
+
+    /// Creates chromosome out of given genes, giving all of them the same
+    /// initial step size.
+    pub fn chromosome(genes: impl IntoIterator<Item = f32>, step: f32) -> Chromosome {
+        let genes: Vec<_> = genes.into_iter().collect();
+        let steps = vec![step; genes.len()];
+
+        genes.into_iter().chain(steps).collect()
+    }
+
+    /// Returns genes of given chromosome, without their step sizes.
+    pub fn genes(chromosome: &Chromosome) -> impl Iterator<Item = &f32> {
+        chromosome.iter().take(chromosome.len() / 2)
+    }
+
+    /// Returns step sizes of given chromosome.
+    pub fn steps(chromosome: &Chromosome) -> impl Iterator<Item = &f32> {
+        chromosome.iter().skip(chromosome.len() / 2)
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        assert_eq!(
+            child.len() % 2,
+            0,
+            "chromosome should contain genes followed by their step sizes"
+        );
+
+        let n = child.len() / 2;
+
+        if n == 0 {
+            return;
+        }
+
+        let global_tau = 1.0 / (2.0 * n as f32).sqrt();
+        let tau = 1.0 / (2.0 * (n as f32).sqrt()).sqrt();
+        let global = global_tau * rng.sample::<f32, _>(StandardNormal);
+
+        let mut values: Vec<_> = child.iter_mut().collect();
+        let (genes, steps) = values.split_at_mut(n);
+
+        for (gene, step) in genes.iter_mut().zip(steps.iter_mut()) {
+            let local = tau * rng.sample::<f32, _>(StandardNormal);
+
+            **step = (**step * (global + local).exp()).max(self.min_step);
+            **gene += **step * rng.sample::<f32, _>(StandardNormal);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(min_step: f32, step: f32) -> Chromosome {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = SelfAdaptiveMutation::chromosome([1.0, 2.0, 3.0], step);
+
+        SelfAdaptiveMutation::new(min_step).mutate(&mut rng, &mut child);
+
+        child
+    }
+
+    mod chromosome {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let chromosome = SelfAdaptiveMutation::chromosome([1.0, 2.0], 0.5);
+            let genes: Vec<_> = SelfAdaptiveMutation::genes(&chromosome).copied().collect();
+            let steps: Vec<_> = SelfAdaptiveMutation::steps(&chromosome).copied().collect();
+
+            assert_eq!(chromosome.len(), 4);
+            approx::assert_relative_eq!(genes.as_slice(), [1.0, 2.0].as_slice());
+            approx::assert_relative_eq!(steps.as_slice(), [0.5, 0.5].as_slice());
+        }
+    }
+
+    mod mutate {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let actual: Vec<_> = actual(0.0, 0.5).into_iter().collect();
+            let expected = vec![
+                -0.30511296,
+                1.7915821,
+                1.9357346,
+                1.0909863,
+                0.31025016,
+                0.49524295,
+            ];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn keeps_steps_above_min_step() {
+            let actual = actual(0.4, 0.1);
+
+            for step in SelfAdaptiveMutation::steps(&actual) {
+                assert!(*step >= 0.4);
+            }
+        }
+
+        #[test]
+        fn given_zero_steps_does_not_change_genes() {
+            let actual = actual(0.0, 0.0);
+            let genes: Vec<_> = SelfAdaptiveMutation::genes(&actual).copied().collect();
+
+            approx::assert_relative_eq!(genes.as_slice(), [1.0, 2.0, 3.0].as_slice());
+        }
+
+        #[test]
+        #[should_panic(expected = "chromosome should contain genes followed by their step sizes")]
+        fn rejects_chromosomes_without_steps() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut child = [1.0, 2.0, 3.0].into_iter().collect();
+
+            SelfAdaptiveMutation::new(0.0).mutate(&mut rng, &mut child);
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::*;
+
+/// Swaps genes with other, randomly chosen genes of the same chromosome.
+///
+/// Contrary to mutations that overwrite genes, this one keeps
+/// permutations valid.
+#[derive(Clone, Debug)]
+pub struct SwapMutation {
+    /// Probability of swapping a gene:
+    /// - 0.0 = no genes will be touched
+    /// - 1.0 = all genes will be swapped (possibly with themselves)
+    chance: f32,
+}
+
+impl SwapMutation {
+    pub fn new(chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        Self { chance }
+    }
+}
+
+impl<G> MutationMethod<G> for SwapMutation
+where
+    G: Gene,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        for index in 0..child.len() {
+            if rng.gen_bool(self.chance as _) {
+                let other = rng.gen_range(0..child.len());
+
+                child.swap(index, other);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Chromosome<usize> {
+        let mut child = (0..8).collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        SwapMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child
+    }
+
+    #[test]
+    fn given_zero_chance_does_not_change_the_original_chromosome() {
+        assert_eq!(actual(0.0), (0..8).collect());
+    }
+
+    #[test]
+    fn given_fifty_fifty_chance_swaps_some_genes() {
+        let actual = actual(0.5);
+
+        assert_eq!(actual, [3, 1, 2, 6, 5, 4, 0, 7].into_iter().collect());
+        assert!(is_permutation(&actual));
+    }
+
+    #[test]
+    fn given_max_chance_swaps_all_genes() {
+        let actual = actual(1.0);
+
+        assert_eq!(actual, [1, 2, 3, 7, 0, 4, 5, 6].into_iter().collect());
+        assert!(is_permutation(&actual));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::*;
+
+/// How `GeneticAlgorithm::evolve()` keeps the population from converging
+/// onto a single solution - by making individuals share their fitness
+/// with others in the same niche, before parents get selected.
+///
+/// Sharing assumes non-negative fitness (as roulette wheel selection
+/// does anyway) - dividing a negative fitness would make it better.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Niching {
+    /// Individuals are selected by their raw fitness.
+    #[default]
+    None,
+
+    /// Fitness sharing (Goldberg & Richardson, 1987): each individual's
+    /// fitness gets divided by its niche count, i.e. the sum of
+    /// `1 - (distance / radius)^alpha` over all individuals closer than
+    /// `radius` (including itself).
+    ///
+    /// Distance is measured with `Chromosome::distance()`.
+    FitnessSharing { radius: f32, alpha: f32 },
+
+    /// Speciation: individuals get clustered into species - each species
+    /// is represented by its fittest member, and an individual joins the
+    /// first species whose representative is closer than `threshold`
+    /// (or starts a new one); then each individual's fitness gets divided
+    /// by the size of its species, as in NEAT.
+    Speciation { threshold: f32 },
+}
+
+impl Niching {
+    /// Clusters the population into species (see `Self::Speciation`);
+    /// for fitness sharing, `radius` is used as the threshold.
+    ///
+    /// Returns indices of individuals, species by species - starting
+    /// with the species of the fittest individual (by `fitnesses`, which
+    /// might've been penalized).
+    pub(crate) fn species<I>(self, population: &[I], fitnesses: &[f32]) -> Vec<Vec<usize>>
+    where
+        I: Individual,
+    {
+        let threshold = match self {
+            Self::None => return Vec::new(),
+            Self::FitnessSharing { radius, .. } => radius,
+            Self::Speciation { threshold } => threshold,
+        };
+
+        let mut order: Vec<_> = (0..population.len()).collect();
+
+        order.sort_by(|&a, &b| fitnesses[b].total_cmp(&fitnesses[a]));
+
+        let mut species: Vec<Vec<usize>> = Vec::new();
+
+        for individual in order {
+            let chromosome = population[individual].chromosome();
+
+            let found = species.iter_mut().find(|species| {
+                population[species[0]].chromosome().distance(chromosome) < threshold
+            });
+
+            match found {
+                Some(species) => species.push(individual),
+                None => species.push(vec![individual]),
+            }
+        }
+
+        species
+    }
+
+    /// Returns `fitnesses` of individuals, after sharing them within
+    /// their niches; `species` must come from `Self::species()`.
+    pub(crate) fn shared_fitnesses<I>(
+        self,
+        population: &[I],
+        fitnesses: &[f32],
+        species: &[Vec<usize>],
+    ) -> Vec<f32>
+    where
+        I: Individual,
+    {
+        match self {
+            Self::None => fitnesses.to_vec(),
+
+            Self::FitnessSharing { radius, alpha } => population
+                .iter()
+                .zip(fitnesses)
+                .map(|(a, fitness)| {
+                    let niche_count: f32 = population
+                        .iter()
+                        .map(|b| a.chromosome().distance(b.chromosome()))
+                        .filter(|&distance| distance < radius)
+                        .map(|distance| 1.0 - (distance / radius).powf(alpha))
+                        .sum();
+
+                    fitness / niche_count.max(1.0)
+                })
+                .collect(),
+
+            Self::Speciation { .. } => {
+                let mut shared = vec![0.0; population.len()];
+
+                for species in species {
+                    for &individual in species {
+                        shared[individual] = fitnesses[individual] / species.len() as f32;
+                    }
+                }
+
+                shared
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().copied().collect())
+    }
+
+    fn fitnesses(population: &[TestIndividual]) -> Vec<f32> {
+        population
+            .iter()
+            .map(|individual| individual.fitness())
+            .collect()
+    }
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            individual(&[1.0, 1.0]), // fitness = 2.0
+            individual(&[5.0, 5.0]), // fitness = 10.0
+            individual(&[1.0, 1.5]), // fitness = 2.5
+            individual(&[4.0, 5.0]), // fitness = 9.0
+            individual(&[9.0, 0.0]), // fitness = 9.0
+        ]
+    }
+
+    mod species {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let population = population();
+            let actual = Niching::Speciation { threshold: 1.0 }
+                .species(&population, &fitnesses(&population));
+            let expected = vec![vec![1, 3], vec![4], vec![2, 0]];
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_no_niching() {
+            let population = population();
+
+            assert!(Niching::None
+                .species(&population, &fitnesses(&population))
+                .is_empty());
+        }
+    }
+
+    mod shared_fitnesses {
+        use super::*;
+
+        fn actual(niching: Niching) -> Vec<f32> {
+            let population = population();
+            let fitnesses = fitnesses(&population);
+            let species = niching.species(&population, &fitnesses);
+
+            niching.shared_fitnesses(&population, &fitnesses, &species)
+        }
+
+        #[test]
+        fn given_no_niching() {
+            let actual = actual(Niching::None);
+            let expected = [2.0, 10.0, 2.5, 9.0, 9.0];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn given_fitness_sharing() {
+            let actual = actual(Niching::FitnessSharing {
+                radius: 1.0,
+                alpha: 1.0,
+            });
+
+            // Individuals 0 & 2 and 1 & 3 lie 0.25 and 0.5 apart, so
+            // their niche counts are 1.75 and 1.5
+            let expected = [2.0 / 1.75, 10.0 / 1.5, 2.5 / 1.75, 9.0 / 1.5, 9.0];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn given_speciation() {
+            let actual = actual(Niching::Speciation { threshold: 1.0 });
+            let expected = [1.0, 5.0, 1.25, 4.5, 9.0];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
+}
@@ -0,0 +1,442 @@
+use crate::*;
+use std::cmp::Ordering;
+
+/// Individual that's judged by many objectives at once (e.g. food eaten
+/// vs energy spent), instead of a single fitness.
+///
+/// All objectives are maximized - negate the ones that should be
+/// minimized. `Individual::fitness()` is still used for `Statistics`,
+/// so it's worth returning the most important objective there.
+pub trait MultiObjectiveIndividual: Individual {
+    fn objectives(&self) -> Vec<f32>;
+}
+
+/// Returns whether `a` dominates `b` - i.e. whether `a` is at least as
+/// good as `b` in all objectives, and strictly better in at least one.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    assert_eq!(a.len(), b.len());
+
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Splits individuals (given by their objectives) into Pareto fronts:
+/// the first front contains individuals that aren't dominated by anyone,
+/// the second one contains individuals dominated only by the first
+/// front, and so on (Deb et al., 2002).
+///
+/// Returns indices of individuals, front by front.
+pub fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    // For each individual: whom it dominates and by how many it's
+    // dominated
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); objectives.len()];
+    let mut domination_counts = vec![0; objectives.len()];
+
+    for a in 0..objectives.len() {
+        for b in (a + 1)..objectives.len() {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominated[a].push(b);
+                domination_counts[b] += 1;
+            } else if dominates(&objectives[b], &objectives[a]) {
+                dominated[b].push(a);
+                domination_counts[a] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+
+    let mut front: Vec<_> = (0..objectives.len())
+        .filter(|&individual| domination_counts[individual] == 0)
+        .collect();
+
+    while !front.is_empty() {
+        let mut next_front = Vec::new();
+
+        for &a in &front {
+            for &b in &dominated[a] {
+                domination_counts[b] -= 1;
+
+                if domination_counts[b] == 0 {
+                    next_front.push(b);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next_front;
+    }
+
+    fronts
+}
+
+/// Computes crowding distance of each individual of given front - i.e.
+/// how far it is from its neighbours, summed over all the objectives,
+/// each normalized by its range.
+///
+/// Individuals at the edges of the front get an infinite distance, so
+/// that the front's extremes are always preserved.
+///
+/// Returns distances in the same order as `front`.
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+
+    if front.len() <= 2 {
+        distances.fill(f32::INFINITY);
+        return distances;
+    }
+
+    let dimensions = objectives[front[0]].len();
+    let mut order: Vec<_> = (0..front.len()).collect();
+
+    (0..dimensions).for_each(|dimension| {
+        let values: Vec<_> = front
+            .iter()
+            .map(|&individual| objectives[individual][dimension])
+            .collect();
+
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+        let first = order[0];
+        let last = order[order.len() - 1];
+
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        let range = values[last] - values[first];
+
+        if range <= 0.0 {
+            return;
+        }
+
+        for window in order.windows(3) {
+            distances[window[1]] += (values[window[2]] - values[window[0]]) / range;
+        }
+    });
+
+    distances
+}
+
+/// Non-dominated sorting genetic algorithm, NSGA-II (Deb et al., 2002):
+/// evolves `MultiObjectiveIndividual`s towards the Pareto front, while
+/// keeping them spread along it.
+///
+/// Each call to `Self::evolve()` keeps the better half of the population
+/// unchanged (ranked by their Pareto front first, and crowding distance
+/// second), and replaces the other half with children of that half -
+/// so that after evaluating the next generation, parents compete with
+/// their children, as in the original algorithm.
+pub struct Nsga2<G = f32> {
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    mutation_method: Box<dyn MutationMethod<G>>,
+    repair_method: Option<Box<dyn RepairMethod<G>>>,
+}
+
+/// Rank of an individual - lower is better.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rank {
+    front: usize,
+    crowding_distance: f32,
+}
+
+impl Rank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.front
+            .cmp(&other.front)
+            .then_with(|| other.crowding_distance.total_cmp(&self.crowding_distance))
+    }
+}
+
+impl<G> Nsga2<G>
+where
+    G: Gene,
+{
+    pub fn new(
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
+    ) -> Self {
+        Self {
+            crossover_method: Box::new(crossover_method),
+            mutation_method: Box::new(mutation_method),
+            repair_method: None,
+        }
+    }
+
+    /// Makes each child (and each survivor) get repaired after crossover
+    /// and mutation; see `GeneticAlgorithm::with_bounds()`.
+    pub fn with_bounds(mut self, repair_method: impl RepairMethod<G> + 'static) -> Self {
+        self.repair_method = Some(Box::new(repair_method));
+        self
+    }
+
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<G>)
+    where
+        I: MultiObjectiveIndividual<Gene = G>,
+    {
+        assert!(!population.is_empty());
+
+        let objectives: Vec<_> = population
+            .iter()
+            .map(|individual| individual.objectives())
+            .collect();
+
+        let fronts = non_dominated_sort(&objectives);
+
+        let mut ranks = vec![
+            Rank {
+                front: 0,
+                crowding_distance: 0.0,
+            };
+            population.len()
+        ];
+
+        for (front_index, front) in fronts.iter().enumerate() {
+            let distances = crowding_distance(&objectives, front);
+
+            for (&individual, crowding_distance) in front.iter().zip(distances) {
+                ranks[individual] = Rank {
+                    front: front_index,
+                    crowding_distance,
+                };
+            }
+        }
+
+        let mut order: Vec<_> = (0..population.len()).collect();
+
+        order.sort_by(|&a, &b| ranks[a].cmp(&ranks[b]));
+
+        let survivors = &order[..population.len().div_ceil(2)];
+
+        let mut new_population: Vec<_> = survivors
+            .iter()
+            .map(|&individual| {
+                let mut survivor = population[individual].chromosome().clone();
+
+                if let Some(repair_method) = &self.repair_method {
+                    repair_method.repair(rng, &mut survivor);
+                }
+
+                I::create(survivor)
+            })
+            .collect();
+
+        // Crowded binary tournament among the survivors
+        let select = |rng: &mut dyn RngCore| {
+            let a = *survivors.choose(rng).unwrap();
+            let b = *survivors.choose(rng).unwrap();
+
+            if ranks[b].cmp(&ranks[a]) == Ordering::Less {
+                population[b].chromosome()
+            } else {
+                population[a].chromosome()
+            }
+        };
+
+        while new_population.len() < population.len() {
+            let parent_a = select(rng);
+            let parent_b = select(rng);
+
+            let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+
+            self.mutation_method.mutate(rng, &mut child);
+
+            if let Some(repair_method) = &self.repair_method {
+                repair_method.repair(rng, &mut child);
+            }
+
+            new_population.push(I::create(child));
+        }
+
+        self.mutation_method.advance();
+
+        let mut stats = Statistics::new(population);
+        stats.elites_survived = survivors.len();
+        stats.pareto_front = fronts[0]
+            .iter()
+            .map(|&individual| objectives[individual].clone())
+            .collect();
+
+        (new_population, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Schaffer's function N. 1: `min(x^2)` and `min((x - 2)^2)`; its
+    /// Pareto front consists of all `x` within `[0, 2]`.
+    #[derive(Clone, Debug)]
+    struct Schaffer {
+        chromosome: Chromosome,
+    }
+
+    impl Individual for Schaffer {
+        type Gene = f32;
+
+        fn create(chromosome: Chromosome) -> Self {
+            Self { chromosome }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.objectives()[0]
+        }
+    }
+
+    impl MultiObjectiveIndividual for Schaffer {
+        fn objectives(&self) -> Vec<f32> {
+            let x = self.chromosome[0];
+
+            vec![-x.powi(2), -(x - 2.0).powi(2)]
+        }
+    }
+
+    fn objectives() -> Vec<Vec<f32>> {
+        vec![
+            vec![1.0, 5.0], // front 0
+            vec![2.0, 2.0], // front 1 (dominated by 2)
+            vec![3.0, 3.0], // front 0
+            vec![5.0, 1.0], // front 0
+            vec![1.0, 1.0], // front 2
+            vec![2.5, 0.5], // front 1 (dominated by 2)
+        ]
+    }
+
+    mod dominates {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert!(dominates(&[2.0, 2.0], &[1.0, 2.0]));
+            assert!(!dominates(&[1.0, 2.0], &[2.0, 2.0]));
+            assert!(!dominates(&[2.0, 2.0], &[2.0, 2.0]));
+            assert!(!dominates(&[3.0, 1.0], &[1.0, 3.0]));
+        }
+    }
+
+    mod non_dominated_sort {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let actual = non_dominated_sort(&objectives());
+            let expected = vec![vec![0, 2, 3], vec![1, 5], vec![4]];
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod crowding_distance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let actual = crowding_distance(&objectives(), &[0, 2, 3]);
+
+            // Individual 2 lies between 0 and 3: (5 - 1) / 4 for the
+            // first objective, (5 - 1) / 4 for the second one
+            let expected = [f32::INFINITY, 2.0, f32::INFINITY];
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn given_small_front() {
+            assert_eq!(
+                crowding_distance(&objectives(), &[1, 5]),
+                [f32::INFINITY, f32::INFINITY]
+            );
+        }
+    }
+
+    mod evolve {
+        use super::*;
+
+        fn population(rng: &mut dyn RngCore) -> Vec<Schaffer> {
+            (0..20)
+                .map(|_| Schaffer::create([rng.gen_range(-10.0..=10.0)].into_iter().collect()))
+                .collect()
+        }
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let nsga2 = Nsga2::new(
+                ArithmeticCrossover::new(0.5),
+                GaussianMutation::new(0.5, 0.3),
+            );
+            let mut population = population(&mut rng);
+
+            for _ in 0..30 {
+                (population, _) = nsga2.evolve(&mut rng, &population);
+            }
+
+            let (_, stats) = nsga2.evolve(&mut rng, &population);
+
+            assert_eq!(stats.elites_survived(), 10);
+            assert!(stats.pareto_front().len() >= 10);
+
+            // The front should have converged onto `x` within `[0, 2]`,
+            // and spread across it
+            let xs: Vec<_> = population
+                .iter()
+                .filter(|individual| stats.pareto_front().contains(&individual.objectives()))
+                .map(|individual| individual.chromosome[0])
+                .collect();
+
+            assert!(xs.iter().all(|x| (-0.1..=2.1).contains(x)));
+            assert!(xs.iter().any(|&x| x < 0.5));
+            assert!(xs.iter().any(|&x| x > 1.5));
+        }
+
+        #[test]
+        fn keeps_better_half_unchanged() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let nsga2 = Nsga2::new(
+                ArithmeticCrossover::new(0.5),
+                GaussianMutation::new(0.5, 0.3),
+            );
+
+            let population: Vec<_> = [3.0, 1.0, -5.0, 8.0, 0.5, -1.0]
+                .into_iter()
+                .map(|x| Schaffer::create([x].into_iter().collect()))
+                .collect();
+
+            let (actual, stats) = nsga2.evolve(&mut rng, &population);
+
+            let survivors: Vec<_> = actual[..3]
+                .iter()
+                .map(|individual| individual.chromosome[0])
+                .collect();
+
+            assert_eq!(actual.len(), 6);
+
+            // Pareto front is `[1.0, 0.5]`, then comes the second front
+            // (`[3.0, -1.0]`), whose crowding distances tie
+            assert_eq!(survivors, [1.0, 0.5, 3.0]);
+            assert_eq!(stats.pareto_front().len(), 2);
+        }
+
+        #[test]
+        fn honours_bounds() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let bounds = Bounds::uniform(-10.0, 10.0).with_repair(Repair::Reflect);
+            let nsga2 = Nsga2::new(BlendCrossover::new(2.0), GaussianMutation::new(1.0, 5.0))
+                .with_bounds(bounds.clone());
+            let mut population = population(&mut rng);
+
+            for _ in 0..10 {
+                (population, _) = nsga2.evolve(&mut rng, &population);
+
+                assert!(population
+                    .iter()
+                    .all(|individual| individual.chromosome.is_within(&bounds)));
+            }
+        }
+    }
+}
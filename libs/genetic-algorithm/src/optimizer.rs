@@ -0,0 +1,128 @@
+use crate::*;
+
+/// Something that evolves a population of individuals, one generation
+/// at a time - e.g. `GeneticAlgorithm`, `EvolutionStrategy` or `CmaEs`.
+///
+/// Allows choosing the optimizer at runtime (e.g. from a config), by
+/// keeping it as `Box<dyn Optimizer<I>>`; the population passed into
+/// `Self::evolve()` must be already evaluated, the returned one is not.
+pub trait Optimizer<I>
+where
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<I::Gene>);
+}
+
+impl<I, O> Optimizer<I> for Box<O>
+where
+    I: Individual,
+    O: Optimizer<I> + ?Sized,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<I::Gene>) {
+        (**self).evolve(rng, population)
+    }
+}
+
+impl<S, I> Optimizer<I> for GeneticAlgorithm<S, I::Gene>
+where
+    S: SelectionMethod,
+    I: Individual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<I::Gene>) {
+        GeneticAlgorithm::evolve(self, rng, population)
+    }
+}
+
+impl<I> Optimizer<I> for Nsga2<I::Gene>
+where
+    I: MultiObjectiveIndividual,
+{
+    fn evolve(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<I::Gene>) {
+        Nsga2::evolve(self, rng, population)
+    }
+}
+
+/// Sphere function, `-(x[0]^2 + x[1]^2 + ...)`, which all the
+/// optimizers should be able to bring close to zero.
+#[cfg(test)]
+#[derive(Clone, Debug)]
+pub(crate) struct Sphere(Chromosome);
+
+#[cfg(test)]
+impl Individual for Sphere {
+    type Gene = f32;
+
+    fn create(chromosome: Chromosome) -> Self {
+        Self(chromosome)
+    }
+
+    fn chromosome(&self) -> &Chromosome {
+        &self.0
+    }
+
+    fn fitness(&self) -> f32 {
+        -self.0.iter().map(|x| x.powi(2)).sum::<f32>()
+    }
+}
+
+/// Returns `count` random `Sphere`s with `genes` genes each.
+#[cfg(test)]
+pub(crate) fn spheres(rng: &mut dyn RngCore, count: usize, genes: usize) -> Vec<Sphere> {
+    (0..count)
+        .map(|_| Sphere::create((0..genes).map(|_| rng.gen_range(-5.0..=5.0)).collect()))
+        .collect()
+}
+
+/// Evolves `population` for given number of generations, returning the
+/// best fitness found in the last one.
+#[cfg(test)]
+pub(crate) fn optimize<I>(
+    rng: &mut dyn RngCore,
+    optimizer: &mut dyn Optimizer<I>,
+    mut population: Vec<I>,
+    generations: usize,
+) -> f32
+where
+    I: Individual,
+{
+    for _ in 0..generations {
+        (population, _) = optimizer.evolve(rng, &population);
+    }
+
+    let (_, stats) = optimizer.evolve(rng, &population);
+
+    stats.max_fitness()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let optimizers: Vec<Box<dyn Optimizer<Sphere>>> = vec![
+            Box::new(
+                GeneticAlgorithm::new(
+                    TournamentSelection::new(2),
+                    ArithmeticCrossover::new(0.5),
+                    GaussianMutation::new(0.2, 0.1),
+                )
+                .with_replacement(Replacement::Elitist { elites: 1 }),
+            ),
+            Box::new(EvolutionStrategy::plus(5, GaussianMutation::new(1.0, 0.1))),
+            Box::new(CmaEs::new(1.0)),
+        ];
+
+        for mut optimizer in optimizers {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = spheres(&mut rng, 20, 5);
+            let initial = Statistics::new(&population).max_fitness();
+            let actual = optimize(&mut rng, &mut optimizer, population, 50);
+
+            assert!(actual > initial, "{} <= {}", actual, initial);
+            assert!(actual > -0.5, "{}", actual);
+        }
+    }
+}
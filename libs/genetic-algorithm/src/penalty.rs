@@ -0,0 +1,130 @@
+use crate::*;
+
+/// How `GeneticAlgorithm::evolve()` punishes individuals that violate the
+/// problem's constraints (see `Individual::violation()`), before elites
+/// and parents get picked.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Penalty {
+    /// Constraints are ignored.
+    #[default]
+    None,
+
+    /// Static penalty: `fitness - coeff * violation`.
+    ///
+    /// Penalized fitness can turn negative, which roulette wheel
+    /// selection doesn't accept - prefer `TournamentSelection` or
+    /// `RankSelection` then.
+    Static { coeff: f32 },
+
+    /// Proportional penalty: fitness gets divided by `1 + coeff *
+    /// violation` (or multiplied by it, if it's negative - so that
+    /// violators always end up worse off); keeps non-negative fitness
+    /// non-negative, so it works with any selection method.
+    Proportional { coeff: f32 },
+}
+
+impl Penalty {
+    /// Returns fitness of given individual after applying the penalty.
+    pub fn apply<I>(self, individual: &I) -> f32
+    where
+        I: Individual,
+    {
+        let fitness = individual.fitness();
+
+        match self {
+            Self::None => fitness,
+            Self::Static { coeff } => fitness - coeff * individual.violation(),
+
+            Self::Proportional { coeff } => {
+                let factor = 1.0 + coeff * individual.violation();
+
+                if fitness < 0.0 {
+                    fitness * factor
+                } else {
+                    fitness / factor
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Individual whose genes must not exceed 1.0.
+    struct Capped(Chromosome);
+
+    impl Individual for Capped {
+        type Gene = f32;
+
+        fn create(chromosome: Chromosome) -> Self {
+            Self(chromosome)
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.0
+        }
+
+        fn fitness(&self) -> f32 {
+            self.0.iter().sum()
+        }
+
+        fn violation(&self) -> f32 {
+            self.0.violation(&Bounds::uniform(f32::MIN, 1.0))
+        }
+    }
+
+    mod apply {
+        use super::*;
+
+        fn individual() -> Capped {
+            // fitness = 4.0, violation = 1.5
+            Capped::create([0.5, 2.5, 1.0].into_iter().collect())
+        }
+
+        #[test]
+        fn none() {
+            approx::assert_relative_eq!(Penalty::None.apply(&individual()), 4.0);
+        }
+
+        #[test]
+        fn static_() {
+            let penalty = Penalty::Static { coeff: 2.0 };
+
+            approx::assert_relative_eq!(penalty.apply(&individual()), 1.0);
+        }
+
+        #[test]
+        fn static_given_large_violation() {
+            let penalty = Penalty::Static { coeff: 10.0 };
+
+            // Not floored, so that heavier violators keep scoring worse
+            approx::assert_relative_eq!(penalty.apply(&individual()), -11.0);
+        }
+
+        #[test]
+        fn proportional() {
+            let penalty = Penalty::Proportional { coeff: 2.0 };
+
+            approx::assert_relative_eq!(penalty.apply(&individual()), 1.0);
+        }
+
+        #[test]
+        fn proportional_given_negative_fitness() {
+            // fitness = -0.5, violation = 1.5
+            let individual = Capped::create([-3.0, 2.5].into_iter().collect());
+            let penalty = Penalty::Proportional { coeff: 2.0 };
+
+            approx::assert_relative_eq!(penalty.apply(&individual), -2.0);
+        }
+
+        #[test]
+        fn given_feasible_individual() {
+            let individual = Capped::create([0.5, 1.0].into_iter().collect());
+            let penalty = Penalty::Static { coeff: 2.0 };
+
+            approx::assert_relative_eq!(penalty.apply(&individual), 1.5);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/// How `GeneticAlgorithm::evolve()` builds the next generation out of
+/// the current population and its children.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Replacement {
+    /// The entire population gets replaced by children.
+    ///
+    /// This means that the best individual found so far can get lost
+    /// through crossover and mutation.
+    #[default]
+    Generational,
+
+    /// The best `elites` individuals get copied into the next generation
+    /// unchanged, the rest is replaced by children.
+    Elitist { elites: usize },
+
+    /// Only the worst `replaced` individuals get replaced by children,
+    /// everybody else survives unchanged.
+    ///
+    /// This is the same as `Elitist { elites: population - replaced }`,
+    /// just spelled from the other side - handy for evolving in small
+    /// steps (e.g. replacing one or two individuals at a time).
+    SteadyState { replaced: usize },
+}
+
+impl Replacement {
+    /// Returns how many of the best individuals survive into the next
+    /// generation of given size.
+    pub(crate) fn survivors(self, population: usize) -> usize {
+        match self {
+            Self::Generational => 0,
+            Self::Elitist { elites } => elites.min(population),
+            Self::SteadyState { replaced } => population.saturating_sub(replaced),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod survivors {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(Replacement::Generational.survivors(10), 0);
+            assert_eq!(Replacement::Elitist { elites: 2 }.survivors(10), 2);
+            assert_eq!(Replacement::Elitist { elites: 20 }.survivors(10), 10);
+            assert_eq!(Replacement::SteadyState { replaced: 2 }.survivors(10), 8);
+            assert_eq!(Replacement::SteadyState { replaced: 20 }.survivors(10), 0);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+pub use self::{rank::*, roulette_wheel::*, stochastic_universal::*, tournament::*, truncation::*};
+
+use crate::*;
+
+mod rank;
+mod roulette_wheel;
+mod stochastic_universal;
+mod tournament;
+mod truncation;
+
+/// Picks an index of an individual each time it's called; see
+/// `SelectionMethod::sampler()`.
+pub type Sampler<'a> = Box<dyn FnMut(&mut dyn RngCore) -> usize + 'a>;
+
+/// Picks individuals by their fitness.
+///
+/// Methods work on fitnesses alone (and return indices of the picked
+/// individuals), which allows `GeneticAlgorithm` to select by fitness
+/// that's been penalized or shared within niches; `Self::select()` and
+/// `Self::select_many()` then map these indices back to individuals.
+pub trait SelectionMethod: Debug {
+    /// Prepares for picking `count` individuals, given fitness of each
+    /// one (e.g. ranks them, so that it doesn't happen for each pick);
+    /// the returned sampler then picks one individual per call.
+    ///
+    /// Most methods pick each individual on its own, but some (like
+    /// `StochasticUniversalSampling`) pick better when they see the
+    /// entire batch.
+    fn sampler<'a>(&'a self, fitnesses: &'a [f32], count: usize) -> Sampler<'a>;
+
+    /// Picks an individual, given fitness of each one; returns its index.
+    fn select_index(&self, rng: &mut dyn RngCore, fitnesses: &[f32]) -> usize {
+        self.sampler(fitnesses, 1)(rng)
+    }
+
+    /// Picks `count` individuals at once - e.g. all the parents of the
+    /// next generation; returns their indices.
+    fn select_indices(&self, rng: &mut dyn RngCore, fitnesses: &[f32], count: usize) -> Vec<usize> {
+        let mut sampler = self.sampler(fitnesses, count);
+
+        (0..count).map(|_| sampler(rng)).collect()
+    }
+
+    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    where
+        I: Individual,
+    {
+        &population[self.select_index(rng, &fitnesses(population))]
+    }
+
+    /// Selects `count` individuals at once; see `Self::select_indices()`.
+    fn select_many<'a, I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+    where
+        I: Individual,
+    {
+        self.select_indices(rng, &fitnesses(population), count)
+            .into_iter()
+            .map(|index| &population[index])
+            .collect()
+    }
+}
+
+fn fitnesses<I>(population: &[I]) -> Vec<f32>
+where
+    I: Individual,
+{
+    population
+        .iter()
+        .map(|individual| individual.fitness())
+        .collect()
+}
+
+/// Counts how many times each fitness has been selected.
+#[cfg(test)]
+pub(crate) fn histogram<'a>(
+    individuals: impl IntoIterator<Item = &'a TestIndividual>,
+) -> std::collections::BTreeMap<i32, usize> {
+    individuals
+        .into_iter()
+        .fold(Default::default(), |mut histogram, individual| {
+            // as _ means "compiler, pretty please infer what type is required and cast this value into it".
+            // since we've declared the btreemap key to be i32 the type is i32
+            *histogram.entry(individual.fitness() as _).or_default() += 1;
+
+            histogram
+        })
+}
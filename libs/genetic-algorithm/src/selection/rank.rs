@@ -0,0 +1,126 @@
+use crate::*;
+use rand::distributions::{Distribution, WeightedIndex};
+
+/// Linear rank selection: individuals are sorted by their fitness and
+/// picked proportionally to their position in that order - the worst
+/// one has weight of 1, the best one has weight of `population.len()`.
+///
+/// Compared to `RouletteWheelSelection` this keeps a steady selection
+/// pressure no matter how fitnesses are spread - a single outstanding
+/// individual doesn't take over the population, and individuals with
+/// near-identical fitnesses are still told apart. Individuals with equal
+/// fitness share the same (average) rank.
+#[derive(Clone, Debug, Default)]
+pub struct RankSelection;
+
+impl RankSelection {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl RankSelection {
+    /// Returns rank of each individual, to be used as its weight.
+    fn ranks(fitnesses: &[f32]) -> WeightedIndex<f32> {
+        let mut sorted = fitnesses.to_vec();
+
+        sorted.sort_by(f32::total_cmp);
+
+        let ranks = fitnesses.iter().map(|&fitness| {
+            let worse = sorted.partition_point(|&other| other < fitness);
+            let not_better = sorted.partition_point(|&other| other <= fitness);
+
+            // Ranks of `worse + 1 ..= not_better`, averaged
+            (worse + not_better + 1) as f32 / 2.0
+        });
+
+        WeightedIndex::new(ranks).expect("got an empty population")
+    }
+}
+
+impl SelectionMethod for RankSelection {
+    fn sampler<'a>(&'a self, fitnesses: &'a [f32], _: usize) -> Sampler<'a> {
+        let ranks = Self::ranks(fitnesses);
+
+        Box::new(move |rng| ranks.sample(rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let method = RankSelection::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(20.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(400.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let actual_histogram = histogram((0..1000).map(|_| method.select(&mut rng, &population)));
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            1 => 104,
+            3 => 183,
+            20 => 300,
+            400 => 413,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn select_many() {
+        let method = RankSelection::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(20.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(400.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let actual_histogram = histogram(method.select_many(&mut rng, &population, 1000));
+
+        // Same as picking one by one
+        let expected_histogram = maplit::btreemap! {
+            1 => 104,
+            3 => 183,
+            20 => 300,
+            400 => 413,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn shares_ranks_between_equal_fitnesses() {
+        let method = RankSelection::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(0.0),
+            TestIndividual::new(2.0),
+            TestIndividual::new(0.0),
+        ];
+
+        let actual_histogram = histogram((0..1000).map(|_| method.select(&mut rng, &population)));
+
+        // Ranks: 1.5, 3.0 and 1.5
+        let expected_histogram = maplit::btreemap! {
+            0 => 477,
+            2 => 523,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::IteratorRandom;
+
+/// Fitness proportionate selection: an individual twice as fit as
+/// another one is twice as likely to get picked.
+///
+/// When the entire population has zero fitness (e.g. no bird managed to
+/// eat anything), everybody's equally likely to get picked.
+#[derive(Clone, Debug)]
+pub struct RouletteWheelSelection;
+
+impl RouletteWheelSelection {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SelectionMethod for RouletteWheelSelection {
+    fn sampler<'a>(&'a self, fitnesses: &'a [f32], _: usize) -> Sampler<'a> {
+        match WeightedIndex::new(fitnesses) {
+            Ok(weights) => Box::new(move |rng| weights.sample(rng)),
+
+            Err(WeightedError::AllWeightsZero) => Box::new(|rng| {
+                (0..fitnesses.len())
+                    .choose(rng)
+                    .expect("got an empty population")
+            }),
+
+            Err(err) => panic!("couldn't select an individual: {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test() {
+        let method = RouletteWheelSelection::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let actual_histogram = histogram((0..1000).map(|_| method.select(&mut rng, &population)));
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            1 => 98,
+            2 => 202,
+            3 => 278,
+            4 => 422,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn survives_zero_fitness() {
+        let method = RouletteWheelSelection::new();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::WithChromosome {
+                chromosome: [0.0].into_iter().collect(),
+            },
+            TestIndividual::WithChromosome {
+                chromosome: [0.0, 0.0].into_iter().collect(),
+            },
+        ];
+
+        let actual_histogram: BTreeMap<usize, usize> = (0..1000)
+            .map(|_| method.select(&mut rng, &population).chromosome().len())
+            .fold(Default::default(), |mut histogram, len| {
+                *histogram.entry(len).or_default() += 1;
+                histogram
+            });
+
+        let expected_histogram = maplit::btreemap! {
+            1 => 479,
+            2 => 521,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::*;
+use rand::seq::IteratorRandom;
+
+/// Stochastic universal sampling (Baker, 1987): like
+/// `RouletteWheelSelection`, but instead of spinning the wheel once per
+/// individual, the wheel is spun once with `count` equally spaced
+/// pointers.
+///
+/// Each individual gets picked either `floor(expected)` or
+/// `ceil(expected)` times - a lucky spin can't make a mediocre
+/// individual the parent of the entire next generation.
+///
+/// Selecting a single individual is the same as using
+/// `RouletteWheelSelection`; the difference is in `Self::select_indices()`
+/// (and in samplers prepared for more than one pick).
+#[derive(Clone, Debug, Default)]
+pub struct StochasticUniversalSampling;
+
+impl StochasticUniversalSampling {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SelectionMethod for StochasticUniversalSampling {
+    fn sampler<'a>(&'a self, fitnesses: &'a [f32], count: usize) -> Sampler<'a> {
+        let mut selected = Vec::new();
+
+        // The wheel gets spun on the first pick, for the entire batch
+        Box::new(move |rng| {
+            if selected.is_empty() {
+                selected = self.select_indices(rng, fitnesses, count.max(1));
+                selected.reverse();
+            }
+
+            selected.pop().unwrap()
+        })
+    }
+
+    fn select_indices(&self, rng: &mut dyn RngCore, fitnesses: &[f32], count: usize) -> Vec<usize> {
+        assert!(!fitnesses.is_empty(), "got an empty population");
+
+        // There'd be no spacing between pointers
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let total_fitness: f32 = fitnesses.iter().sum();
+
+        if total_fitness <= 0.0 {
+            return (0..count)
+                .map(|_| (0..fitnesses.len()).choose(rng).unwrap())
+                .collect();
+        }
+
+        let spacing = total_fitness / count as f32;
+        let start = rng.gen_range(0.0..spacing);
+
+        let mut index = 0;
+        let mut cumulative_fitness = fitnesses[0];
+
+        let mut selected: Vec<_> = (0..count)
+            .map(|n| {
+                let pointer = start + n as f32 * spacing;
+
+                while cumulative_fitness <= pointer && index + 1 < fitnesses.len() {
+                    index += 1;
+                    cumulative_fitness += fitnesses[index];
+                }
+
+                index
+            })
+            .collect();
+
+        // Pointers visit the population in order, so without shuffling
+        // neighbouring individuals would always end up mating together
+        selected.shuffle(rng);
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ]
+    }
+
+    mod select {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+
+            let actual_histogram =
+                histogram((0..1000).map(|_| method.select(&mut rng, &population)));
+
+            let expected_histogram = maplit::btreemap! {
+                // fitness => how many times this fitness has been chosen
+                1 => 98,
+                2 => 202,
+                3 => 278,
+                4 => 422,
+            };
+
+            assert_eq!(actual_histogram, expected_histogram);
+        }
+    }
+
+    mod select_many {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+
+            let actual_histogram = histogram(method.select_many(&mut rng, &population, 1000));
+
+            // Exactly proportional to fitness, since 1000 divides evenly
+            let expected_histogram = maplit::btreemap! {
+                1 => 100,
+                2 => 200,
+                3 => 300,
+                4 => 400,
+            };
+
+            assert_eq!(actual_histogram, expected_histogram);
+        }
+
+        #[test]
+        fn never_strays_from_expected_counts() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = population();
+
+            for _ in 0..100 {
+                let actual_histogram = histogram(method.select_many(&mut rng, &population, 4));
+
+                // Expected counts are 0.4, 0.8, 1.2 and 1.6
+                assert!(actual_histogram.get(&1).copied().unwrap_or_default() <= 1);
+                assert!(actual_histogram.get(&2).copied().unwrap_or_default() <= 1);
+                assert!((1..=2).contains(&actual_histogram[&3]));
+                assert!((1..=2).contains(&actual_histogram[&4]));
+            }
+        }
+
+        #[test]
+        fn given_zero_count() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            assert!(method.select_many(&mut rng, &population(), 0).is_empty());
+        }
+
+        #[test]
+        fn survives_zero_fitness() {
+            let method = StochasticUniversalSampling::new();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let population = vec![TestIndividual::new(0.0), TestIndividual::new(0.0)];
+
+            assert_eq!(method.select_many(&mut rng, &population, 10).len(), 10);
+        }
+    }
+}
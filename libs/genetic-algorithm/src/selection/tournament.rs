@@ -0,0 +1,89 @@
+use crate::*;
+use rand::seq::IteratorRandom;
+
+/// Picks `size` random individuals (possibly repeating some) and returns
+/// the fittest of them.
+///
+/// The larger the tournament, the higher the selection pressure - with
+/// size of 1 this becomes a purely random choice, while size equal to
+/// the population's size picks the best individual most of the time.
+#[derive(Clone, Debug)]
+pub struct TournamentSelection {
+    size: usize,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0);
+
+        Self { size }
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn sampler<'a>(&'a self, fitnesses: &'a [f32], _: usize) -> Sampler<'a> {
+        Box::new(|rng| {
+            (0..self.size)
+                .map(|_| {
+                    (0..fitnesses.len())
+                        .choose(rng)
+                        .expect("got an empty population")
+                })
+                .max_by(|&a, &b| fitnesses[a].total_cmp(&fitnesses[b]))
+                .unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ]
+    }
+
+    #[test]
+    fn test() {
+        let method = TournamentSelection::new(2);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let actual_histogram = histogram((0..1000).map(|_| method.select(&mut rng, &population)));
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            1 => 66,
+            2 => 179,
+            3 => 299,
+            4 => 456,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn given_size_of_one_picks_randomly() {
+        let method = TournamentSelection::new(1);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let actual_histogram = histogram((0..1000).map(|_| method.select(&mut rng, &population)));
+
+        let expected_histogram = maplit::btreemap! {
+            1 => 262,
+            2 => 246,
+            3 => 238,
+            4 => 254,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+}
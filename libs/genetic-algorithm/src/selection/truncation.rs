@@ -0,0 +1,108 @@
+use crate::*;
+
+/// Picks uniformly among the best `fraction` of the population (always
+/// at least one individual); everybody else never gets a chance.
+#[derive(Clone, Debug)]
+pub struct TruncationSelection {
+    /// Part of the population that's allowed to reproduce:
+    /// - 0.1 = only the best 10% of individuals
+    /// - 1.0 = all individuals, i.e. a purely random choice
+    fraction: f32,
+}
+
+impl TruncationSelection {
+    pub fn new(fraction: f32) -> Self {
+        assert!(fraction > 0.0 && fraction <= 1.0);
+
+        Self { fraction }
+    }
+}
+
+impl TruncationSelection {
+    /// Returns indices of individuals allowed to reproduce.
+    fn best(&self, fitnesses: &[f32]) -> Vec<usize> {
+        assert!(!fitnesses.is_empty(), "got an empty population");
+
+        let mut best: Vec<_> = (0..fitnesses.len()).collect();
+
+        best.sort_by(|&a, &b| fitnesses[b].total_cmp(&fitnesses[a]));
+
+        let count = (best.len() as f32 * self.fraction).ceil() as usize;
+
+        best.truncate(count.clamp(1, best.len()));
+        best
+    }
+}
+
+impl SelectionMethod for TruncationSelection {
+    fn sampler<'a>(&'a self, fitnesses: &'a [f32], _: usize) -> Sampler<'a> {
+        let best = self.best(fitnesses);
+
+        Box::new(move |rng| *best.choose(rng).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ]
+    }
+
+    #[test]
+    fn test() {
+        let method = TruncationSelection::new(0.5);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let actual_histogram = histogram((0..1000).map(|_| method.select(&mut rng, &population)));
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            3 => 521,
+            4 => 479,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn select_many() {
+        let method = TruncationSelection::new(0.5);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let actual_histogram = histogram(method.select_many(&mut rng, &population, 1000));
+
+        // Same as picking one by one
+        let expected_histogram = maplit::btreemap! {
+            3 => 521,
+            4 => 479,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn always_keeps_at_least_one_individual() {
+        let method = TruncationSelection::new(0.01);
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        let actual_histogram = histogram((0..100).map(|_| method.select(&mut rng, &population)));
+
+        let expected_histogram = maplit::btreemap! {
+            4 => 100,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+}
@@ -0,0 +1,329 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Statistics<G = f32> {
+    min_fitness: f32,
+    max_fitness: f32,
+    avg_fitness: f32,
+    std_dev_fitness: f32,
+
+    /// Fitnesses of the entire population, sorted ascending
+    fitnesses: Vec<f32>,
+
+    diversity: f32,
+    best_chromosome: Chromosome<G>,
+    pub(crate) elites_survived: usize,
+    pub(crate) pareto_front: Vec<Vec<f32>>,
+    pub(crate) species: Vec<usize>,
+}
+
+impl<G> Statistics<G>
+where
+    G: Gene,
+{
+    pub fn new<I>(population: &[I]) -> Self
+    where
+        I: Individual<Gene = G>,
+    {
+        assert!(!population.is_empty());
+
+        let mut min_fitness = population[0].fitness();
+        let mut max_fitness = min_fitness;
+        let mut sum_fitness = 0.0;
+        let mut best = &population[0];
+
+        for individual in population {
+            let fitness = individual.fitness();
+
+            if fitness > max_fitness {
+                best = individual;
+            }
+
+            min_fitness = min_fitness.min(fitness);
+            max_fitness = max_fitness.max(fitness);
+            sum_fitness += fitness;
+        }
+
+        let avg_fitness = sum_fitness / (population.len() as f32);
+
+        let variance = population
+            .iter()
+            .map(|individual| (individual.fitness() - avg_fitness).powi(2))
+            .sum::<f32>()
+            / (population.len() as f32);
+
+        let mut fitnesses: Vec<_> = population
+            .iter()
+            .map(|individual| individual.fitness())
+            .collect();
+
+        fitnesses.sort_by(f32::total_cmp);
+
+        Self {
+            min_fitness,
+            max_fitness,
+            avg_fitness,
+            std_dev_fitness: variance.sqrt(),
+            fitnesses,
+            diversity: Self::diversity_of(population),
+            best_chromosome: best.chromosome().clone(),
+            elites_survived: 0,
+            pareto_front: Vec::new(),
+            species: Vec::new(),
+        }
+    }
+
+    /// Mean distance between genes of each pair of individuals - for
+    /// chromosomes of the same length that's the average of
+    /// `Chromosome::distance()` over all pairs.
+    ///
+    /// Instead of comparing all the pairs, genes get sorted one position
+    /// at a time, which brings it down to `O(genes * n log n)`.
+    fn diversity_of<I>(population: &[I]) -> f32
+    where
+        I: Individual<Gene = G>,
+    {
+        let genes = population
+            .iter()
+            .map(|individual| individual.chromosome().len())
+            .max()
+            .unwrap_or_default();
+
+        let mut positions = Vec::with_capacity(population.len());
+        let mut sum_distance = 0.0;
+        let mut counted_genes = 0;
+
+        for gene in 0..genes {
+            positions.clear();
+
+            positions.extend(population.iter().filter_map(|individual| {
+                let chromosome = individual.chromosome();
+
+                (gene < chromosome.len()).then(|| chromosome[gene].position())
+            }));
+
+            let n = positions.len();
+
+            if n < 2 {
+                continue;
+            }
+
+            positions.sort_by(f64::total_cmp);
+
+            // When sorted, each position lies above `index` other ones and
+            // below `n - 1 - index` other ones - so that's how many times
+            // it gets added to (and subtracted from) the sum of distances
+            let distance: f64 = positions
+                .iter()
+                .enumerate()
+                .map(|(index, position)| position * (2.0 * index as f64 + 1.0 - n as f64))
+                .sum();
+
+            sum_distance += distance / (n * (n - 1) / 2) as f64;
+            counted_genes += 1;
+        }
+
+        if counted_genes > 0 {
+            (sum_distance / counted_genes as f64) as f32
+        } else {
+            0.0
+        }
+    }
+
+    pub fn min_fitness(&self) -> f32 {
+        self.min_fitness
+    }
+
+    pub fn max_fitness(&self) -> f32 {
+        self.max_fitness
+    }
+
+    pub fn avg_fitness(&self) -> f32 {
+        self.avg_fitness
+    }
+
+    pub fn median_fitness(&self) -> f32 {
+        self.percentile_fitness(50.0)
+    }
+
+    /// Standard deviation of the fitness across the population.
+    pub fn std_dev_fitness(&self) -> f32 {
+        self.std_dev_fitness
+    }
+
+    /// Fitness below which given percentage (0..=100) of the population
+    /// lies; values between individuals are interpolated linearly.
+    pub fn percentile_fitness(&self, percentile: f32) -> f32 {
+        assert!((0.0..=100.0).contains(&percentile));
+
+        let position = percentile / 100.0 * (self.fitnesses.len() - 1) as f32;
+        let lower = position.floor() as usize;
+        let upper = position.ceil() as usize;
+        let weight = position - lower as f32;
+
+        self.fitnesses[lower] + weight * (self.fitnesses[upper] - self.fitnesses[lower])
+    }
+
+    pub fn lower_quartile_fitness(&self) -> f32 {
+        self.percentile_fitness(25.0)
+    }
+
+    pub fn upper_quartile_fitness(&self) -> f32 {
+        self.percentile_fitness(75.0)
+    }
+
+    /// Mean distance between genes of each pair of individuals.
+    ///
+    /// When it approaches zero, all individuals are more or less the
+    /// same - which, if fitness isn't satisfying yet, is a sign of a
+    /// premature convergence.
+    pub fn diversity(&self) -> f32 {
+        self.diversity
+    }
+
+    /// Chromosome of the fittest individual.
+    pub fn best_chromosome(&self) -> &Chromosome<G> {
+        &self.best_chromosome
+    }
+
+    /// How many elites survived into the next generation, i.e. got
+    /// copied into it unchanged (see `Replacement`) - that's the
+    /// configured number of elites, capped at the population's size.
+    pub fn elites_survived(&self) -> usize {
+        self.elites_survived
+    }
+
+    /// Objectives of individuals that aren't dominated by anyone else
+    /// (see `Nsga2`); empty for single-objective evolution.
+    pub fn pareto_front(&self) -> &[Vec<f32>] {
+        &self.pareto_front
+    }
+
+    /// Number of individuals in each species, starting with the species
+    /// of the fittest individual (see `Niching`); empty when niching is
+    /// disabled.
+    pub fn species(&self) -> &[usize] {
+        &self.species
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().copied().collect())
+    }
+
+    fn statistics() -> Statistics {
+        Statistics::new(&[
+            individual(&[1.0, 1.0, 1.0]), // fitness = 3.0
+            individual(&[0.0, 0.0, 0.0]), // fitness = 0.0
+            individual(&[1.0, 2.0, 4.0]), // fitness = 7.0
+            individual(&[1.0, 2.0, 1.0]), // fitness = 4.0
+            individual(&[0.0, 0.0, 1.0]), // fitness = 1.0
+        ])
+    }
+
+    #[test]
+    fn test() {
+        let stats = statistics();
+
+        approx::assert_relative_eq!(stats.min_fitness(), 0.0);
+        approx::assert_relative_eq!(stats.max_fitness(), 7.0);
+        approx::assert_relative_eq!(stats.avg_fitness(), 3.0);
+        approx::assert_relative_eq!(stats.median_fitness(), 3.0);
+        approx::assert_relative_eq!(stats.std_dev_fitness(), 2.4494898);
+        approx::assert_relative_eq!(stats.lower_quartile_fitness(), 1.0);
+        approx::assert_relative_eq!(stats.upper_quartile_fitness(), 4.0);
+        assert_eq!(
+            stats.best_chromosome(),
+            &[1.0, 2.0, 4.0].into_iter().collect()
+        );
+    }
+
+    mod percentile_fitness {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let stats = statistics();
+
+            approx::assert_relative_eq!(stats.percentile_fitness(0.0), 0.0);
+            approx::assert_relative_eq!(stats.percentile_fitness(10.0), 0.4);
+            approx::assert_relative_eq!(stats.percentile_fitness(90.0), 5.8);
+            approx::assert_relative_eq!(stats.percentile_fitness(100.0), 7.0);
+        }
+
+        #[test]
+        fn given_single_individual() {
+            let stats = Statistics::new(&[individual(&[2.0])]);
+
+            approx::assert_relative_eq!(stats.median_fitness(), 2.0);
+            approx::assert_relative_eq!(stats.percentile_fitness(75.0), 2.0);
+        }
+    }
+
+    mod diversity {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        #[test]
+        fn test() {
+            let stats = Statistics::new(&[
+                individual(&[0.0, 0.0]),
+                individual(&[1.0, 0.0]),
+                individual(&[1.0, 2.0]),
+            ]);
+
+            // Pairs: (1.0 + 0.0) / 2 = 0.5, (1.0 + 2.0) / 2 = 1.5 and
+            // (0.0 + 2.0) / 2 = 1.0
+            approx::assert_relative_eq!(stats.diversity(), (0.5 + 1.5 + 1.0) / 3.0);
+        }
+
+        #[test]
+        fn given_identical_individuals() {
+            let stats = Statistics::new(&[individual(&[1.0, 2.0]), individual(&[1.0, 2.0])]);
+
+            approx::assert_relative_eq!(stats.diversity(), 0.0);
+        }
+
+        #[test]
+        fn matches_distances_between_all_pairs() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let population: Vec<_> = (0..50)
+                .map(|_| {
+                    let genes: Vec<f32> = (0..4).map(|_| rng.gen_range(-1.0..1.0)).collect();
+
+                    individual(&genes)
+                })
+                .collect();
+
+            let mut sum_distance = 0.0;
+            let mut pairs = 0;
+
+            for (index, a) in population.iter().enumerate() {
+                for b in &population[index + 1..] {
+                    sum_distance += a.chromosome().distance(b.chromosome());
+                    pairs += 1;
+                }
+            }
+
+            approx::assert_relative_eq!(
+                Statistics::new(&population).diversity(),
+                sum_distance / pairs as f32,
+                epsilon = 1e-5
+            );
+        }
+
+        #[test]
+        fn compares_only_common_genes() {
+            let stats = Statistics::new(&[individual(&[1.0]), individual(&[2.0, 5.0])]);
+
+            approx::assert_relative_eq!(stats.diversity(), 1.0);
+        }
+    }
+}
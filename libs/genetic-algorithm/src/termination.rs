@@ -0,0 +1,208 @@
+use std::time::{Duration, Instant};
+
+/// When `Evolution::run()` stops; at least one criterion must be set.
+///
+/// Criteria are checked after each generation gets evaluated, so e.g.
+/// the time budget can be exceeded by up to one generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Termination {
+    max_generations: Option<usize>,
+    target_fitness: Option<f32>,
+    stagnation: Option<usize>,
+    time_budget: Option<Duration>,
+}
+
+/// Which criterion of `Termination` has been met.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    MaxGenerations,
+    TargetFitness,
+    Stagnation,
+    TimeBudget,
+}
+
+impl Termination {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops after evaluating given number of generations.
+    pub fn with_max_generations(mut self, generations: usize) -> Self {
+        assert!(generations > 0);
+
+        self.max_generations = Some(generations);
+        self
+    }
+
+    /// Stops as soon as any individual reaches given fitness.
+    pub fn with_target_fitness(mut self, fitness: f32) -> Self {
+        self.target_fitness = Some(fitness);
+        self
+    }
+
+    /// Stops when the best fitness hasn't improved for given number of
+    /// generations.
+    pub fn with_stagnation(mut self, generations: usize) -> Self {
+        assert!(generations > 0);
+
+        self.stagnation = Some(generations);
+        self
+    }
+
+    /// Stops once given time has passed since the start.
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    pub(crate) fn is_set(&self) -> bool {
+        self.max_generations.is_some()
+            || self.target_fitness.is_some()
+            || self.stagnation.is_some()
+            || self.time_budget.is_some()
+    }
+
+    pub(crate) fn check(&self, progress: &Progress) -> Option<StopReason> {
+        if let Some(target_fitness) = self.target_fitness {
+            if progress.best_fitness >= target_fitness {
+                return Some(StopReason::TargetFitness);
+            }
+        }
+
+        if let Some(max_generations) = self.max_generations {
+            if progress.generations >= max_generations {
+                return Some(StopReason::MaxGenerations);
+            }
+        }
+
+        if let Some(stagnation) = self.stagnation {
+            if progress.stagnant >= stagnation {
+                return Some(StopReason::Stagnation);
+            }
+        }
+
+        if let Some(time_budget) = self.time_budget {
+            if progress.started.elapsed() >= time_budget {
+                return Some(StopReason::TimeBudget);
+            }
+        }
+
+        None
+    }
+}
+
+/// What `Termination` looks at.
+#[derive(Clone, Debug)]
+pub(crate) struct Progress {
+    /// Number of generations evaluated so far
+    generations: usize,
+
+    /// Best fitness seen so far
+    best_fitness: f32,
+
+    /// Number of generations since `best_fitness` has last improved
+    stagnant: usize,
+
+    started: Instant,
+}
+
+impl Progress {
+    pub(crate) fn new() -> Self {
+        Self {
+            generations: 0,
+            best_fitness: f32::NEG_INFINITY,
+            stagnant: 0,
+            started: Instant::now(),
+        }
+    }
+
+    /// Records a freshly evaluated generation, given its best fitness.
+    pub(crate) fn record(&mut self, max_fitness: f32) {
+        self.generations += 1;
+
+        if max_fitness > self.best_fitness {
+            self.best_fitness = max_fitness;
+            self.stagnant = 0;
+        } else {
+            self.stagnant += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(fitnesses: &[f32]) -> Progress {
+        let mut progress = Progress::new();
+
+        for &fitness in fitnesses {
+            progress.record(fitness);
+        }
+
+        progress
+    }
+
+    mod check {
+        use super::*;
+
+        #[test]
+        fn max_generations() {
+            let termination = Termination::new().with_max_generations(3);
+
+            assert_eq!(termination.check(&progress(&[1.0, 2.0])), None);
+
+            assert_eq!(
+                termination.check(&progress(&[1.0, 2.0, 3.0])),
+                Some(StopReason::MaxGenerations)
+            );
+        }
+
+        #[test]
+        fn target_fitness() {
+            let termination = Termination::new().with_target_fitness(5.0);
+
+            assert_eq!(termination.check(&progress(&[1.0, 4.9])), None);
+
+            assert_eq!(
+                termination.check(&progress(&[1.0, 5.0, 2.0])),
+                Some(StopReason::TargetFitness)
+            );
+        }
+
+        #[test]
+        fn stagnation() {
+            let termination = Termination::new().with_stagnation(2);
+
+            assert_eq!(
+                termination.check(&progress(&[1.0, 2.0, 2.0, 3.0, 1.0])),
+                None
+            );
+
+            assert_eq!(
+                termination.check(&progress(&[1.0, 2.0, 2.0, 1.5])),
+                Some(StopReason::Stagnation)
+            );
+        }
+
+        #[test]
+        fn time_budget() {
+            let termination = Termination::new().with_time_budget(Duration::ZERO);
+
+            assert_eq!(
+                termination.check(&progress(&[])),
+                Some(StopReason::TimeBudget)
+            );
+
+            let termination = Termination::new().with_time_budget(Duration::from_secs(3600));
+
+            assert_eq!(termination.check(&progress(&[])), None);
+        }
+
+        #[test]
+        fn given_nothing() {
+            assert!(!Termination::new().is_set());
+            assert_eq!(Termination::new().check(&progress(&[1.0, 1.0])), None);
+        }
+    }
+}
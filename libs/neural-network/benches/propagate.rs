@@ -0,0 +1,77 @@
+//! Compares propagating a whole population through `Network::propagate()`
+//! (one call per bird) with a single `NetworkBatch::propagate()`.
+//!
+//! Uses the same topology as `lib-simulation`'s brains (9 eye cells).
+//!
+//! Run with `cargo bench -p lib-neural-network`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lib_neural_network::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+const CELLS: usize = 9;
+
+fn topology() -> [LayerTopology; 3] {
+    [
+        LayerTopology {
+            neurons: CELLS,
+            activation: Activation::Relu,
+            kind: LayerKind::Dense,
+        },
+        LayerTopology {
+            neurons: 2 * CELLS,
+            activation: Activation::Relu,
+            kind: LayerKind::Dense,
+        },
+        LayerTopology {
+            neurons: 2,
+            activation: Activation::Tanh,
+            kind: LayerKind::Dense,
+        },
+    ]
+}
+
+fn propagate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("propagate");
+    let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+    for birds in [40, 1_000, 10_000] {
+        let networks: Vec<_> = (0..birds)
+            .map(|_| Network::random(&mut rng, &topology()))
+            .collect();
+
+        let inputs: Vec<f32> = (0..birds * CELLS)
+            .map(|_| rng.gen_range(0.0..=1.0))
+            .collect();
+
+        group.throughput(Throughput::Elements(birds as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("per_network", birds),
+            &inputs,
+            |b, inputs| {
+                b.iter(|| {
+                    networks
+                        .iter()
+                        .zip(inputs.chunks(CELLS))
+                        .map(|(network, inputs)| network.propagate(inputs.to_vec()))
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+
+        let batch = NetworkBatch::from_networks(&networks);
+        let mut buffer = batch.buffer();
+        let mut outputs = vec![0.0; birds * batch.output_size()];
+
+        group.bench_with_input(BenchmarkId::new("batch", birds), &inputs, |b, inputs| {
+            b.iter(|| batch.propagate(inputs, &mut outputs, &mut buffer))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, propagate);
+criterion_main!(benches);
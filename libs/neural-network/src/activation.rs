@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+/// Slope used by `Activation::LeakyRelu` for negative inputs.
+const LEAKY_RELU_SLOPE: f32 = 0.01;
+
+/// Function applied to a neuron's output (bias + weighted inputs).
+///
+/// ReLU is the default, since that's what every layer used to be
+/// hard-coded to - but it can never produce a negative number, so
+/// output layers that need to (e.g. "turn left") should pick one of
+/// `Tanh`, `Softsign`, `LeakyRelu` or `Identity` instead.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activation {
+    /// max(0, x)
+    #[default]
+    Relu,
+
+    /// x for x > 0, 0.01 * x otherwise
+    LeakyRelu,
+
+    /// 1 / (1 + e^-x), range (0, 1)
+    Sigmoid,
+
+    /// tanh(x), range (-1, 1)
+    Tanh,
+
+    /// x
+    Identity,
+
+    /// x / (1 + |x|), range (-1, 1)
+    Softsign,
+}
+
+impl Activation {
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            Self::Relu => x.max(0.0),
+            Self::LeakyRelu => {
+                if x > 0.0 {
+                    x
+                } else {
+                    LEAKY_RELU_SLOPE * x
+                }
+            }
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::Tanh => x.tanh(),
+            Self::Identity => x,
+            Self::Softsign => x / (1.0 + x.abs()),
+        }
+    }
+
+    /// Derivative of `apply()` at given (pre-activation) `x`; used by
+    /// backpropagation.
+    pub fn derivative(self, x: f32) -> f32 {
+        match self {
+            Self::Relu => {
+                if x > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::LeakyRelu => {
+                if x > 0.0 {
+                    1.0
+                } else {
+                    LEAKY_RELU_SLOPE
+                }
+            }
+            Self::Sigmoid => {
+                let y = self.apply(x);
+
+                y * (1.0 - y)
+            }
+            Self::Tanh => 1.0 - x.tanh().powi(2),
+            Self::Identity => 1.0,
+            Self::Softsign => 1.0 / (1.0 + x.abs()).powi(2),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Activation::Relu, -2.0, 0.0)]
+    #[test_case(Activation::Relu, 0.5, 0.5)]
+    #[test_case(Activation::LeakyRelu, -2.0, -0.02)]
+    #[test_case(Activation::LeakyRelu, 0.5, 0.5)]
+    #[test_case(Activation::Sigmoid, 0.0, 0.5)]
+    #[test_case(Activation::Sigmoid, 2.0, 0.880797)]
+    #[test_case(Activation::Tanh, -1.0, -0.7615942)]
+    #[test_case(Activation::Tanh, 0.0, 0.0)]
+    #[test_case(Activation::Identity, -3.5, -3.5)]
+    #[test_case(Activation::Softsign, -3.0, -0.75)]
+    #[test_case(Activation::Softsign, 1.0, 0.5)]
+    fn apply(activation: Activation, x: f32, expected: f32) {
+        approx::assert_relative_eq!(activation.apply(x), expected, epsilon = 1e-6);
+    }
+
+    #[test_case(Activation::Relu, -2.0, 0.0)]
+    #[test_case(Activation::Relu, 0.5, 1.0)]
+    #[test_case(Activation::LeakyRelu, -2.0, 0.01)]
+    #[test_case(Activation::LeakyRelu, 0.5, 1.0)]
+    #[test_case(Activation::Sigmoid, 0.0, 0.25)]
+    #[test_case(Activation::Tanh, 0.0, 1.0)]
+    #[test_case(Activation::Tanh, -1.0, 0.41997434)]
+    #[test_case(Activation::Identity, -3.5, 1.0)]
+    #[test_case(Activation::Softsign, 1.0, 0.25)]
+    fn derivative(activation: Activation, x: f32, expected: f32) {
+        approx::assert_relative_eq!(activation.derivative(x), expected, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,595 @@
+use crate::*;
+
+/// Many networks of the same topology, stored in one contiguous buffer
+/// and evaluated together.
+///
+/// Each network's weights follow the layout of `Network::weights()` -
+/// that is: every layer is a row-major `outputs x (1 + inputs)` matrix
+/// where each row is a single neuron's `[bias, weights...]` (recurrent
+/// layers have more rows and columns, layer norm has a `[beta, gamma]`
+/// row per output and softmax and dropout have no weights at all - see
+/// `LayerKind`).
+///
+/// Propagating through a batch gives bit-identical results to calling
+/// `Network::propagate()` (or `Network::step()`) on each network
+/// separately, but doesn't allocate - all the intermediate values live
+/// in a `BatchBuffer`.
+#[derive(Clone, Debug)]
+pub struct NetworkBatch {
+    layers: Vec<BatchLayer>,
+    network_len: usize,
+    state_len: usize,
+    weights: Vec<f32>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct BatchLayer {
+    inputs: usize,
+    outputs: usize,
+    activation: Activation,
+    kind: LayerKind,
+}
+
+/// Scratch space for `NetworkBatch::propagate()`.
+#[derive(Clone, Debug)]
+pub struct BatchBuffer {
+    current: Vec<f32>,
+    next: Vec<f32>,
+    gates: Vec<f32>,
+    hidden: Vec<f32>,
+}
+
+/// Hidden state of all networks in a batch, carried from one
+/// `NetworkBatch::step()` to the next; see `NetworkState`.
+#[derive(Clone, Debug)]
+pub struct BatchState {
+    hidden: Vec<f32>,
+    state_len: usize,
+}
+
+impl NetworkBatch {
+    /// Packs given networks into a batch; all of them must share the
+    /// same topology.
+    pub fn from_networks<'a>(networks: impl IntoIterator<Item = &'a Network>) -> Self {
+        let mut networks = networks.into_iter();
+        let first = networks.next().expect("got no networks");
+        let topology = first.topology();
+
+        let mut weights: Vec<_> = first.weights().collect();
+
+        for network in networks {
+            assert_eq!(
+                network.topology(),
+                topology,
+                "got networks of different topologies"
+            );
+
+            weights.extend(network.weights());
+        }
+
+        Self::from_weights(&topology, weights)
+    }
+
+    /// Creates a batch out of weights of many networks, laid one after
+    /// another.
+    pub fn from_weights(layers: &[LayerTopology], weights: Vec<f32>) -> Self {
+        assert!(layers.len() > 1);
+
+        let layers: Vec<_> = layers
+            .windows(2)
+            .map(|layers| BatchLayer {
+                inputs: layers[0].neurons,
+                outputs: layers[1].neurons,
+                activation: layers[1].activation,
+                kind: layers[1].kind,
+            })
+            .collect();
+
+        let network_len = layers.iter().map(BatchLayer::weights_count).sum();
+        let state_len = layers.iter().map(BatchLayer::state_size).sum();
+
+        assert!(network_len > 0);
+        assert_eq!(weights.len() % network_len, 0, "got incomplete network");
+
+        Self {
+            layers,
+            network_len,
+            state_len,
+            weights,
+        }
+    }
+
+    /// Number of networks in this batch.
+    pub fn len(&self) -> usize {
+        self.weights.len() / self.network_len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Number of inputs each network expects.
+    pub fn input_size(&self) -> usize {
+        self.layers[0].inputs
+    }
+
+    /// Number of outputs each network returns.
+    pub fn output_size(&self) -> usize {
+        self.layers[self.layers.len() - 1].outputs
+    }
+
+    /// Returns weights of the `index`-th network, in the layout of
+    /// `Network::weights()`.
+    pub fn network_weights(&self, index: usize) -> &[f32] {
+        &self.weights[index * self.network_len..][..self.network_len]
+    }
+
+    /// Allocates a buffer large enough for this batch's widest layer.
+    pub fn buffer(&self) -> BatchBuffer {
+        let width = self
+            .layers
+            .iter()
+            .map(|layer| layer.inputs.max(layer.outputs))
+            .max()
+            .unwrap_or_default();
+
+        let gates = self
+            .layers
+            .iter()
+            .filter(|layer| layer.kind == LayerKind::Gru)
+            .map(|layer| 3 * layer.outputs)
+            .max()
+            .unwrap_or_default();
+
+        BatchBuffer {
+            current: vec![0.0; width],
+            next: vec![0.0; width],
+            gates: vec![0.0; gates],
+            hidden: vec![0.0; self.state_len],
+        }
+    }
+
+    /// Creates a fresh (zeroed) hidden state for `Self::step()`.
+    pub fn state(&self) -> BatchState {
+        BatchState {
+            hidden: vec![0.0; self.len() * self.state_len],
+            state_len: self.state_len,
+        }
+    }
+
+    /// Propagates `inputs` through all networks at once.
+    ///
+    /// `inputs` holds `self.len()` rows of `self.input_size()` values
+    /// (the n-th row is fed into the n-th network) and `outputs`
+    /// receives `self.len()` rows of `self.output_size()` values.
+    ///
+    /// Just like `Network::propagate()`, recurrent layers start from a
+    /// zeroed hidden state.
+    pub fn propagate(&self, inputs: &[f32], outputs: &mut [f32], buffer: &mut BatchBuffer) {
+        self.propagate_with(inputs, outputs, buffer, None);
+    }
+
+    /// Like `Self::propagate()`, but carries hidden state of recurrent
+    /// layers over from the previous step.
+    pub fn step(
+        &self,
+        inputs: &[f32],
+        outputs: &mut [f32],
+        buffer: &mut BatchBuffer,
+        state: &mut BatchState,
+    ) {
+        assert_eq!(
+            state.hidden.len(),
+            self.len() * self.state_len,
+            "got state of a different batch"
+        );
+
+        self.propagate_with(inputs, outputs, buffer, Some(&mut state.hidden));
+    }
+
+    fn propagate_with(
+        &self,
+        inputs: &[f32],
+        outputs: &mut [f32],
+        buffer: &mut BatchBuffer,
+        mut state: Option<&mut [f32]>,
+    ) {
+        let input_size = self.input_size();
+        let output_size = self.output_size();
+
+        assert_eq!(inputs.len(), self.len() * input_size);
+        assert_eq!(outputs.len(), self.len() * output_size);
+
+        let BatchBuffer {
+            current,
+            next,
+            gates,
+            hidden: zeroed_hidden,
+        } = buffer;
+
+        for (index, ((network, inputs), outputs)) in self
+            .weights
+            .chunks_exact(self.network_len)
+            .zip(inputs.chunks_exact(input_size))
+            .zip(outputs.chunks_exact_mut(output_size))
+            .enumerate()
+        {
+            let (mut current, mut next) = (&mut *current, &mut *next);
+            let mut weights = network;
+
+            let mut hidden = match &mut state {
+                Some(state) => &mut state[index * self.state_len..][..self.state_len],
+                None => {
+                    zeroed_hidden.fill(0.0);
+                    &mut zeroed_hidden[..]
+                }
+            };
+
+            current[..input_size].copy_from_slice(inputs);
+
+            for layer in &self.layers {
+                let (layer_weights, remaining_weights) = weights.split_at(layer.weights_count());
+
+                let (layer_hidden, remaining_hidden) =
+                    std::mem::take(&mut hidden).split_at_mut(layer.state_size());
+
+                layer.propagate(
+                    layer_weights,
+                    &current[..layer.inputs],
+                    &mut next[..layer.outputs],
+                    layer_hidden,
+                    gates,
+                );
+
+                weights = remaining_weights;
+                hidden = remaining_hidden;
+                std::mem::swap(&mut current, &mut next);
+            }
+
+            outputs.copy_from_slice(&current[..output_size]);
+        }
+    }
+}
+
+impl BatchLayer {
+    fn weights_count(&self) -> usize {
+        self.kind
+            .weights_count(self.inputs, self.outputs)
+            .expect("layer comes from an existing network, so its size must fit")
+    }
+
+    fn state_size(&self) -> usize {
+        self.kind.state_size(self.outputs)
+    }
+
+    /// Mirrors `Layer::step()` operation-by-operation, so that the
+    /// results stay bit-identical.
+    fn propagate(
+        &self,
+        weights: &[f32],
+        inputs: &[f32],
+        outputs: &mut [f32],
+        hidden: &mut [f32],
+        gates: &mut [f32],
+    ) {
+        let mut neurons = weights.chunks_exact(1 + self.inputs + hidden.len());
+
+        match self.kind {
+            LayerKind::Dense | LayerKind::Rnn => {
+                for (neuron, output) in neurons.zip(outputs.iter_mut()) {
+                    *output = self.activation.apply(Self::sum(neuron, inputs, hidden));
+                }
+
+                hidden.copy_from_slice(&outputs[..hidden.len()]);
+            }
+
+            LayerKind::Gru => {
+                let (gates, reset_hidden) =
+                    gates[..3 * self.outputs].split_at_mut(2 * self.outputs);
+
+                // (`gates` goes first, so that `zip()` doesn't consume
+                // the first candidate neuron when it runs out of gates)
+                for (gate, neuron) in gates.iter_mut().zip(neurons.by_ref()) {
+                    *gate = Activation::Sigmoid.apply(Self::sum(neuron, inputs, hidden));
+                }
+
+                let (update, reset) = gates.split_at(self.outputs);
+
+                for ((reset_hidden, hidden), reset) in
+                    reset_hidden.iter_mut().zip(&*hidden).zip(reset)
+                {
+                    *reset_hidden = hidden * reset;
+                }
+
+                for (((neuron, output), hidden), update) in neurons
+                    .zip(outputs.iter_mut())
+                    .zip(hidden.iter_mut())
+                    .zip(update)
+                {
+                    let candidate = self
+                        .activation
+                        .apply(Self::sum(neuron, inputs, reset_hidden));
+
+                    *hidden = (1.0 - update) * candidate + update * *hidden;
+                    *output = *hidden;
+                }
+            }
+
+            LayerKind::Softmax => Layer::softmax(inputs, outputs),
+
+            LayerKind::LayerNorm => {
+                let (mean, inv_std) = Layer::normalization(inputs);
+
+                for ((parameters, &input), output) in
+                    weights.chunks_exact(2).zip(inputs).zip(outputs.iter_mut())
+                {
+                    *output = self.activation.apply(Layer::normalize(
+                        parameters[0],
+                        parameters[1],
+                        input,
+                        mean,
+                        inv_std,
+                    ));
+                }
+            }
+
+            LayerKind::Dropout { .. } => outputs.copy_from_slice(inputs),
+        }
+    }
+
+    /// Mirrors `Neuron::sum()` for a neuron that sees `inputs` followed
+    /// by `hidden`.
+    fn sum(neuron: &[f32], inputs: &[f32], hidden: &[f32]) -> f32 {
+        let (input_weights, hidden_weights) = neuron[1..].split_at(inputs.len());
+
+        let sum = inputs
+            .iter()
+            .zip(input_weights)
+            .map(|(input, weight)| input * weight)
+            .sum::<f32>();
+
+        // (continuing the same fold keeps the order of additions intact)
+        let sum = hidden
+            .iter()
+            .zip(hidden_weights)
+            .fold(sum, |sum, (hidden, weight)| sum + hidden * weight);
+
+        neuron[0] + sum
+    }
+}
+
+impl BatchState {
+    /// Forgets everything the `index`-th network remembered so far.
+    pub fn reset(&mut self, index: usize) {
+        self.hidden[index * self.state_len..][..self.state_len].fill(0.0);
+    }
+
+    /// Forgets everything all networks remembered so far.
+    pub fn reset_all(&mut self) {
+        self.hidden.fill(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn topology() -> [LayerTopology; 4] {
+        [
+            LayerTopology {
+                neurons: 5,
+                activation: Activation::Relu,
+                kind: LayerKind::Dense,
+            },
+            LayerTopology {
+                neurons: 10,
+                activation: Activation::LeakyRelu,
+                kind: LayerKind::Dense,
+            },
+            LayerTopology {
+                neurons: 3,
+                activation: Activation::Sigmoid,
+                kind: LayerKind::Dense,
+            },
+            LayerTopology {
+                neurons: 2,
+                activation: Activation::Tanh,
+                kind: LayerKind::Dense,
+            },
+        ]
+    }
+
+    mod from_networks {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let networks: Vec<_> = (0..3)
+                .map(|_| Network::random(&mut rng, &topology()))
+                .collect();
+
+            let batch = NetworkBatch::from_networks(&networks);
+
+            assert_eq!(batch.len(), 3);
+            assert_eq!(batch.input_size(), 5);
+            assert_eq!(batch.output_size(), 2);
+
+            for (index, network) in networks.iter().enumerate() {
+                let expected: Vec<_> = network.weights().collect();
+
+                assert_eq!(batch.network_weights(index), expected.as_slice());
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "got networks of different topologies")]
+        fn rejects_different_topologies() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut other_topology = topology();
+            other_topology[2].activation = Activation::Relu;
+
+            NetworkBatch::from_networks(&[
+                Network::random(&mut rng, &topology()),
+                Network::random(&mut rng, &other_topology),
+            ]);
+        }
+    }
+
+    mod propagate {
+        use super::*;
+        use rand::Rng;
+
+        #[test]
+        fn is_bit_identical_to_network_propagate() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let networks: Vec<_> = (0..50)
+                .map(|_| Network::random(&mut rng, &topology()))
+                .collect();
+
+            let inputs: Vec<f32> = (0..50 * 5).map(|_| rng.gen_range(-2.0..=2.0)).collect();
+
+            let batch = NetworkBatch::from_networks(&networks);
+            let mut buffer = batch.buffer();
+            let mut actual = vec![0.0; 50 * 2];
+
+            batch.propagate(&inputs, &mut actual, &mut buffer);
+
+            let expected: Vec<_> = networks
+                .iter()
+                .zip(inputs.chunks(5))
+                .flat_map(|(network, inputs)| network.propagate(inputs.to_vec()))
+                .collect();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn supports_all_feed_forward_kinds() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut topology = topology().to_vec();
+
+            topology.insert(
+                2,
+                LayerTopology {
+                    neurons: 10,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::LayerNorm,
+                },
+            );
+
+            topology.insert(
+                3,
+                LayerTopology {
+                    neurons: 10,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dropout { rate: 0.5 },
+                },
+            );
+
+            topology.push(LayerTopology {
+                neurons: 2,
+                activation: Activation::Relu,
+                kind: LayerKind::Softmax,
+            });
+
+            let networks: Vec<_> = (0..10)
+                .map(|_| Network::random(&mut rng, &topology))
+                .collect();
+
+            let inputs: Vec<f32> = (0..10 * 5).map(|_| rng.gen_range(-2.0..=2.0)).collect();
+
+            let batch = NetworkBatch::from_networks(&networks);
+            let mut buffer = batch.buffer();
+            let mut actual = vec![0.0; 10 * 2];
+
+            batch.propagate(&inputs, &mut actual, &mut buffer);
+
+            let expected: Vec<_> = networks
+                .iter()
+                .zip(inputs.chunks(5))
+                .flat_map(|(network, inputs)| network.propagate(inputs.to_vec()))
+                .collect();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod step {
+        use super::*;
+        use rand::Rng;
+
+        fn recurrent_topology() -> [LayerTopology; 4] {
+            let mut topology = topology();
+            topology[1].kind = LayerKind::Gru;
+            topology[2].kind = LayerKind::Rnn;
+            topology
+        }
+
+        #[test]
+        fn is_bit_identical_to_network_step() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let networks: Vec<_> = (0..20)
+                .map(|_| Network::random(&mut rng, &recurrent_topology()))
+                .collect();
+
+            let batch = NetworkBatch::from_networks(&networks);
+            let mut buffer = batch.buffer();
+            let mut state = batch.state();
+            let mut network_states: Vec<_> = networks.iter().map(Network::state).collect();
+
+            for _ in 0..5 {
+                let inputs: Vec<f32> = (0..20 * 5).map(|_| rng.gen_range(-2.0..=2.0)).collect();
+                let mut actual = vec![0.0; 20 * 2];
+
+                batch.step(&inputs, &mut actual, &mut buffer, &mut state);
+
+                let expected: Vec<_> = networks
+                    .iter()
+                    .zip(&mut network_states)
+                    .zip(inputs.chunks(5))
+                    .flat_map(|((network, state), inputs)| network.step(inputs.to_vec(), state))
+                    .collect();
+
+                assert_eq!(actual, expected);
+            }
+        }
+
+        #[test]
+        fn propagate_starts_from_zeroed_state() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let networks: Vec<_> = (0..3)
+                .map(|_| Network::random(&mut rng, &recurrent_topology()))
+                .collect();
+
+            let batch = NetworkBatch::from_networks(&networks);
+            let mut buffer = batch.buffer();
+            let mut state = batch.state();
+            let inputs = vec![0.5; 3 * 5];
+
+            let mut first = vec![0.0; 3 * 2];
+            let mut second = vec![0.0; 3 * 2];
+            let mut stateless = vec![0.0; 3 * 2];
+
+            batch.step(&inputs, &mut first, &mut buffer, &mut state);
+            batch.step(&inputs, &mut second, &mut buffer, &mut state);
+            batch.propagate(&inputs, &mut stateless, &mut buffer);
+
+            assert_ne!(first, second);
+            assert_eq!(first, stateless);
+
+            // Resetting a single network makes only that one forget
+            state.reset(1);
+            batch.step(&inputs, &mut second, &mut buffer, &mut state);
+
+            assert_ne!(first[0..2], second[0..2]);
+            assert_eq!(first[2..4], second[2..4]);
+            assert_ne!(first[4..6], second[4..6]);
+        }
+    }
+}
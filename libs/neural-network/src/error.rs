@@ -0,0 +1,115 @@
+use crate::LayerKind;
+use thiserror::Error as ThisError;
+
+/// Describes why a network (or one of its parts) couldn't be built or
+/// propagated.
+///
+/// Layers are counted the same way as in `&[LayerTopology]` - that is:
+/// layer 0 is the input layer, layer 1 is the first one with neurons.
+#[derive(ThisError, Clone, Debug, PartialEq, Eq)]
+pub enum NetworkError {
+    #[error("network needs at least two layers, got {0}")]
+    NotEnoughLayers(usize),
+
+    #[error("layer {layer} has no neurons")]
+    EmptyLayer { layer: usize },
+
+    #[error("neuron {neuron} in layer {layer} has no weights")]
+    EmptyNeuron { layer: usize, neuron: usize },
+
+    #[error("neuron {neuron} in layer {layer} has {actual} weights, expected {expected}")]
+    WeightsMismatch {
+        layer: usize,
+        neuron: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("not enough weights for neuron {neuron} in layer {layer}")]
+    NotEnoughWeights { layer: usize, neuron: usize },
+
+    #[error("got too many weights: expected {expected}")]
+    TooManyWeights { expected: usize },
+
+    #[error("layer {layer} expects {expected} inputs, got {actual}")]
+    InputsMismatch {
+        layer: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error(
+        "layer {layer} must have as many neurons as the previous one ({expected}), got {actual}"
+    )]
+    SizeMismatch {
+        layer: usize,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("layer {layer} is {kind:?}, which has no neurons")]
+    UnexpectedNeurons { layer: usize, kind: LayerKind },
+
+    #[error("layer {layer} is {kind:?}, so it needs a multiple of {gates} neurons, got {actual}")]
+    IncompleteGates {
+        layer: usize,
+        kind: LayerKind,
+        gates: usize,
+        actual: usize,
+    },
+
+    #[error(
+        "layer {layer} is {kind:?}, so its neurons need more than {state_size} weights (inputs followed by hidden state), got {actual}"
+    )]
+    MissingInputWeights {
+        layer: usize,
+        kind: LayerKind,
+        state_size: usize,
+        actual: usize,
+    },
+
+    #[error("layer {layer} has too many weights to fit in memory")]
+    LayerTooLarge { layer: usize },
+
+    #[error("layer {layer} is {kind:?}, but dropout rate must lie within [0, 1)")]
+    InvalidDropoutRate { layer: usize, kind: LayerKind },
+
+    #[error("layer {layer} is {kind:?}, which can't be quantized")]
+    UnsupportedLayer { layer: usize, kind: LayerKind },
+}
+
+impl NetworkError {
+    /// Points this error at given layer; used when errors bubble up
+    /// from `Layer` (which doesn't know its own index) to `Network`.
+    pub(crate) fn in_layer(mut self, index: usize) -> Self {
+        match &mut self {
+            Self::EmptyLayer { layer }
+            | Self::EmptyNeuron { layer, .. }
+            | Self::WeightsMismatch { layer, .. }
+            | Self::NotEnoughWeights { layer, .. }
+            | Self::InputsMismatch { layer, .. }
+            | Self::SizeMismatch { layer, .. }
+            | Self::UnexpectedNeurons { layer, .. }
+            | Self::IncompleteGates { layer, .. }
+            | Self::MissingInputWeights { layer, .. }
+            | Self::LayerTooLarge { layer }
+            | Self::InvalidDropoutRate { layer, .. }
+            | Self::UnsupportedLayer { layer, .. } => *layer = index,
+            Self::NotEnoughLayers(_) | Self::TooManyWeights { .. } => {}
+        }
+
+        self
+    }
+
+    /// Ditto, but for errors bubbling up from `Neuron` to `Layer`.
+    pub(crate) fn in_neuron(mut self, index: usize) -> Self {
+        match &mut self {
+            Self::EmptyNeuron { neuron, .. }
+            | Self::WeightsMismatch { neuron, .. }
+            | Self::NotEnoughWeights { neuron, .. } => *neuron = index,
+            _ => {}
+        }
+
+        self
+    }
+}
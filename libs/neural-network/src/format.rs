@@ -0,0 +1,620 @@
+//! On-disk representation of a `Network`.
+//!
+//! Both formats are self-describing - they contain the topology (with
+//! activations) and the weights, so a network can be restored without
+//! having to know its shape up front:
+//!
+//! - JSON, for humans and for the web:
+//!
+//!   `{ "version": 2, "topology": [{ "neurons": 9, "activation": "relu", "kind": "dense" }, ...], "weights": [...] }`
+//!
+//! - binary, for compactness (all numbers are little-endian):
+//!
+//!   `b"KRNN"`, version (u32), layers (u32), then per layer: neurons
+//!   (u32), activation (u8) and kind (u8, followed by rate (f32) for
+//!   dropout layers), then weights (u32) followed by that many f32s.
+//!
+//! Version 1 didn't know about layer kinds (every layer was dense) -
+//! such files can still be read.
+
+use crate::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error as ThisError;
+
+/// Current version of both formats; bumped whenever their layout
+/// changes.
+pub const FORMAT_VERSION: u32 = 2;
+
+const MAGIC: &[u8; 4] = b"KRNN";
+
+#[derive(ThisError, Debug)]
+pub enum FormatError {
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("not a network file (bad magic bytes)")]
+    BadMagic,
+
+    #[error("unsupported format version {0} (expected at most {FORMAT_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("file ended unexpectedly")]
+    UnexpectedEof,
+
+    #[error("file contains trailing bytes")]
+    TrailingBytes,
+
+    #[error("unknown activation code {0}")]
+    UnknownActivation(u8),
+
+    #[error("unknown layer kind code {0}")]
+    UnknownLayerKind(u8),
+
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+}
+
+#[derive(Serialize, Deserialize)]
+struct NetworkFile {
+    version: u32,
+    topology: Vec<LayerTopology>,
+    weights: Vec<f32>,
+}
+
+impl Network {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.to_file()).expect("network is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, FormatError> {
+        Self::from_file(serde_json::from_str(json)?)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let file = self.to_file();
+        let mut bytes = Vec::new();
+
+        bytes.extend(MAGIC);
+        bytes.extend(file.version.to_le_bytes());
+        bytes.extend((file.topology.len() as u32).to_le_bytes());
+
+        for layer in &file.topology {
+            bytes.extend((layer.neurons as u32).to_le_bytes());
+            bytes.push(layer.activation.code());
+            bytes.push(layer.kind.code());
+
+            if let LayerKind::Dropout { rate } = layer.kind {
+                bytes.extend(rate.to_le_bytes());
+            }
+        }
+
+        bytes.extend((file.weights.len() as u32).to_le_bytes());
+
+        for weight in file.weights {
+            bytes.extend(weight.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormatError> {
+        let mut reader = Reader { bytes };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(FormatError::BadMagic);
+        }
+
+        let version = reader.u32()?;
+
+        // Check the version before reading anything else, since the
+        // rest of the layout might differ between versions
+        if !is_supported(version) {
+            return Err(FormatError::UnsupportedVersion(version));
+        }
+
+        let topology = (0..reader.u32()?)
+            .map(|_| {
+                let neurons = reader.u32()? as usize;
+                let activation = reader.u8()?;
+                let activation = Activation::from_code(activation)
+                    .ok_or(FormatError::UnknownActivation(activation))?;
+
+                let mut kind = if version >= 2 {
+                    let kind = reader.u8()?;
+                    LayerKind::from_code(kind).ok_or(FormatError::UnknownLayerKind(kind))?
+                } else {
+                    LayerKind::Dense
+                };
+
+                if let LayerKind::Dropout { rate } = &mut kind {
+                    *rate = reader.f32()?;
+                }
+
+                Ok(LayerTopology {
+                    neurons,
+                    activation,
+                    kind,
+                })
+            })
+            .collect::<Result<_, FormatError>>()?;
+
+        let weights = (0..reader.u32()?)
+            .map(|_| reader.f32())
+            .collect::<Result<_, _>>()?;
+
+        if !reader.bytes.is_empty() {
+            return Err(FormatError::TrailingBytes);
+        }
+
+        Self::from_file(NetworkFile {
+            version,
+            topology,
+            weights,
+        })
+    }
+
+    fn to_file(&self) -> NetworkFile {
+        NetworkFile {
+            version: FORMAT_VERSION,
+            topology: self.topology(),
+            weights: self.weights().collect(),
+        }
+    }
+
+    fn from_file(file: NetworkFile) -> Result<Self, FormatError> {
+        if !is_supported(file.version) {
+            return Err(FormatError::UnsupportedVersion(file.version));
+        }
+
+        Ok(Self::try_from_weights(&file.topology, file.weights)?)
+    }
+}
+
+impl Activation {
+    fn code(self) -> u8 {
+        match self {
+            Self::Relu => 0,
+            Self::LeakyRelu => 1,
+            Self::Sigmoid => 2,
+            Self::Tanh => 3,
+            Self::Identity => 4,
+            Self::Softsign => 5,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Self::Relu,
+            1 => Self::LeakyRelu,
+            2 => Self::Sigmoid,
+            3 => Self::Tanh,
+            4 => Self::Identity,
+            5 => Self::Softsign,
+            _ => return None,
+        })
+    }
+}
+
+impl LayerKind {
+    fn code(self) -> u8 {
+        match self {
+            Self::Dense => 0,
+            Self::Rnn => 1,
+            Self::Gru => 2,
+            Self::Softmax => 3,
+            Self::LayerNorm => 4,
+            Self::Dropout { .. } => 5,
+        }
+    }
+
+    fn from_code(code: u8) -> Option<Self> {
+        Some(match code {
+            0 => Self::Dense,
+            1 => Self::Rnn,
+            2 => Self::Gru,
+            3 => Self::Softmax,
+            4 => Self::LayerNorm,
+
+            // (the rate is stored separately)
+            5 => Self::Dropout { rate: 0.0 },
+
+            _ => return None,
+        })
+    }
+}
+
+fn is_supported(version: u32) -> bool {
+    (1..=FORMAT_VERSION).contains(&version)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], FormatError> {
+        if self.bytes.len() < len {
+            return Err(FormatError::UnexpectedEof);
+        }
+
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, FormatError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, FormatError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, FormatError> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn network() -> Network {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Network::random(
+            &mut rng,
+            &[
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::LeakyRelu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 1,
+                    activation: Activation::Softsign,
+                    kind: LayerKind::Dense,
+                },
+            ],
+        )
+    }
+
+    fn recurrent_network() -> Network {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Network::random(
+            &mut rng,
+            &[
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Gru,
+                },
+                LayerTopology {
+                    neurons: 1,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Rnn,
+                },
+            ],
+        )
+    }
+
+    fn mixed_network() -> Network {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Network::random(
+            &mut rng,
+            &[
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::LayerNorm,
+                },
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dropout { rate: 0.25 },
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Identity,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Softmax,
+                },
+            ],
+        )
+    }
+
+    fn assert_same(actual: &Network, expected: &Network) {
+        assert_eq!(actual.topology(), expected.topology());
+
+        let actual: Vec<_> = actual.weights().collect();
+        let expected: Vec<_> = expected.weights().collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    mod json {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let network = network();
+            let actual = Network::from_json(&network.to_json()).unwrap();
+
+            assert_same(&actual, &network);
+        }
+
+        #[test]
+        fn contains_version_and_topology() {
+            let json = network().to_json();
+
+            assert!(json.starts_with(
+                r#"{"version":2,"topology":[{"neurons":3,"activation":"relu","kind":"dense"}"#
+            ));
+            assert!(json.contains(r#"{"neurons":1,"activation":"softsign","kind":"dense"}"#));
+        }
+
+        #[test]
+        fn rejects_unsupported_version() {
+            let json = network()
+                .to_json()
+                .replace(r#""version":2"#, r#""version":3"#);
+
+            assert!(matches!(
+                Network::from_json(&json),
+                Err(FormatError::UnsupportedVersion(3))
+            ));
+        }
+
+        #[test]
+        fn reads_version_1() {
+            let json = r#"{"version":1,"topology":[{"neurons":1,"activation":"relu"},{"neurons":1,"activation":"tanh"}],"weights":[0.5,1.0]}"#;
+            let network = Network::from_json(json).unwrap();
+
+            assert_eq!(network.topology()[1].kind, LayerKind::Dense);
+            assert_eq!(network.propagate(vec![1.0]), vec![1.5f32.tanh()]);
+        }
+
+        #[test]
+        fn round_trip_recurrent() {
+            let network = recurrent_network();
+            let actual = Network::from_json(&network.to_json()).unwrap();
+
+            assert_same(&actual, &network);
+        }
+
+        #[test]
+        fn round_trip_mixed() {
+            let network = mixed_network();
+            let json = network.to_json();
+            let actual = Network::from_json(&json).unwrap();
+
+            assert!(json.contains(r#""kind":{"dropout":{"rate":0.25}}"#));
+            assert_same(&actual, &network);
+        }
+
+        #[test]
+        fn rejects_too_many_weights() {
+            let json = network()
+                .to_json()
+                .replace(r#""weights":["#, r#""weights":[0.5,"#);
+
+            assert!(matches!(
+                Network::from_json(&json),
+                Err(FormatError::Network(NetworkError::TooManyWeights {
+                    expected: 11
+                }))
+            ));
+        }
+
+        #[test]
+        fn rejects_empty_layer() {
+            let json = r#"{"version":1,"topology":[{"neurons":1,"activation":"relu"},{"neurons":0,"activation":"relu"}],"weights":[]}"#;
+
+            assert!(matches!(
+                Network::from_json(json),
+                Err(FormatError::Network(NetworkError::EmptyLayer { layer: 1 }))
+            ));
+        }
+
+        #[test]
+        fn rejects_oversized_topology() {
+            let json = format!(
+                r#"{{"version":2,"topology":[{{"neurons":{0},"activation":"relu"}},{{"neurons":{0},"activation":"relu"}}],"weights":[]}}"#,
+                usize::MAX / 2
+            );
+
+            assert!(matches!(
+                Network::from_json(&json),
+                Err(FormatError::Network(NetworkError::LayerTooLarge {
+                    layer: 1
+                }))
+            ));
+        }
+
+        #[test]
+        fn rejects_garbage() {
+            assert!(matches!(
+                Network::from_json("nope"),
+                Err(FormatError::Json(_))
+            ));
+        }
+    }
+
+    mod bytes {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let network = network();
+            let actual = Network::from_bytes(&network.to_bytes()).unwrap();
+
+            assert_same(&actual, &network);
+        }
+
+        #[test]
+        fn layout() {
+            let bytes = network().to_bytes();
+
+            assert_eq!(&bytes[..4], b"KRNN");
+            assert_eq!(&bytes[4..8], &2u32.to_le_bytes());
+            assert_eq!(&bytes[8..12], &3u32.to_le_bytes());
+
+            // magic + version + 3 layers + weight count + 11 weights
+            assert_eq!(bytes.len(), 4 + 4 + 4 + 3 * 6 + 4 + 11 * 4);
+        }
+
+        #[test]
+        fn reads_version_1() {
+            let mut bytes = b"KRNN".to_vec();
+
+            for value in [1u32, 2, 1] {
+                bytes.extend(value.to_le_bytes());
+            }
+
+            bytes.push(0);
+            bytes.extend(1u32.to_le_bytes());
+            bytes.push(3);
+            bytes.extend(2u32.to_le_bytes());
+            bytes.extend(0.5f32.to_le_bytes());
+            bytes.extend(1.0f32.to_le_bytes());
+
+            let network = Network::from_bytes(&bytes).unwrap();
+
+            assert_eq!(network.topology()[1].kind, LayerKind::Dense);
+            assert_eq!(network.propagate(vec![1.0]), vec![1.5f32.tanh()]);
+        }
+
+        #[test]
+        fn round_trip_recurrent() {
+            let network = recurrent_network();
+            let actual = Network::from_bytes(&network.to_bytes()).unwrap();
+
+            assert_same(&actual, &network);
+        }
+
+        #[test]
+        fn round_trip_mixed() {
+            let network = mixed_network();
+            let actual = Network::from_bytes(&network.to_bytes()).unwrap();
+
+            assert_same(&actual, &network);
+        }
+
+        #[test]
+        fn rejects_unknown_layer_kind() {
+            let mut bytes = network().to_bytes();
+
+            // Kind of the first layer
+            bytes[17] = 42;
+
+            assert!(matches!(
+                Network::from_bytes(&bytes),
+                Err(FormatError::UnknownLayerKind(42))
+            ));
+        }
+
+        #[test]
+        fn rejects_bad_magic() {
+            let mut bytes = network().to_bytes();
+            bytes[0] = b'X';
+
+            assert!(matches!(
+                Network::from_bytes(&bytes),
+                Err(FormatError::BadMagic)
+            ));
+        }
+
+        #[test]
+        fn rejects_unsupported_version() {
+            let mut bytes = network().to_bytes();
+            bytes[4] = 7;
+
+            assert!(matches!(
+                Network::from_bytes(&bytes),
+                Err(FormatError::UnsupportedVersion(7))
+            ));
+        }
+
+        #[test]
+        fn rejects_truncated_file() {
+            let bytes = network().to_bytes();
+
+            assert!(matches!(
+                Network::from_bytes(&bytes[..bytes.len() - 1]),
+                Err(FormatError::UnexpectedEof)
+            ));
+        }
+
+        #[test]
+        fn rejects_trailing_bytes() {
+            let mut bytes = network().to_bytes();
+            bytes.push(0);
+
+            assert!(matches!(
+                Network::from_bytes(&bytes),
+                Err(FormatError::TrailingBytes)
+            ));
+        }
+
+        #[test]
+        fn rejects_oversized_topology() {
+            let mut bytes = b"KRNN".to_vec();
+
+            bytes.extend(2u32.to_le_bytes());
+            bytes.extend(2u32.to_le_bytes());
+
+            // Two layers of `u32::MAX` neurons, the second one being GRU
+            for kind in [0, 2] {
+                bytes.extend(u32::MAX.to_le_bytes());
+                bytes.push(0);
+                bytes.push(kind);
+            }
+
+            bytes.extend(0u32.to_le_bytes());
+
+            assert!(matches!(
+                Network::from_bytes(&bytes),
+                Err(FormatError::Network(NetworkError::LayerTooLarge {
+                    layer: 1
+                }))
+            ));
+        }
+
+        #[test]
+        fn rejects_unknown_activation() {
+            let mut bytes = network().to_bytes();
+
+            // Activation of the first layer
+            bytes[16] = 42;
+
+            assert!(matches!(
+                Network::from_bytes(&bytes),
+                Err(FormatError::UnknownActivation(42))
+            ));
+        }
+    }
+}
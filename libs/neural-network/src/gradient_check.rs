@@ -0,0 +1,274 @@
+use crate::*;
+
+/// Step used by `Network::check_gradients()` to nudge each weight.
+///
+/// Central differences are accurate up to `O(step^2)`, but - since we
+/// work on `f32` - too small a step drowns the difference in rounding
+/// errors.
+const STEP: f32 = 1e-2;
+
+/// Gradients smaller than this are compared absolutely rather than
+/// relatively - otherwise e.g. `1e-9` vs `2e-9` would count as a 50%
+/// error.
+const MIN_SCALE: f32 = 1e-3;
+
+/// Result of `Network::check_gradients()`.
+#[derive(Clone, Debug)]
+pub struct GradientCheck {
+    /// Gradients computed by backpropagation (`Network::gradients()`).
+    pub analytic: Vec<f32>,
+
+    /// Gradients estimated with central finite differences.
+    pub numerical: Vec<f32>,
+
+    /// Relative error between both gradients, for each weight.
+    pub errors: Vec<f32>,
+}
+
+impl GradientCheck {
+    /// Index of the weight with the largest error (following the
+    /// layout of `Network::weights()`), together with that error.
+    ///
+    /// Returns `None` for networks without any weights.
+    pub fn worst(&self) -> Option<(usize, f32)> {
+        self.errors
+            .iter()
+            .copied()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    /// The largest relative error; zero for networks without any
+    /// weights.
+    pub fn max_relative_error(&self) -> f32 {
+        self.worst().map_or(0.0, |(_, error)| error)
+    }
+}
+
+impl Network {
+    /// Compares gradients computed by `Self::gradients()` with ones
+    /// estimated numerically, by nudging each weight up and down and
+    /// seeing how the loss changes.
+    ///
+    /// This is slow (the network gets propagated twice per weight), so
+    /// it's meant for tests - see `assert_gradients!`.
+    pub fn check_gradients(&self, inputs: &[f32], targets: &[f32], loss: Loss) -> GradientCheck {
+        let topology = self.topology();
+        let weights: Vec<_> = self.weights().collect();
+        let (_, analytic) = self.gradients(inputs, targets, loss);
+
+        let loss_at = |weights: &[f32]| {
+            let network = Self::from_weights(&topology, weights.iter().copied());
+            loss.compute(&network.propagate(inputs.to_vec()), targets)
+        };
+
+        let numerical: Vec<_> = (0..weights.len())
+            .map(|index| {
+                let mut nudged = weights.clone();
+
+                nudged[index] = weights[index] + STEP;
+                let plus = loss_at(&nudged);
+
+                nudged[index] = weights[index] - STEP;
+                let minus = loss_at(&nudged);
+
+                (plus - minus) / (2.0 * STEP)
+            })
+            .collect();
+
+        let errors = analytic
+            .iter()
+            .zip(&numerical)
+            .map(|(analytic, numerical)| {
+                let scale = analytic.abs().max(numerical.abs()).max(MIN_SCALE);
+                (analytic - numerical).abs() / scale
+            })
+            .collect();
+
+        GradientCheck {
+            analytic,
+            numerical,
+            errors,
+        }
+    }
+}
+
+/// Asserts that backpropagation through given network agrees with
+/// finite differences (see `Network::check_gradients()`):
+///
+/// ```
+/// # use lib_neural_network::*;
+/// # use rand::SeedableRng;
+/// # let mut rng = rand_chacha::ChaCha8Rng::from_seed(Default::default());
+/// let network = Network::random(
+///     &mut rng,
+///     &[
+///         LayerTopology { neurons: 2, activation: Activation::Relu, kind: LayerKind::Dense },
+///         LayerTopology { neurons: 1, activation: Activation::Tanh, kind: LayerKind::Dense },
+///     ],
+/// );
+///
+/// assert_gradients!(network, [0.5, -0.5], [1.0], Loss::MeanSquaredError);
+/// assert_gradients!(network, [0.5, -0.5], [1.0], Loss::MeanSquaredError, max_relative = 0.05);
+/// ```
+///
+/// The default tolerance is `0.01`.
+#[macro_export]
+macro_rules! assert_gradients {
+    ($network:expr, $inputs:expr, $targets:expr, $loss:expr $(,)?) => {
+        $crate::assert_gradients!($network, $inputs, $targets, $loss, max_relative = 0.01)
+    };
+
+    ($network:expr, $inputs:expr, $targets:expr, $loss:expr, max_relative = $max_relative:expr $(,)?) => {{
+        let check = $network.check_gradients(&$inputs, &$targets, $loss);
+
+        if let Some((index, error)) = check.worst() {
+            if error > $max_relative {
+                panic!(
+                    "gradient check failed for weight {}: analytic = {}, numerical = {} (relative error = {}, expected at most {})",
+                    index, check.analytic[index], check.numerical[index], error, $max_relative,
+                );
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn network(topology: &[LayerTopology]) -> Network {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        Network::random(&mut rng, topology)
+    }
+
+    mod check_gradients {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let network = network(&[
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::Sigmoid,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Softsign,
+                    kind: LayerKind::Dense,
+                },
+            ]);
+
+            let check =
+                network.check_gradients(&[0.1, 0.5, -0.3], &[0.5, -0.5], Loss::MeanSquaredError);
+
+            assert_eq!(check.analytic.len(), 4 * 4 + 2 * 5);
+            assert_eq!(check.numerical.len(), check.analytic.len());
+            assert!(check.max_relative_error() < 0.01);
+
+            approx::assert_abs_diff_eq!(
+                check.analytic.as_slice(),
+                check.numerical.as_slice(),
+                epsilon = 1e-3
+            );
+        }
+
+        #[test]
+        fn reports_the_worst_weight() {
+            let check = GradientCheck {
+                analytic: vec![1.0, 2.0, 0.0],
+                numerical: vec![1.0, 1.0, 0.0001],
+                errors: vec![0.0, 0.5, 0.1],
+            };
+
+            assert_eq!(check.worst(), Some((1, 0.5)));
+            assert_eq!(check.max_relative_error(), 0.5);
+        }
+    }
+
+    mod assert_gradients {
+        use super::*;
+
+        #[test]
+        fn passes_for_all_feed_forward_kinds() {
+            let network = network(&[
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::LeakyRelu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::LayerNorm,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Identity,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Softmax,
+                },
+            ]);
+
+            assert_gradients!(
+                network,
+                [0.3, -0.2, 0.8],
+                [0.9, 0.1],
+                Loss::MeanSquaredError
+            );
+            assert_gradients!(network, [0.3, -0.2, 0.8], [1.0, 0.0], Loss::CrossEntropy);
+        }
+
+        #[test]
+        #[should_panic(expected = "gradient check failed for weight")]
+        fn fails_for_wrong_gradients() {
+            struct Broken(Network);
+
+            impl Broken {
+                fn check_gradients(
+                    &self,
+                    inputs: &[f32],
+                    targets: &[f32],
+                    loss: Loss,
+                ) -> GradientCheck {
+                    let mut check = self.0.check_gradients(inputs, targets, loss);
+                    check.analytic[0] += 1.0;
+                    check.errors[0] = 1.0;
+                    check
+                }
+            }
+
+            let network = Broken(network(&[
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 1,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Dense,
+                },
+            ]));
+
+            assert_gradients!(network, [0.5, -0.5], [1.0], Loss::MeanSquaredError);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::*;
+use rand_distr::StandardNormal;
+
+/// How `Network::random_with()` picks the initial weights and biases.
+///
+/// Sampling everything from `[-1, 1]` (the default) works fine for
+/// small networks, but the more inputs a neuron has, the larger its
+/// sum tends to get - so wide layers end up saturated (`Tanh`,
+/// `Sigmoid`) or dead (`Relu`) right from the start. `Xavier` and `He`
+/// scale the weights down according to the layer's size, which keeps
+/// outputs of each layer in a sane range.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Initializer {
+    /// Weights and biases from `U(-1, 1)`
+    #[default]
+    Uniform,
+
+    /// Weights from `U(-1, 1)`, biases equal to zero
+    ZeroBias,
+
+    /// Weights from `N(0, 2 / (fan_in + fan_out))`, biases equal to
+    /// zero (Glorot & Bengio, 2010); suits `Tanh` and `Sigmoid`
+    Xavier,
+
+    /// Weights from `N(0, 2 / fan_in)`, biases equal to zero (He et al.,
+    /// 2015); suits `Relu` and `LeakyRelu`
+    He,
+
+    /// Weights from `N(0, std_dev^2)`, biases equal to zero
+    Normal { std_dev: f32 },
+}
+
+impl Initializer {
+    pub(crate) fn bias(self, rng: &mut dyn RngCore) -> f32 {
+        match self {
+            Self::Uniform => rng.gen_range(-1.0..=1.0),
+            _ => 0.0,
+        }
+    }
+
+    /// Samples a weight of a neuron that has `fan_in` inputs, inside of
+    /// a layer that has `fan_out` outputs.
+    pub(crate) fn weight(self, rng: &mut dyn RngCore, fan_in: usize, fan_out: usize) -> f32 {
+        let std_dev = match self {
+            Self::Uniform | Self::ZeroBias => return rng.gen_range(-1.0..=1.0),
+            Self::Xavier => (2.0 / (fan_in + fan_out) as f32).sqrt(),
+            Self::He => (2.0 / fan_in as f32).sqrt(),
+            Self::Normal { std_dev } => std_dev,
+        };
+
+        std_dev * rng.sample::<f32, _>(StandardNormal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Returns mean and standard deviation of weights sampled by given
+    /// initializer.
+    fn sample(initializer: Initializer, fan_in: usize, fan_out: usize) -> (f32, f32) {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let weights: Vec<_> = (0..10_000)
+            .map(|_| initializer.weight(&mut rng, fan_in, fan_out))
+            .collect();
+
+        let mean = weights.iter().sum::<f32>() / weights.len() as f32;
+
+        let variance = weights
+            .iter()
+            .map(|weight| (weight - mean).powi(2))
+            .sum::<f32>()
+            / weights.len() as f32;
+
+        (mean, variance.sqrt())
+    }
+
+    mod weight {
+        use super::*;
+
+        #[test]
+        fn uniform() {
+            let (mean, std_dev) = sample(Initializer::Uniform, 100, 10);
+
+            approx::assert_abs_diff_eq!(mean, 0.0, epsilon = 0.02);
+            approx::assert_abs_diff_eq!(std_dev, (1.0f32 / 3.0).sqrt(), epsilon = 0.02);
+        }
+
+        #[test]
+        fn xavier() {
+            let (mean, std_dev) = sample(Initializer::Xavier, 100, 100);
+
+            approx::assert_abs_diff_eq!(mean, 0.0, epsilon = 0.01);
+            approx::assert_abs_diff_eq!(std_dev, 0.1, epsilon = 0.01);
+        }
+
+        #[test]
+        fn he() {
+            let (mean, std_dev) = sample(Initializer::He, 200, 10);
+
+            approx::assert_abs_diff_eq!(mean, 0.0, epsilon = 0.01);
+            approx::assert_abs_diff_eq!(std_dev, 0.1, epsilon = 0.01);
+        }
+
+        #[test]
+        fn normal() {
+            let (mean, std_dev) = sample(Initializer::Normal { std_dev: 0.5 }, 3, 2);
+
+            approx::assert_abs_diff_eq!(mean, 0.0, epsilon = 0.02);
+            approx::assert_abs_diff_eq!(std_dev, 0.5, epsilon = 0.02);
+        }
+    }
+
+    mod bias {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            approx::assert_relative_eq!(Initializer::Uniform.bias(&mut rng), -0.6255188);
+
+            for initializer in [
+                Initializer::ZeroBias,
+                Initializer::Xavier,
+                Initializer::He,
+                Initializer::Normal { std_dev: 0.5 },
+            ] {
+                assert_eq!(initializer.bias(&mut rng), 0.0);
+            }
+        }
+    }
+}
@@ -1,47 +1,519 @@
 use crate::*;
 
+/// Added to the variance in `LayerKind::LayerNorm`, so that constant
+/// inputs don't cause a division by zero.
+const LAYER_NORM_EPSILON: f32 = 1e-5;
+
 #[derive(Clone, Debug)]
 pub struct Layer {
     pub neurons: Vec<Neuron>,
+    pub activation: Activation,
+    pub kind: LayerKind,
+
+    /// Number of outputs; kept separately, since softmax and dropout
+    /// layers have no neurons to count.
+    pub size: usize,
 }
 
 impl Layer {
-    pub fn new(neurons: Vec<Neuron>) -> Self {
-        assert!(!neurons.is_empty());
+    pub fn new(neurons: Vec<Neuron>, activation: Activation, kind: LayerKind) -> Self {
+        Self::try_new(neurons, activation, kind).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        assert!(neurons
-            .iter()
-            .all(|neuron| neuron.weights.len() == neurons[0].weights.len()));
+    /// Creates a layer of given kind; for recurrent layers, each neuron
+    /// expects the layer's inputs followed by its hidden state, and for
+    /// layer norm, each neuron holds one output's `beta` (as its bias)
+    /// and `gamma` (as its only weight).
+    ///
+    /// Softmax and dropout layers have no neurons - they are created
+    /// with `Self::without_neurons()`.
+    pub fn try_new(
+        neurons: Vec<Neuron>,
+        activation: Activation,
+        kind: LayerKind,
+    ) -> Result<Self, NetworkError> {
+        if !kind.has_neurons() && kind != LayerKind::LayerNorm {
+            return Err(NetworkError::UnexpectedNeurons { layer: 0, kind });
+        }
+
+        let expected = match neurons.first() {
+            Some(neuron) if kind == LayerKind::LayerNorm => {
+                if neuron.weights.len() != 1 {
+                    return Err(NetworkError::WeightsMismatch {
+                        layer: 0,
+                        neuron: 0,
+                        expected: 1,
+                        actual: neuron.weights.len(),
+                    });
+                }
+
+                1
+            }
+            Some(neuron) => neuron.weights.len(),
+            None => return Err(NetworkError::EmptyLayer { layer: 0 }),
+        };
+
+        for (index, neuron) in neurons.iter().enumerate() {
+            if neuron.weights.is_empty() {
+                return Err(NetworkError::EmptyNeuron {
+                    layer: 0,
+                    neuron: index,
+                });
+            }
+
+            if neuron.weights.len() != expected {
+                return Err(NetworkError::WeightsMismatch {
+                    layer: 0,
+                    neuron: index,
+                    expected,
+                    actual: neuron.weights.len(),
+                });
+            }
+        }
+
+        let size = neurons.len() / kind.gates();
+
+        if size * kind.gates() != neurons.len() {
+            return Err(NetworkError::IncompleteGates {
+                layer: 0,
+                kind,
+                gates: kind.gates(),
+                actual: neurons.len(),
+            });
+        }
+
+        let state_size = kind.state_size(size);
+
+        if expected <= state_size {
+            return Err(NetworkError::MissingInputWeights {
+                layer: 0,
+                kind,
+                state_size,
+                actual: expected,
+            });
+        }
 
-        Self { neurons }
+        Ok(Self {
+            size,
+            neurons,
+            activation,
+            kind,
+        })
+    }
+
+    /// Creates a layer that has no parameters (softmax or dropout).
+    pub fn without_neurons(size: usize, activation: Activation, kind: LayerKind) -> Self {
+        assert!(
+            matches!(kind, LayerKind::Softmax | LayerKind::Dropout { .. }),
+            "{:?} layers need neurons",
+            kind
+        );
+
+        Self {
+            neurons: Vec::new(),
+            activation,
+            kind,
+            size,
+        }
     }
 
     pub fn from_weights(
         input_size: usize,
         output_size: usize,
+        activation: Activation,
+        kind: LayerKind,
         weights: &mut dyn Iterator<Item = f32>,
-    ) -> Self {
-        let neurons = (0..output_size)
-            .map(|_| Neuron::from_weights(input_size, weights))
-            .collect();
+    ) -> Result<Self, NetworkError> {
+        let too_large = || NetworkError::LayerTooLarge { layer: 0 };
+
+        let neuron_size = match kind {
+            LayerKind::Softmax | LayerKind::Dropout { .. } => {
+                return Ok(Self::without_neurons(output_size, activation, kind));
+            }
+            LayerKind::LayerNorm => 1,
+            _ => input_size
+                .checked_add(kind.state_size(output_size))
+                .ok_or_else(too_large)?,
+        };
+
+        let neurons_count = kind
+            .gates()
+            .checked_mul(output_size)
+            .ok_or_else(too_large)?;
+
+        let neurons = (0..neurons_count)
+            .map(|index| {
+                Neuron::from_weights(neuron_size, weights).map_err(|err| err.in_neuron(index))
+            })
+            .collect::<Result<_, _>>()?;
 
-        Self { neurons }
+        Self::try_new(neurons, activation, kind)
     }
 
-    pub fn random(rng: &mut dyn RngCore, input_neurons: usize, output_neurons: usize) -> Self {
-        let neurons = (0..output_neurons)
-            .map(|_| Neuron::random(rng, input_neurons))
-            .collect();
+    /// Creates a layer with random weights; layer norm ignores the
+    /// `initializer` and always starts as an identity (`gamma = 1` and
+    /// `beta = 0`).
+    pub fn random(
+        rng: &mut dyn RngCore,
+        input_neurons: usize,
+        output_neurons: usize,
+        activation: Activation,
+        kind: LayerKind,
+        initializer: Initializer,
+    ) -> Self {
+        let neurons = match kind {
+            LayerKind::Softmax | LayerKind::Dropout { .. } => {
+                return Self::without_neurons(output_neurons, activation, kind);
+            }
+
+            LayerKind::LayerNorm => (0..output_neurons)
+                .map(|_| Neuron::new(0.0, vec![1.0]))
+                .collect(),
+
+            _ => {
+                let neuron_size = input_neurons + kind.state_size(output_neurons);
+
+                let neurons_count = kind
+                    .gates()
+                    .checked_mul(output_neurons)
+                    .expect("layer is too large");
 
-        Self::new(neurons)
+                (0..neurons_count)
+                    .map(|_| Neuron::random(rng, neuron_size, output_neurons, initializer))
+                    .collect()
+            }
+        };
+
+        Self::new(neurons, activation, kind)
     }
 
+    /// Propagates `inputs` through the layer; recurrent layers start
+    /// from (and then forget) a zeroed hidden state.
     pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        if self.kind == LayerKind::Dense {
+            self.propagate_dense(&inputs)
+        } else {
+            self.step(inputs, &mut vec![0.0; self.state_size()])
+        }
+    }
+
+    /// Propagates `inputs` through the layer, reading and then updating
+    /// its `hidden` state (which, for feed-forward layers, is empty).
+    pub(crate) fn step(&self, inputs: Vec<f32>, hidden: &mut [f32]) -> Vec<f32> {
+        assert_eq!(hidden.len(), self.state_size());
+
+        let mut inputs = inputs;
+        inputs.extend_from_slice(hidden);
+
+        match self.kind {
+            LayerKind::Dense => self.propagate_dense(&inputs),
+
+            LayerKind::Rnn => {
+                let outputs = self.propagate_dense(&inputs);
+                hidden.copy_from_slice(&outputs);
+                outputs
+            }
+
+            LayerKind::Gru => {
+                let size = self.output_size();
+                let (gates, candidates) = self.neurons.split_at(2 * size);
+
+                let gates: Vec<_> = gates
+                    .iter()
+                    .map(|neuron| neuron.propagate(&inputs, Activation::Sigmoid))
+                    .collect();
+
+                let (update, reset) = gates.split_at(size);
+
+                // The candidate sees only the part of hidden state that
+                // the reset gate lets through
+                let hidden_inputs = inputs.len() - size;
+
+                for (input, reset) in inputs[hidden_inputs..].iter_mut().zip(reset) {
+                    *input *= reset;
+                }
+
+                for ((hidden, candidate), update) in hidden.iter_mut().zip(candidates).zip(update) {
+                    let candidate = candidate.propagate(&inputs, self.activation);
+
+                    *hidden = (1.0 - update) * candidate + update * *hidden;
+                }
+
+                hidden.to_vec()
+            }
+
+            LayerKind::Softmax => {
+                let mut outputs = vec![0.0; inputs.len()];
+                Self::softmax(&inputs, &mut outputs);
+                outputs
+            }
+
+            LayerKind::LayerNorm => self
+                .sums(&inputs, None)
+                .into_iter()
+                .map(|sum| self.activation.apply(sum))
+                .collect(),
+
+            LayerKind::Dropout { .. } => inputs,
+        }
+    }
+
+    fn propagate_dense(&self, inputs: &[f32]) -> Vec<f32> {
         self.neurons
             .iter()
-            .map(|neuron| neuron.propagate(&inputs))
+            .map(|neuron| neuron.propagate(inputs, self.activation))
             .collect()
     }
+
+    pub fn try_propagate(&self, inputs: Vec<f32>) -> Result<Vec<f32>, NetworkError> {
+        let expected = self.input_size();
+
+        if inputs.len() != expected {
+            return Err(NetworkError::InputsMismatch {
+                layer: 0,
+                expected,
+                actual: inputs.len(),
+            });
+        }
+
+        Ok(self.propagate(inputs))
+    }
+
+    /// Number of inputs this layer expects.
+    pub(crate) fn input_size(&self) -> usize {
+        if self.kind.has_neurons() {
+            self.neurons[0].weights.len() - self.state_size()
+        } else {
+            self.size
+        }
+    }
+
+    /// Number of outputs this layer returns.
+    pub(crate) fn output_size(&self) -> usize {
+        self.size
+    }
+
+    /// Size of this layer's hidden state; zero for feed-forward layers.
+    pub(crate) fn state_size(&self) -> usize {
+        self.kind.state_size(self.output_size())
+    }
+
+    /// Performs the first half of the forward pass, returning what
+    /// `Self::backward()` later needs to know about it:
+    ///
+    /// - for layers with neurons and layer norm, these are the outputs
+    ///   *before* the activation function,
+    /// - for softmax, these are the outputs,
+    /// - for dropout, these are factors each input gets multiplied by;
+    ///   inputs get dropped only when given `dropout` rng.
+    ///
+    /// See `Self::outputs()` for the second half.
+    pub(crate) fn sums(&self, inputs: &[f32], dropout: Option<&mut dyn RngCore>) -> Vec<f32> {
+        match self.kind {
+            LayerKind::Dense | LayerKind::Rnn | LayerKind::Gru => self
+                .neurons
+                .iter()
+                .map(|neuron| neuron.sum(inputs))
+                .collect(),
+
+            LayerKind::Softmax => {
+                let mut outputs = vec![0.0; inputs.len()];
+                Self::softmax(inputs, &mut outputs);
+                outputs
+            }
+
+            LayerKind::LayerNorm => {
+                let (mean, inv_std) = Self::normalization(inputs);
+
+                self.neurons
+                    .iter()
+                    .zip(inputs)
+                    .map(|(neuron, &input)| {
+                        Self::normalize(neuron.bias, neuron.weights[0], input, mean, inv_std)
+                    })
+                    .collect()
+            }
+
+            LayerKind::Dropout { rate } => match dropout {
+                Some(rng) => (0..inputs.len())
+                    .map(|_| {
+                        if rng.gen::<f32>() < rate {
+                            0.0
+                        } else {
+                            1.0 / (1.0 - rate)
+                        }
+                    })
+                    .collect(),
+
+                None => vec![1.0; inputs.len()],
+            },
+        }
+    }
+
+    /// Turns what `Self::sums()` returned into the layer's outputs.
+    pub(crate) fn outputs(&self, inputs: &[f32], sums: &[f32]) -> Vec<f32> {
+        match self.kind {
+            LayerKind::Softmax => sums.to_vec(),
+
+            LayerKind::Dropout { .. } => inputs
+                .iter()
+                .zip(sums)
+                .map(|(input, factor)| input * factor)
+                .collect(),
+
+            _ => sums.iter().map(|&sum| self.activation.apply(sum)).collect(),
+        }
+    }
+
+    /// Number of parameters this layer contributes to
+    /// `Network::weights()`.
+    pub(crate) fn weights_count(&self) -> usize {
+        self.neurons
+            .iter()
+            .map(|neuron| neuron.weights.len() + 1)
+            .sum()
+    }
+
+    /// Backpropagates `output_gradients` (derivatives of the loss with
+    /// respect to this layer's outputs) through the layer.
+    ///
+    /// `sums` must come from `Self::sums(inputs)`; parameter gradients
+    /// are accumulated into `gradients` and the derivatives with respect
+    /// to `inputs` are returned.
+    pub(crate) fn backward(
+        &self,
+        inputs: &[f32],
+        sums: &[f32],
+        output_gradients: &[f32],
+        gradients: &mut [f32],
+    ) -> Vec<f32> {
+        assert_eq!(output_gradients.len(), self.output_size());
+        assert_eq!(gradients.len(), self.weights_count());
+
+        match self.kind {
+            LayerKind::Dense | LayerKind::Rnn | LayerKind::Gru => {
+                assert_eq!(sums.len(), self.neurons.len());
+
+                let mut input_gradients = vec![0.0; inputs.len()];
+
+                for ((neuron, gradients), (&sum, &output_gradient)) in self
+                    .neurons
+                    .iter()
+                    .zip(gradients.chunks_mut(inputs.len() + 1))
+                    .zip(sums.iter().zip(output_gradients))
+                {
+                    let delta = output_gradient * self.activation.derivative(sum);
+
+                    neuron.backward(inputs, delta, gradients, &mut input_gradients);
+                }
+
+                input_gradients
+            }
+
+            LayerKind::Softmax => {
+                let dot: f32 = output_gradients
+                    .iter()
+                    .zip(sums)
+                    .map(|(gradient, output)| gradient * output)
+                    .sum();
+
+                sums.iter()
+                    .zip(output_gradients)
+                    .map(|(output, gradient)| output * (gradient - dot))
+                    .collect()
+            }
+
+            LayerKind::LayerNorm => {
+                let (mean, inv_std) = Self::normalization(inputs);
+                let len = inputs.len() as f32;
+
+                let normalized: Vec<_> = inputs
+                    .iter()
+                    .map(|input| (input - mean) * inv_std)
+                    .collect();
+
+                // Derivatives with respect to the normalized inputs
+                let normalized_gradients: Vec<_> = self
+                    .neurons
+                    .iter()
+                    .zip(gradients.chunks_mut(2))
+                    .zip(sums.iter().zip(output_gradients))
+                    .zip(&normalized)
+                    .map(
+                        |(((neuron, gradients), (&sum, &output_gradient)), normalized)| {
+                            let delta = output_gradient * self.activation.derivative(sum);
+
+                            gradients[0] += delta;
+                            gradients[1] += delta * normalized;
+
+                            delta * neuron.weights[0]
+                        },
+                    )
+                    .collect();
+
+                let mean_gradient = normalized_gradients.iter().sum::<f32>() / len;
+
+                let mean_projection = normalized_gradients
+                    .iter()
+                    .zip(&normalized)
+                    .map(|(gradient, normalized)| gradient * normalized)
+                    .sum::<f32>()
+                    / len;
+
+                normalized_gradients
+                    .iter()
+                    .zip(&normalized)
+                    .map(|(gradient, normalized)| {
+                        inv_std * (gradient - mean_gradient - normalized * mean_projection)
+                    })
+                    .collect()
+            }
+
+            LayerKind::Dropout { .. } => output_gradients
+                .iter()
+                .zip(sums)
+                .map(|(gradient, factor)| gradient * factor)
+                .collect(),
+        }
+    }
+
+    /// Writes softmax of `inputs` into `outputs`.
+    pub(crate) fn softmax(inputs: &[f32], outputs: &mut [f32]) {
+        // Subtracting the maximum doesn't change the result, but keeps
+        // `exp()` from overflowing
+        let max = inputs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        for (output, input) in outputs.iter_mut().zip(inputs) {
+            *output = (input - max).exp();
+        }
+
+        let sum = outputs.iter().sum::<f32>();
+
+        for output in outputs.iter_mut() {
+            *output /= sum;
+        }
+    }
+
+    /// Returns mean and inverse of the standard deviation of `inputs`,
+    /// as used by layer norm.
+    pub(crate) fn normalization(inputs: &[f32]) -> (f32, f32) {
+        let len = inputs.len() as f32;
+        let mean = inputs.iter().sum::<f32>() / len;
+
+        let variance = inputs
+            .iter()
+            .map(|input| (input - mean).powi(2))
+            .sum::<f32>()
+            / len;
+
+        (mean, 1.0 / (variance + LAYER_NORM_EPSILON).sqrt())
+    }
+
+    /// Layer norm's output for a single input, before the activation
+    /// function.
+    pub(crate) fn normalize(beta: f32, gamma: f32, input: f32, mean: f32, inv_std: f32) -> f32 {
+        gamma * ((input - mean) * inv_std) + beta
+    }
 }
 
 #[cfg(test)]
@@ -56,7 +528,14 @@ mod tests {
         #[test]
         fn test() {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
-            let layer = Layer::random(&mut rng, 3, 2);
+            let layer = Layer::random(
+                &mut rng,
+                3,
+                2,
+                Activation::Relu,
+                LayerKind::Dense,
+                Initializer::Uniform,
+            );
 
             let actual_biases: Vec<_> = layer.neurons.iter().map(|neuron| neuron.bias).collect();
             let expected_biases = vec![-0.6255188, 0.5238807];
@@ -85,15 +564,319 @@ mod tests {
                 Neuron::new(0.0, vec![0.1, 0.2, 0.3]),
                 Neuron::new(0.0, vec![0.4, 0.5, 0.6]),
             );
-            let layer = Layer::new(vec![neurons.0.clone(), neurons.1.clone()]);
+            let layer = Layer::new(
+                vec![neurons.0.clone(), neurons.1.clone()],
+                Activation::Sigmoid,
+                LayerKind::Dense,
+            );
 
             let inputs = &[-0.5, 0.0, 0.5];
 
             let actual = layer.propagate(inputs.to_vec());
-            let expected = vec![neurons.0.propagate(inputs), neurons.1.propagate(inputs)];
+            let expected = vec![
+                neurons.0.propagate(inputs, Activation::Sigmoid),
+                neurons.1.propagate(inputs, Activation::Sigmoid),
+            ];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn softmax() {
+            let layer = Layer::without_neurons(3, Activation::Relu, LayerKind::Softmax);
+
+            let actual = layer.propagate(vec![1.0, 2.0, -1.0]);
+            let sum = 1.0f32.exp() + 2.0f32.exp() + (-1.0f32).exp();
+            let expected = vec![
+                1.0f32.exp() / sum,
+                2.0f32.exp() / sum,
+                (-1.0f32).exp() / sum,
+            ];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn softmax_survives_large_inputs() {
+            let layer = Layer::without_neurons(2, Activation::Relu, LayerKind::Softmax);
+
+            let actual = layer.propagate(vec![1000.0, 1000.0]);
+
+            approx::assert_relative_eq!(actual.as_slice(), [0.5, 0.5].as_slice());
+        }
+
+        #[test]
+        fn layer_norm() {
+            let layer = Layer::new(
+                vec![
+                    Neuron::new(0.0, vec![1.0]),
+                    Neuron::new(0.5, vec![2.0]),
+                    Neuron::new(0.0, vec![1.0]),
+                ],
+                Activation::Identity,
+                LayerKind::LayerNorm,
+            );
+
+            assert_eq!(layer.input_size(), 3);
+            assert_eq!(layer.output_size(), 3);
+
+            // mean = 2, std = sqrt(2 / 3)
+            let actual = layer.propagate(vec![1.0, 2.0, 3.0]);
+            let normalized = 1.0 / (2.0f32 / 3.0 + LAYER_NORM_EPSILON).sqrt();
+            let expected = vec![-normalized, 0.5, normalized];
 
             approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
         }
+
+        #[test]
+        fn dropout() {
+            let layer =
+                Layer::without_neurons(3, Activation::Relu, LayerKind::Dropout { rate: 0.5 });
+
+            assert_eq!(layer.propagate(vec![1.0, -2.0, 3.0]), vec![1.0, -2.0, 3.0]);
+        }
+    }
+
+    mod sums {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        #[test]
+        fn dropout() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let layer =
+                Layer::without_neurons(1000, Activation::Relu, LayerKind::Dropout { rate: 0.25 });
+            let inputs = vec![1.0; 1000];
+
+            assert_eq!(layer.sums(&inputs, None), inputs);
+
+            let factors = layer.sums(&inputs, Some(&mut rng));
+            let dropped = factors.iter().filter(|&&factor| factor == 0.0).count();
+
+            assert!((200..300).contains(&dropped));
+
+            assert!(factors
+                .iter()
+                .all(|&factor| factor == 0.0 || factor == 1.0 / 0.75));
+        }
+    }
+
+    mod step {
+        use super::*;
+
+        fn run(layer: &Layer, inputs: &[f32]) -> Vec<f32> {
+            let mut hidden = vec![0.0; layer.state_size()];
+
+            inputs
+                .iter()
+                .map(|&input| layer.step(vec![input], &mut hidden)[0])
+                .collect()
+        }
+
+        #[test]
+        fn rnn() {
+            let layer = Layer::try_new(
+                vec![Neuron::new(0.0, vec![1.0, 0.5])],
+                Activation::Identity,
+                LayerKind::Rnn,
+            )
+            .unwrap();
+
+            assert_eq!(layer.input_size(), 1);
+            assert_eq!(layer.output_size(), 1);
+
+            let actual = run(&layer, &[1.0, 0.0, 0.0]);
+            let expected = vec![1.0, 0.5, 0.25];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn gru() {
+            // Both gates are stuck at sigmoid(0) = 0.5, so each step
+            // yields `0.5 * (x + 0.5 * h) + 0.5 * h`
+            let layer = Layer::try_new(
+                vec![
+                    Neuron::new(0.0, vec![0.0, 0.0]),
+                    Neuron::new(0.0, vec![0.0, 0.0]),
+                    Neuron::new(0.0, vec![1.0, 1.0]),
+                ],
+                Activation::Identity,
+                LayerKind::Gru,
+            )
+            .unwrap();
+
+            assert_eq!(layer.input_size(), 1);
+            assert_eq!(layer.output_size(), 1);
+
+            let actual = run(&layer, &[1.0, 0.0, 0.0]);
+            let expected = vec![0.5, 0.375, 0.28125];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn propagate_starts_from_zeroed_state() {
+            let layer = Layer::try_new(
+                vec![Neuron::new(0.0, vec![1.0, 0.5])],
+                Activation::Identity,
+                LayerKind::Rnn,
+            )
+            .unwrap();
+
+            assert_eq!(layer.propagate(vec![1.0]), vec![1.0]);
+            assert_eq!(layer.propagate(vec![1.0]), vec![1.0]);
+        }
+    }
+
+    mod try_new {
+        use super::*;
+
+        #[test]
+        fn rejects_wide_layer_norm() {
+            let actual = Layer::try_new(
+                vec![Neuron::new(0.0, vec![1.0, 1.0])],
+                Activation::Identity,
+                LayerKind::LayerNorm,
+            );
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::WeightsMismatch {
+                    layer: 0,
+                    neuron: 0,
+                    expected: 1,
+                    actual: 2
+                }
+            );
+        }
+
+        #[test]
+        fn rejects_no_neurons() {
+            assert_eq!(
+                Layer::try_new(vec![], Activation::Relu, LayerKind::Dense).unwrap_err(),
+                NetworkError::EmptyLayer { layer: 0 }
+            );
+        }
+
+        #[test]
+        fn rejects_mismatched_neurons() {
+            let actual = Layer::try_new(
+                vec![
+                    Neuron::new(0.0, vec![0.1, 0.2]),
+                    Neuron::new(0.0, vec![0.1, 0.2]),
+                    Neuron::new(0.0, vec![0.1]),
+                ],
+                Activation::Relu,
+                LayerKind::Dense,
+            );
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::WeightsMismatch {
+                    layer: 0,
+                    neuron: 2,
+                    expected: 2,
+                    actual: 1
+                }
+            );
+        }
+
+        #[test]
+        fn rejects_neurons_for_softmax() {
+            let actual = Layer::try_new(
+                vec![Neuron::new(0.0, vec![0.1, 0.2])],
+                Activation::Identity,
+                LayerKind::Softmax,
+            );
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::UnexpectedNeurons {
+                    layer: 0,
+                    kind: LayerKind::Softmax
+                }
+            );
+        }
+
+        #[test]
+        fn rejects_incomplete_gru() {
+            let actual = Layer::try_new(
+                vec![
+                    Neuron::new(0.0, vec![0.1, 0.2]),
+                    Neuron::new(0.0, vec![0.1, 0.2]),
+                ],
+                Activation::Tanh,
+                LayerKind::Gru,
+            );
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::IncompleteGates {
+                    layer: 0,
+                    kind: LayerKind::Gru,
+                    gates: 3,
+                    actual: 2
+                }
+            );
+        }
+
+        #[test]
+        fn rejects_rnn_without_input_weights() {
+            // Two outputs means two weights for the hidden state alone
+            let actual = Layer::try_new(
+                vec![
+                    Neuron::new(0.0, vec![0.1, 0.2]),
+                    Neuron::new(0.0, vec![0.1, 0.2]),
+                ],
+                Activation::Tanh,
+                LayerKind::Rnn,
+            );
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::MissingInputWeights {
+                    layer: 0,
+                    kind: LayerKind::Rnn,
+                    state_size: 2,
+                    actual: 2
+                }
+            );
+        }
+    }
+
+    mod backward {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let layer = Layer::new(
+                vec![
+                    Neuron::new(0.0, vec![1.0, -1.0]),
+                    Neuron::new(0.5, vec![2.0, 0.5]),
+                ],
+                Activation::Relu,
+                LayerKind::Dense,
+            );
+
+            let inputs = [1.0, 2.0];
+            let sums = layer.sums(&inputs, None);
+            let mut gradients = vec![0.0; 6];
+
+            // First neuron's sum is negative, so ReLU lets no gradient
+            // through it
+            let input_gradients = layer.backward(&inputs, &sums, &[3.0, 2.0], &mut gradients);
+
+            approx::assert_relative_eq!(sums.as_slice(), [-1.0, 3.5].as_slice());
+
+            approx::assert_relative_eq!(
+                gradients.as_slice(),
+                [0.0, 0.0, 0.0, 2.0, 2.0, 4.0].as_slice()
+            );
+
+            approx::assert_relative_eq!(input_gradients.as_slice(), [4.0, 1.0].as_slice());
+        }
     }
 
     mod from_weights {
@@ -104,8 +887,11 @@ mod tests {
             let layer = Layer::from_weights(
                 3,
                 2,
+                Activation::Relu,
+                LayerKind::Dense,
                 &mut vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8].into_iter(),
-            );
+            )
+            .unwrap();
 
             let actual_biases: Vec<_> = layer.neurons.iter().map(|neuron| neuron.bias).collect();
             let expected_biases = vec![0.1, 0.5];
@@ -120,5 +906,44 @@ mod tests {
             approx::assert_relative_eq!(actual_biases.as_slice(), expected_biases.as_slice());
             approx::assert_relative_eq!(actual_weights.as_slice(), expected_weights.as_slice());
         }
+
+        #[test]
+        fn takes_parameters_only_where_needed() {
+            let mut weights = vec![0.1, 0.2, 0.3, 0.4, 0.5].into_iter();
+
+            let softmax =
+                Layer::from_weights(2, 2, Activation::Relu, LayerKind::Softmax, &mut weights)
+                    .unwrap();
+
+            let layer_norm =
+                Layer::from_weights(2, 2, Activation::Relu, LayerKind::LayerNorm, &mut weights)
+                    .unwrap();
+
+            assert!(softmax.neurons.is_empty());
+            assert_eq!(softmax.weights_count(), 0);
+            assert_eq!(layer_norm.weights_count(), 4);
+            assert_eq!(layer_norm.neurons[1].bias, 0.3);
+            assert_eq!(layer_norm.neurons[1].weights, vec![0.4]);
+            assert_eq!(weights.next(), Some(0.5));
+        }
+
+        #[test]
+        fn reports_which_neuron_ran_out_of_weights() {
+            let actual = Layer::from_weights(
+                3,
+                2,
+                Activation::Relu,
+                LayerKind::Dense,
+                &mut vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6].into_iter(),
+            );
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::NotEnoughWeights {
+                    layer: 0,
+                    neuron: 1
+                }
+            );
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+/// How a layer turns its inputs into outputs.
+///
+/// Recurrent layers (`Rnn` and `Gru`) additionally remember their
+/// previous outputs (the *hidden state*) and feed them back in on the
+/// next step - see `Network::step()`.
+///
+/// `Softmax`, `LayerNorm` and `Dropout` work on each input separately
+/// (or on all of them together), so they must have as many neurons as
+/// the layer before them; only `LayerNorm` has parameters of its own.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayerKind {
+    /// Plain, feed-forward layer: `activation(bias + W * x)`
+    #[default]
+    Dense,
+
+    /// Simple (Elman) recurrent layer:
+    ///
+    /// `h = activation(bias + W * x + U * h)`
+    Rnn,
+
+    /// Gated recurrent unit (as in Cho et al., 2014):
+    ///
+    /// ```text
+    /// z = sigmoid(b_z + W_z * x + U_z * h)
+    /// r = sigmoid(b_r + W_r * x + U_r * h)
+    /// n = activation(b_n + W_n * x + U_n * (r . h))
+    /// h = (1 - z) . n + z . h
+    /// ```
+    Gru,
+
+    /// `y_i = e^x_i / sum(e^x)`, turning inputs into probabilities;
+    /// the layer's activation is not applied
+    Softmax,
+
+    /// Layer normalization (as in Ba et al., 2016):
+    ///
+    /// `y_i = activation(gamma_i * (x_i - mean(x)) / std(x) + beta_i)`
+    ///
+    /// `gamma` starts at one and `beta` at zero; in `Network::weights()`
+    /// each output contributes `[beta, gamma]`
+    LayerNorm,
+
+    /// During training (see `Network::train_batch_with_dropout()`),
+    /// zeroes each input with probability of `rate` and scales the
+    /// remaining ones by `1 / (1 - rate)`; otherwise passes inputs
+    /// through unchanged.
+    ///
+    /// The layer's activation is not applied.
+    Dropout { rate: f32 },
+}
+
+/// Dropout rates are compared bit by bit, which (unlike `f32`'s own
+/// comparison) holds even for NaNs, so `LayerKind` can stay `Eq`.
+impl PartialEq for LayerKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Dropout { rate: a }, Self::Dropout { rate: b }) => a.to_bits() == b.to_bits(),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for LayerKind {}
+
+impl LayerKind {
+    pub fn is_recurrent(self) -> bool {
+        matches!(self, Self::Rnn | Self::Gru)
+    }
+
+    /// Returns whether this layer is made of neurons that see all of the
+    /// layer's inputs (as opposed to e.g. `Softmax`, whose outputs
+    /// mirror its inputs one-to-one).
+    pub(crate) fn has_neurons(self) -> bool {
+        matches!(self, Self::Dense | Self::Rnn | Self::Gru)
+    }
+
+    /// Number of neurons backing each of layer's outputs; GRU needs
+    /// three of them - for the update gate, the reset gate and the
+    /// candidate.
+    pub(crate) fn gates(self) -> usize {
+        match self {
+            Self::Gru => 3,
+            _ => 1,
+        }
+    }
+
+    /// Size of the hidden state of a layer with given number of
+    /// outputs.
+    pub(crate) fn state_size(self, outputs: usize) -> usize {
+        if self.is_recurrent() {
+            outputs
+        } else {
+            0
+        }
+    }
+
+    /// Number of parameters (biases and weights) of a layer with given
+    /// shape, or `None` if it doesn't fit in `usize` (which can happen
+    /// for topologies read from a crafted file).
+    pub(crate) fn weights_count(self, inputs: usize, outputs: usize) -> Option<usize> {
+        match self {
+            Self::Dense | Self::Rnn | Self::Gru => self.gates().checked_mul(outputs)?.checked_mul(
+                inputs
+                    .checked_add(self.state_size(outputs))?
+                    .checked_add(1)?,
+            ),
+            Self::LayerNorm => outputs.checked_mul(2),
+            Self::Softmax | Self::Dropout { .. } => Some(0),
+        }
+    }
+}
@@ -1,4 +1,18 @@
-#[derive(Clone, Copy, Debug)]
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LayerTopology {
     pub neurons: usize,
-}
\ No newline at end of file
+
+    /// Activation applied by this layer's neurons.
+    ///
+    /// Ignored for the first (input) layer, since it doesn't contain
+    /// any neurons of its own.
+    #[serde(default)]
+    pub activation: Activation,
+
+    /// Kind of this layer; ditto, ignored for the input layer.
+    #[serde(default)]
+    pub kind: LayerKind,
+}
@@ -1,12 +1,28 @@
-pub use self::layer_topology::*;
+pub use self::{
+    activation::*, batch::*, error::*, format::*, gradient_check::*, initializer::*, layer_kind::*,
+    layer_topology::*, loss::*, optimizer::*, quantized::*, state::*,
+};
 
 use self::{layer::*, neuron::*};
 use rand::{Rng, RngCore};
 use std::iter::once;
 
+pub mod neat;
+
+mod activation;
+mod batch;
+mod error;
+mod format;
+mod gradient_check;
+mod initializer;
 mod layer;
+mod layer_kind;
 mod layer_topology;
+mod loss;
 mod neuron;
+mod optimizer;
+mod quantized;
+mod state;
 
 #[derive(Clone, Debug)]
 pub struct Network {
@@ -18,40 +34,208 @@ impl Network {
         Self { layers }
     }
 
+    /// Creates a network with weights and biases sampled uniformly from
+    /// `[-1, 1]`; see `Self::random_with()` for other initializers.
     pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
-        assert!(layers.len() > 1);
+        Self::random_with(rng, layers, Initializer::default())
+    }
+
+    pub fn try_random(
+        rng: &mut dyn RngCore,
+        layers: &[LayerTopology],
+    ) -> Result<Self, NetworkError> {
+        Self::try_random_with(rng, layers, Initializer::default())
+    }
+
+    pub fn random_with(
+        rng: &mut dyn RngCore,
+        layers: &[LayerTopology],
+        initializer: Initializer,
+    ) -> Self {
+        Self::try_random_with(rng, layers, initializer).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_random_with(
+        rng: &mut dyn RngCore,
+        layers: &[LayerTopology],
+        initializer: Initializer,
+    ) -> Result<Self, NetworkError> {
+        Self::validate_topology(layers)?;
 
         let layers = layers
             .windows(2)
-            .map(|layers| Layer::random(rng, layers[0].neurons, layers[1].neurons))
+            .map(|layers| {
+                Layer::random(
+                    rng,
+                    layers[0].neurons,
+                    layers[1].neurons,
+                    layers[1].activation,
+                    layers[1].kind,
+                    initializer,
+                )
+            })
             .collect();
 
-        Self::new(layers)
+        Ok(Self::new(layers))
     }
 
     pub fn from_weights(layers: &[LayerTopology], weights: impl IntoIterator<Item = f32>) -> Self {
-        assert!(layers.len() > 1);
+        Self::try_from_weights(layers, weights).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_from_weights(
+        layers: &[LayerTopology],
+        weights: impl IntoIterator<Item = f32>,
+    ) -> Result<Self, NetworkError> {
+        Self::validate_topology(layers)?;
 
         let mut weights = weights.into_iter();
 
-        let layers = layers
+        let layers: Vec<_> = layers
             .windows(2)
-            .map(|layers| Layer::from_weights(layers[0].neurons, layers[1].neurons, &mut weights))
-            .collect();
+            .enumerate()
+            .map(|(index, layers)| {
+                Layer::from_weights(
+                    layers[0].neurons,
+                    layers[1].neurons,
+                    layers[1].activation,
+                    layers[1].kind,
+                    &mut weights,
+                )
+                .map_err(|err| err.in_layer(index + 1))
+            })
+            .collect::<Result<_, _>>()?;
 
+        // Checking for just a single leftover weight (instead of counting
+        // all of them) keeps this working for unbounded iterators
         if weights.next().is_some() {
-            panic!("got too many weights");
+            return Err(NetworkError::TooManyWeights {
+                expected: layers.iter().map(Layer::weights_count).sum(),
+            });
         }
 
-        Self::new(layers)
+        Ok(Self::new(layers))
     }
 
+    /// Propagates `inputs` through the network.
+    ///
+    /// Recurrent layers start from a zeroed hidden state, which is then
+    /// thrown away - use `Self::step()` for networks that should
+    /// remember things.
     pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
         self.layers
             .iter()
             .fold(inputs, |inputs, layer| layer.propagate(inputs))
     }
 
+    /// Propagates `inputs` through the network, carrying hidden state
+    /// of recurrent layers over from the previous step.
+    ///
+    /// For purely feed-forward networks, this is the same as
+    /// `Self::propagate()`.
+    pub fn step(&self, inputs: Vec<f32>, state: &mut NetworkState) -> Vec<f32> {
+        assert_eq!(
+            state.hidden.len(),
+            self.layers.len(),
+            "got state of a different network"
+        );
+
+        self.layers
+            .iter()
+            .zip(&mut state.hidden)
+            .fold(inputs, |inputs, (layer, hidden)| layer.step(inputs, hidden))
+    }
+
+    /// Propagates `inputs` through the network, returning what each
+    /// layer has output along the way - starting with `inputs` itself,
+    /// so that `activations[i]` lines up with `self.topology()[i]`.
+    ///
+    /// Just like in `Self::propagate()`, recurrent layers start from a
+    /// zeroed hidden state.
+    pub fn activations(&self, inputs: Vec<f32>) -> Vec<Vec<f32>> {
+        let mut activations = Vec::with_capacity(self.layers.len() + 1);
+
+        activations.push(inputs);
+
+        for layer in &self.layers {
+            let outputs = layer.propagate(activations[activations.len() - 1].clone());
+            activations.push(outputs);
+        }
+
+        activations
+    }
+
+    /// Returns bias and weights of given neuron.
+    ///
+    /// Layers are numbered the same way as in `Self::topology()`, so
+    /// the first layer that has any neurons is `1`.
+    ///
+    /// Neurons of recurrent layers have weights for the layer's inputs
+    /// followed by weights for its hidden state; GRU layers have three
+    /// neurons per output (all update gates, then all reset gates, then
+    /// all candidates).
+    ///
+    /// Layer norm has one neuron per output, holding its `beta` (as the
+    /// bias) and `gamma` (as the only weight); softmax and dropout layers
+    /// have no neurons at all.
+    pub fn neuron(&self, layer: usize, neuron: usize) -> Option<(f32, &[f32])> {
+        let neuron = self
+            .layers
+            .get(layer.checked_sub(1)?)?
+            .neurons
+            .get(neuron)?;
+
+        Some((neuron.bias, &neuron.weights))
+    }
+
+    /// Creates a fresh (zeroed) hidden state for `Self::step()`.
+    pub fn state(&self) -> NetworkState {
+        NetworkState {
+            hidden: self
+                .layers
+                .iter()
+                .map(|layer| vec![0.0; layer.state_size()])
+                .collect(),
+        }
+    }
+
+    /// Returns whether this network contains any recurrent layers.
+    pub fn is_recurrent(&self) -> bool {
+        self.layers.iter().any(|layer| layer.kind.is_recurrent())
+    }
+
+    pub fn try_propagate(&self, inputs: Vec<f32>) -> Result<Vec<f32>, NetworkError> {
+        // Only the first layer has to be checked, since the following
+        // ones are guaranteed to fit by construction
+        let inputs = self.layers[0]
+            .try_propagate(inputs)
+            .map_err(|err| err.in_layer(1))?;
+
+        Ok(self.layers[1..]
+            .iter()
+            .fold(inputs, |inputs, layer| layer.propagate(inputs)))
+    }
+
+    /// Returns the topology this network has been built from.
+    ///
+    /// Since the input layer has no neurons of its own, its activation
+    /// and kind are always the default ones.
+    pub fn topology(&self) -> Vec<LayerTopology> {
+        let input = LayerTopology {
+            neurons: self.layers[0].input_size(),
+            activation: Activation::default(),
+            kind: LayerKind::default(),
+        };
+
+        let layers = self.layers.iter().map(|layer| LayerTopology {
+            neurons: layer.output_size(),
+            activation: layer.activation,
+            kind: layer.kind,
+        });
+
+        once(input).chain(layers).collect()
+    }
+
     pub fn weights(&self) -> impl Iterator<Item = f32> + '_ {
         self.layers
             .iter()
@@ -59,6 +243,181 @@ impl Network {
             .flat_map(|neuron| once(&neuron.bias).chain(&neuron.weights))
             .cloned()
     }
+
+    /// Computes loss for given sample, together with its gradient with
+    /// respect to every weight.
+    ///
+    /// Gradients follow the same layout as `Self::weights()`; recurrent
+    /// networks are not supported.
+    ///
+    /// Dropout layers don't drop anything here, so that the result is
+    /// deterministic.
+    pub fn gradients(&self, inputs: &[f32], targets: &[f32], loss: Loss) -> (f32, Vec<f32>) {
+        let mut gradients = vec![0.0; self.weights_count()];
+        let loss = self.accumulate_gradients(inputs, targets, loss, &mut gradients, None);
+
+        (loss, gradients)
+    }
+
+    /// Performs a single step of gradient descent over given batch of
+    /// `(inputs, targets)` samples and returns the batch's average loss
+    /// (as measured *before* the update).
+    ///
+    /// Dropout layers don't drop anything here - see
+    /// `Self::train_batch_with_dropout()`.
+    pub fn train_batch(
+        &mut self,
+        batch: &[(Vec<f32>, Vec<f32>)],
+        loss: Loss,
+        optimizer: &mut dyn Optimizer,
+    ) -> f32 {
+        self.train(batch, loss, optimizer, None)
+    }
+
+    /// Same as `Self::train_batch()`, but dropout layers use `rng` to
+    /// pick which inputs to drop.
+    pub fn train_batch_with_dropout(
+        &mut self,
+        rng: &mut dyn RngCore,
+        batch: &[(Vec<f32>, Vec<f32>)],
+        loss: Loss,
+        optimizer: &mut dyn Optimizer,
+    ) -> f32 {
+        self.train(batch, loss, optimizer, Some(rng))
+    }
+
+    fn train(
+        &mut self,
+        batch: &[(Vec<f32>, Vec<f32>)],
+        loss: Loss,
+        optimizer: &mut dyn Optimizer,
+        mut dropout: Option<&mut dyn RngCore>,
+    ) -> f32 {
+        assert!(!batch.is_empty());
+
+        let mut gradients = vec![0.0; self.weights_count()];
+
+        let total_loss: f32 = batch
+            .iter()
+            .map(|(inputs, targets)| {
+                let dropout = dropout.as_mut().map(|rng| &mut **rng as &mut dyn RngCore);
+
+                self.accumulate_gradients(inputs, targets, loss, &mut gradients, dropout)
+            })
+            .sum();
+
+        let batch_size = batch.len() as f32;
+
+        for gradient in &mut gradients {
+            *gradient /= batch_size;
+        }
+
+        let mut weights: Vec<_> = self.weights().collect();
+
+        optimizer.step(&mut weights, &gradients);
+
+        for (weight, new_weight) in self.weights_mut().zip(weights) {
+            *weight = new_weight;
+        }
+
+        total_loss / batch_size
+    }
+
+    fn validate_topology(layers: &[LayerTopology]) -> Result<(), NetworkError> {
+        if layers.len() < 2 {
+            return Err(NetworkError::NotEnoughLayers(layers.len()));
+        }
+
+        if let Some(layer) = layers.iter().position(|layer| layer.neurons == 0) {
+            return Err(NetworkError::EmptyLayer { layer });
+        }
+
+        for (index, layers) in layers.windows(2).enumerate() {
+            let layer = index + 1;
+
+            if !layers[1].kind.has_neurons() && layers[1].neurons != layers[0].neurons {
+                return Err(NetworkError::SizeMismatch {
+                    layer,
+                    expected: layers[0].neurons,
+                    actual: layers[1].neurons,
+                });
+            }
+
+            if let kind @ LayerKind::Dropout { rate } = layers[1].kind {
+                if !(0.0..1.0).contains(&rate) {
+                    return Err(NetworkError::InvalidDropoutRate { layer, kind });
+                }
+            }
+
+            if layers[1]
+                .kind
+                .weights_count(layers[0].neurons, layers[1].neurons)
+                .is_none()
+            {
+                return Err(NetworkError::LayerTooLarge { layer });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn weights_count(&self) -> usize {
+        self.layers.iter().map(Layer::weights_count).sum()
+    }
+
+    fn weights_mut(&mut self) -> impl Iterator<Item = &mut f32> + '_ {
+        self.layers
+            .iter_mut()
+            .flat_map(|layer| layer.neurons.iter_mut())
+            .flat_map(|neuron| once(&mut neuron.bias).chain(&mut neuron.weights))
+    }
+
+    fn accumulate_gradients(
+        &self,
+        inputs: &[f32],
+        targets: &[f32],
+        loss: Loss,
+        gradients: &mut [f32],
+        mut dropout: Option<&mut dyn RngCore>,
+    ) -> f32 {
+        assert!(
+            !self.is_recurrent(),
+            "backpropagation through recurrent layers is not supported"
+        );
+
+        // Forward pass, remembering what each layer has seen (and
+        // computed), since the backward pass needs it
+        let mut layer_inputs = Vec::with_capacity(self.layers.len());
+        let mut layer_sums = Vec::with_capacity(self.layers.len());
+        let mut outputs = inputs.to_vec();
+
+        for layer in &self.layers {
+            let dropout = dropout.as_mut().map(|rng| &mut **rng as &mut dyn RngCore);
+
+            let sums = layer.sums(&outputs, dropout);
+            let next_outputs = layer.outputs(&outputs, &sums);
+
+            layer_inputs.push(outputs);
+            layer_sums.push(sums);
+            outputs = next_outputs;
+        }
+
+        // Backward pass
+        let mut output_gradients = loss.gradient(&outputs, targets);
+        let mut end = gradients.len();
+
+        for ((layer, inputs), sums) in self.layers.iter().zip(&layer_inputs).zip(&layer_sums).rev()
+        {
+            let start = end - layer.weights_count();
+
+            output_gradients =
+                layer.backward(inputs, sums, &output_gradients, &mut gradients[start..end]);
+
+            end = start;
+        }
+
+        loss.compute(&outputs, targets)
+    }
 }
 
 #[cfg(test)]
@@ -77,14 +436,28 @@ mod tests {
             let network = Network::random(
                 &mut rng,
                 &[
-                    LayerTopology { neurons: 3 },
-                    LayerTopology { neurons: 2 },
-                    LayerTopology { neurons: 1 },
+                    LayerTopology {
+                        neurons: 3,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                    LayerTopology {
+                        neurons: 2,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                    LayerTopology {
+                        neurons: 1,
+                        activation: Activation::Tanh,
+                        kind: LayerKind::Dense,
+                    },
                 ],
             );
 
             assert_eq!(network.layers.len(), 2);
             assert_eq!(network.layers[0].neurons.len(), 2);
+            assert_eq!(network.layers[0].activation, Activation::Relu);
+            assert_eq!(network.layers[1].activation, Activation::Tanh);
 
             approx::assert_relative_eq!(network.layers[0].neurons[0].bias, -0.6255188);
 
@@ -109,19 +482,262 @@ mod tests {
         }
     }
 
+    mod random_with {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        fn network(initializer: Initializer) -> Network {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            Network::random_with(
+                &mut rng,
+                &[
+                    LayerTopology {
+                        neurons: 50,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                    LayerTopology {
+                        neurons: 2,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                ],
+                initializer,
+            )
+        }
+
+        #[test]
+        fn is_deterministic() {
+            for initializer in [
+                Initializer::Uniform,
+                Initializer::ZeroBias,
+                Initializer::Xavier,
+                Initializer::He,
+                Initializer::Normal { std_dev: 0.1 },
+            ] {
+                assert_eq!(
+                    network(initializer).weights().collect::<Vec<_>>(),
+                    network(initializer).weights().collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        #[test]
+        fn uniform_is_the_default() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let topology = network(Initializer::Uniform).topology();
+
+            assert_eq!(
+                Network::random(&mut rng, &topology)
+                    .weights()
+                    .collect::<Vec<_>>(),
+                network(Initializer::Uniform).weights().collect::<Vec<_>>(),
+            );
+        }
+
+        #[test]
+        fn he() {
+            let network = network(Initializer::He);
+
+            assert_eq!(network.layers[0].neurons[0].bias, 0.0);
+            assert_eq!(network.layers[0].neurons[1].bias, 0.0);
+
+            approx::assert_relative_eq!(
+                &network.layers[0].neurons[0].weights[..3],
+                [0.27553946, 0.081069365, -0.23925379].as_slice(),
+            );
+        }
+    }
+
+    mod try_random {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        #[test]
+        fn rejects_single_layer() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let actual = Network::try_random(
+                &mut rng,
+                &[LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                }],
+            );
+
+            assert_eq!(actual.unwrap_err(), NetworkError::NotEnoughLayers(1));
+        }
+
+        #[test]
+        fn rejects_empty_layer() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let actual = Network::try_random(
+                &mut rng,
+                &[
+                    LayerTopology {
+                        neurons: 3,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                    LayerTopology {
+                        neurons: 0,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                    LayerTopology {
+                        neurons: 1,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                ],
+            );
+
+            assert_eq!(actual.unwrap_err(), NetworkError::EmptyLayer { layer: 1 });
+        }
+    }
+
+    mod validate_topology {
+        use super::*;
+
+        fn topology(kind: LayerKind, neurons: usize) -> [LayerTopology; 3] {
+            [
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons,
+                    activation: Activation::Identity,
+                    kind,
+                },
+            ]
+        }
+
+        #[test]
+        fn rejects_resizing_layers() {
+            for kind in [
+                LayerKind::Softmax,
+                LayerKind::LayerNorm,
+                LayerKind::Dropout { rate: 0.5 },
+            ] {
+                assert_eq!(
+                    Network::validate_topology(&topology(kind, 3)).unwrap_err(),
+                    NetworkError::SizeMismatch {
+                        layer: 2,
+                        expected: 4,
+                        actual: 3
+                    }
+                );
+
+                assert!(Network::validate_topology(&topology(kind, 4)).is_ok());
+            }
+        }
+
+        #[test]
+        fn rejects_invalid_dropout_rate() {
+            for rate in [-0.5, 1.0, f32::NAN] {
+                let actual = Network::validate_topology(&topology(LayerKind::Dropout { rate }, 4));
+
+                assert!(matches!(
+                    actual,
+                    Err(NetworkError::InvalidDropoutRate { layer: 2, .. })
+                ));
+            }
+        }
+    }
+
+    mod try_from_weights {
+        use super::*;
+
+        fn topology() -> [LayerTopology; 3] {
+            [
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+            ]
+        }
+
+        #[test]
+        fn rejects_not_enough_weights() {
+            let actual = Network::try_from_weights(&topology(), vec![0.5; 10]);
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::NotEnoughWeights {
+                    layer: 2,
+                    neuron: 1
+                }
+            );
+        }
+
+        #[test]
+        fn rejects_too_many_weights() {
+            let actual = Network::try_from_weights(&topology(), vec![0.5; 15]);
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::TooManyWeights { expected: 12 }
+            );
+        }
+
+        #[test]
+        fn rejects_unbounded_weights() {
+            let actual = Network::try_from_weights(&topology(), std::iter::repeat(0.5));
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::TooManyWeights { expected: 12 }
+            );
+        }
+    }
+
     mod from_weights {
         use super::*;
 
         #[test]
         fn test() {
-            let layers = &[LayerTopology { neurons: 3 }, LayerTopology { neurons: 2 }];
+            let layers = &[
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Softsign,
+                    kind: LayerKind::Dense,
+                },
+            ];
             let weights = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
 
-            let actual: Vec<_> = Network::from_weights(layers, weights.clone())
-                .weights()
-                .collect();
+            let network = Network::from_weights(layers, weights.clone());
+            let actual: Vec<_> = network.weights().collect();
 
             approx::assert_relative_eq!(actual.as_slice(), weights.as_slice());
+            assert_eq!(network.layers[0].activation, Activation::Softsign);
         }
     }
 
@@ -131,11 +747,19 @@ mod tests {
         #[test]
         fn test() {
             let layers = (
-                Layer::new(vec![
-                    Neuron::new(0.0, vec![-0.5, -0.4, -0.3]),
-                    Neuron::new(0.0, vec![-0.2, -0.1, 0.0]),
-                ]),
-                Layer::new(vec![Neuron::new(0.0, vec![-0.5, 0.5])]),
+                Layer::new(
+                    vec![
+                        Neuron::new(0.0, vec![-0.5, -0.4, -0.3]),
+                        Neuron::new(0.0, vec![-0.2, -0.1, 0.0]),
+                    ],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+                Layer::new(
+                    vec![Neuron::new(0.0, vec![-0.5, 0.5])],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
             );
             let network = Network::new(vec![layers.0.clone(), layers.1.clone()]);
 
@@ -144,6 +768,485 @@ mod tests {
 
             approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
         }
+
+        #[test]
+        fn can_return_negative_outputs() {
+            let network = Network::new(vec![
+                Layer::new(
+                    vec![Neuron::new(0.0, vec![1.0])],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+                Layer::new(
+                    vec![Neuron::new(-1.0, vec![0.5])],
+                    Activation::Tanh,
+                    LayerKind::Dense,
+                ),
+            ]);
+
+            let actual = network.propagate(vec![1.0]);
+
+            approx::assert_relative_eq!(actual.as_slice(), [(-0.5f32).tanh()].as_slice());
+        }
+    }
+
+    mod activations {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let layers = (
+                Layer::new(
+                    vec![
+                        Neuron::new(0.0, vec![-0.5, -0.4, -0.3]),
+                        Neuron::new(0.1, vec![0.2, 0.1, 0.0]),
+                    ],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+                Layer::new(
+                    vec![Neuron::new(0.0, vec![-0.5, 0.5])],
+                    Activation::Tanh,
+                    LayerKind::Dense,
+                ),
+            );
+            let network = Network::new(vec![layers.0.clone(), layers.1.clone()]);
+
+            let actual = network.activations(vec![0.5, 0.6, 0.7]);
+            let hidden = layers.0.propagate(vec![0.5, 0.6, 0.7]);
+            let output = layers.1.propagate(hidden.clone());
+
+            assert_eq!(actual.len(), 3);
+            assert_eq!(actual[0], vec![0.5, 0.6, 0.7]);
+            approx::assert_relative_eq!(actual[1].as_slice(), hidden.as_slice());
+            approx::assert_relative_eq!(actual[2].as_slice(), output.as_slice());
+            assert_eq!(actual[2], network.propagate(vec![0.5, 0.6, 0.7]));
+        }
+    }
+
+    mod neuron {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let network = Network::new(vec![
+                Layer::new(
+                    vec![
+                        Neuron::new(0.1, vec![0.2, 0.3]),
+                        Neuron::new(0.4, vec![0.5, 0.6]),
+                    ],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+                Layer::new(
+                    vec![Neuron::new(0.7, vec![0.8, 0.9])],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+            ]);
+
+            assert_eq!(network.neuron(1, 1), Some((0.4, [0.5, 0.6].as_slice())));
+            assert_eq!(network.neuron(2, 0), Some((0.7, [0.8, 0.9].as_slice())));
+            assert_eq!(network.neuron(0, 0), None);
+            assert_eq!(network.neuron(1, 2), None);
+            assert_eq!(network.neuron(3, 0), None);
+        }
+    }
+
+    mod try_propagate {
+        use super::*;
+
+        #[test]
+        fn rejects_mismatched_inputs() {
+            let network = Network::new(vec![
+                Layer::new(
+                    vec![Neuron::new(0.0, vec![0.5, 0.5])],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+                Layer::new(
+                    vec![Neuron::new(0.0, vec![0.5])],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+            ]);
+
+            assert_eq!(
+                network.try_propagate(vec![1.0, 2.0, 3.0]).unwrap_err(),
+                NetworkError::InputsMismatch {
+                    layer: 1,
+                    expected: 2,
+                    actual: 3
+                }
+            );
+
+            assert_eq!(
+                network.try_propagate(vec![1.0, 2.0]).unwrap(),
+                network.propagate(vec![1.0, 2.0])
+            );
+        }
+    }
+
+    mod step {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        fn network() -> Network {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            Network::random(
+                &mut rng,
+                &[
+                    LayerTopology {
+                        neurons: 3,
+                        activation: Activation::Relu,
+                        kind: LayerKind::Dense,
+                    },
+                    LayerTopology {
+                        neurons: 4,
+                        activation: Activation::Tanh,
+                        kind: LayerKind::Gru,
+                    },
+                    LayerTopology {
+                        neurons: 2,
+                        activation: Activation::Tanh,
+                        kind: LayerKind::Rnn,
+                    },
+                    LayerTopology {
+                        neurons: 1,
+                        activation: Activation::Tanh,
+                        kind: LayerKind::Dense,
+                    },
+                ],
+            )
+        }
+
+        #[test]
+        fn remembers_previous_steps() {
+            let network = network();
+            let mut state = network.state();
+
+            assert!(network.is_recurrent());
+            assert_eq!(state.values().count(), 4 + 2);
+
+            let first = network.step(vec![0.5, -0.5, 1.0], &mut state);
+            let second = network.step(vec![0.5, -0.5, 1.0], &mut state);
+
+            assert_eq!(first, network.propagate(vec![0.5, -0.5, 1.0]));
+            assert_ne!(first, second);
+
+            state.reset();
+
+            assert_eq!(network.step(vec![0.5, -0.5, 1.0], &mut state), first);
+        }
+
+        #[test]
+        fn survives_weights_round_trip() {
+            let network = network();
+            let restored = Network::from_weights(&network.topology(), network.weights());
+
+            let (mut state, mut restored_state) = (network.state(), restored.state());
+
+            for inputs in [[0.5, -0.5, 1.0], [0.0, 0.0, 0.0], [1.0, 0.25, -1.0]] {
+                assert_eq!(
+                    network.step(inputs.to_vec(), &mut state),
+                    restored.step(inputs.to_vec(), &mut restored_state),
+                );
+            }
+
+            assert_eq!(
+                network.weights().count(),
+                3 * 4 * (1 + 3 + 4) + 2 * (1 + 4 + 2) + (1 + 2)
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "backpropagation through recurrent layers is not supported")]
+        fn rejects_backpropagation() {
+            network().gradients(&[0.5, -0.5, 1.0], &[1.0], Loss::MeanSquaredError);
+        }
+    }
+
+    mod gradients {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let network = Network::new(vec![
+                Layer::new(
+                    vec![Neuron::new(0.5, vec![1.0, -1.0])],
+                    Activation::Tanh,
+                    LayerKind::Dense,
+                ),
+                Layer::new(
+                    vec![Neuron::new(-0.5, vec![2.0])],
+                    Activation::Identity,
+                    LayerKind::Dense,
+                ),
+            ]);
+
+            let inputs = [0.3, 0.1];
+            let targets = [1.0];
+            let (actual_loss, actual) =
+                network.gradients(&inputs, &targets, Loss::MeanSquaredError);
+
+            // Hidden neuron: h = tanh(0.5 + 0.3 - 0.1) = tanh(0.7)
+            // Output neuron: y = -0.5 + 2h
+            let h = 0.7f32.tanh();
+            let y = -0.5 + 2.0 * h;
+            let dy = 2.0 * (y - 1.0);
+            let dh = dy * 2.0 * (1.0 - h * h);
+
+            let expected = [dh, dh * 0.3, dh * 0.1, dy, dy * h];
+
+            approx::assert_relative_eq!(actual_loss, (y - 1.0).powi(2));
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(), epsilon = 1e-6);
+        }
+
+        #[test]
+        fn supports_all_feed_forward_kinds() {
+            use rand::SeedableRng;
+            use rand_chacha::ChaCha8Rng;
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let topology = [
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::LayerNorm,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dropout { rate: 0.5 },
+                },
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Identity,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Softmax,
+                },
+            ];
+
+            let mut weights: Vec<_> = Network::random(&mut rng, &topology).weights().collect();
+
+            // Perturb layer norm's parameters, so that they are not all
+            // the same
+            for weight in &mut weights[16..24] {
+                *weight += rng.gen_range(-0.5..=0.5);
+            }
+
+            let network = Network::from_weights(&topology, weights);
+            let inputs = [0.3, -0.2, 0.8];
+            let targets = [0.2, 0.7, 0.1];
+            let (_, actual) = network.gradients(&inputs, &targets, Loss::MeanSquaredError);
+
+            assert_eq!(actual.len(), 4 * 4 + 2 * 4 + 3 * 5);
+
+            crate::assert_gradients!(network, inputs, targets, Loss::MeanSquaredError);
+        }
+    }
+
+    mod train_batch {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        fn topology() -> [LayerTopology; 3] {
+            [
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 4,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 1,
+                    activation: Activation::Sigmoid,
+                    kind: LayerKind::Dense,
+                },
+            ]
+        }
+
+        fn xor() -> Vec<(Vec<f32>, Vec<f32>)> {
+            vec![
+                (vec![0.0, 0.0], vec![0.0]),
+                (vec![0.0, 1.0], vec![1.0]),
+                (vec![1.0, 0.0], vec![1.0]),
+                (vec![1.0, 1.0], vec![0.0]),
+            ]
+        }
+
+        fn train(loss: Loss, optimizer: &mut dyn Optimizer, epochs: usize) -> (f32, f32) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut network = Network::random(&mut rng, &topology());
+
+            let initial_loss = network.train_batch(&xor(), loss, optimizer);
+
+            let final_loss = (0..epochs)
+                .map(|_| network.train_batch(&xor(), loss, optimizer))
+                .last()
+                .unwrap();
+
+            (initial_loss, final_loss)
+        }
+
+        #[test]
+        fn sgd() {
+            let (initial_loss, final_loss) =
+                train(Loss::MeanSquaredError, &mut Sgd::new(0.5), 2000);
+
+            assert!(final_loss < initial_loss / 10.0);
+        }
+
+        #[test]
+        fn momentum() {
+            let (initial_loss, final_loss) =
+                train(Loss::CrossEntropy, &mut Momentum::new(0.1, 0.9), 1000);
+
+            assert!(final_loss < initial_loss / 10.0);
+        }
+
+        #[test]
+        fn adam() {
+            let (initial_loss, final_loss) = train(Loss::CrossEntropy, &mut Adam::new(0.05), 500);
+
+            assert!(final_loss < initial_loss / 10.0);
+        }
+
+        #[test]
+        fn supports_softmax_and_dropout() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let topology = [
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 8,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 8,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dropout { rate: 0.1 },
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Identity,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Softmax,
+                },
+            ];
+
+            let xor: Vec<_> = xor()
+                .into_iter()
+                .map(|(inputs, targets)| (inputs, vec![targets[0], 1.0 - targets[0]]))
+                .collect();
+
+            let mut network = Network::random(&mut rng, &topology);
+            let mut optimizer = Adam::new(0.02);
+
+            let loss = |network: &Network| -> f32 {
+                xor.iter()
+                    .map(|(inputs, targets)| {
+                        network.gradients(inputs, targets, Loss::MeanSquaredError).0
+                    })
+                    .sum()
+            };
+
+            let initial_loss = loss(&network);
+
+            for _ in 0..500 {
+                network.train_batch_with_dropout(
+                    &mut rng,
+                    &xor,
+                    Loss::MeanSquaredError,
+                    &mut optimizer,
+                );
+            }
+
+            assert!(loss(&network) < initial_loss / 10.0);
+        }
+
+        #[test]
+        fn keeps_weights_layout() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut network = Network::random(&mut rng, &topology());
+            let before: Vec<_> = network.weights().collect();
+
+            let (_, gradients) = network.gradients(&[1.0, 0.0], &[1.0], Loss::MeanSquaredError);
+            network.train_batch(
+                &[(vec![1.0, 0.0], vec![1.0])],
+                Loss::MeanSquaredError,
+                &mut Sgd::new(1.0),
+            );
+
+            let expected: Vec<_> = before.iter().zip(&gradients).map(|(w, g)| w - g).collect();
+            let actual: Vec<_> = network.weights().collect();
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
+
+    mod topology {
+        use super::*;
+        use rand::SeedableRng;
+        use rand_chacha::ChaCha8Rng;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            let topology = [
+                LayerTopology {
+                    neurons: 3,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Sigmoid,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 1,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Dense,
+                },
+            ];
+
+            let network = Network::random(&mut rng, &topology);
+
+            assert_eq!(network.topology(), topology);
+        }
     }
 
     mod weights {
@@ -152,8 +1255,16 @@ mod tests {
         #[test]
         fn test() {
             let network = Network::new(vec![
-                Layer::new(vec![Neuron::new(0.1, vec![0.2, 0.3, 0.4])]),
-                Layer::new(vec![Neuron::new(0.5, vec![0.6, 0.7, 0.8])]),
+                Layer::new(
+                    vec![Neuron::new(0.1, vec![0.2, 0.3, 0.4])],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
+                Layer::new(
+                    vec![Neuron::new(0.5, vec![0.6, 0.7, 0.8])],
+                    Activation::Relu,
+                    LayerKind::Dense,
+                ),
             ]);
 
             let actual: Vec<_> = network.weights().collect();
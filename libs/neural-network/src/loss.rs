@@ -0,0 +1,110 @@
+/// Keeps `CrossEntropy` away from `ln(0)`.
+const CROSS_ENTROPY_EPSILON: f32 = 1e-7;
+
+/// Measures how far network's outputs are from the expected ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Loss {
+    /// mean((output - target)^2)
+    MeanSquaredError,
+
+    /// Binary cross-entropy, averaged over all outputs:
+    ///
+    /// -mean(target * ln(output) + (1 - target) * ln(1 - output))
+    ///
+    /// Expects outputs in range (0, 1) - i.e. the output layer should
+    /// use `Activation::Sigmoid`.
+    CrossEntropy,
+}
+
+impl Loss {
+    pub fn compute(self, outputs: &[f32], targets: &[f32]) -> f32 {
+        assert_eq!(outputs.len(), targets.len());
+
+        let sum: f32 = outputs
+            .iter()
+            .zip(targets)
+            .map(|(&output, &target)| match self {
+                Self::MeanSquaredError => (output - target).powi(2),
+                Self::CrossEntropy => {
+                    let output = output.clamp(CROSS_ENTROPY_EPSILON, 1.0 - CROSS_ENTROPY_EPSILON);
+
+                    -(target * output.ln() + (1.0 - target) * (1.0 - output).ln())
+                }
+            })
+            .sum();
+
+        sum / (outputs.len() as f32)
+    }
+
+    /// Returns derivative of `compute()` with respect to each output.
+    pub fn gradient(self, outputs: &[f32], targets: &[f32]) -> Vec<f32> {
+        assert_eq!(outputs.len(), targets.len());
+
+        let len = outputs.len() as f32;
+
+        outputs
+            .iter()
+            .zip(targets)
+            .map(|(&output, &target)| match self {
+                Self::MeanSquaredError => 2.0 * (output - target) / len,
+                Self::CrossEntropy => {
+                    let output = output.clamp(CROSS_ENTROPY_EPSILON, 1.0 - CROSS_ENTROPY_EPSILON);
+
+                    (output - target) / (output * (1.0 - output)) / len
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod compute {
+        use super::*;
+
+        #[test]
+        fn mean_squared_error() {
+            let actual = Loss::MeanSquaredError.compute(&[0.5, -1.0], &[1.0, 1.0]);
+            let expected = (0.25 + 4.0) / 2.0;
+
+            approx::assert_relative_eq!(actual, expected);
+        }
+
+        #[test]
+        fn cross_entropy() {
+            let actual = Loss::CrossEntropy.compute(&[0.8, 0.4], &[1.0, 0.0]);
+            let expected = -(0.8f32.ln() + 0.6f32.ln()) / 2.0;
+
+            approx::assert_relative_eq!(actual, expected);
+        }
+
+        #[test]
+        fn cross_entropy_does_not_overflow() {
+            let actual = Loss::CrossEntropy.compute(&[0.0, 1.0], &[1.0, 0.0]);
+
+            assert!(actual.is_finite());
+        }
+    }
+
+    mod gradient {
+        use super::*;
+
+        #[test]
+        fn mean_squared_error() {
+            let actual = Loss::MeanSquaredError.gradient(&[0.5, -1.0], &[1.0, 1.0]);
+            let expected = [-0.5, -2.0];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn cross_entropy() {
+            let actual = Loss::CrossEntropy.gradient(&[0.8, 0.4], &[1.0, 0.0]);
+            let expected = [-1.0 / 0.8 / 2.0, 1.0 / 0.6 / 2.0];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice(), epsilon = 1e-6);
+        }
+    }
+}
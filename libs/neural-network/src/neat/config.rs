@@ -0,0 +1,69 @@
+use super::*;
+
+/// Knobs of the NEAT algorithm; defaults follow the original paper
+/// where possible.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// Activation of neurons created by `Genome::add_node()`.
+    pub hidden_activation: Activation,
+
+    /// Activation of output neurons.
+    pub output_activation: Activation,
+
+    /// Probability of mutating weights (and biases) of a genome.
+    pub weight_mutation_rate: f32,
+
+    /// Probability of replacing a mutated weight with a random one,
+    /// instead of nudging it by at most `weight_perturbation`.
+    pub weight_replace_rate: f32,
+    pub weight_perturbation: f32,
+
+    /// Probability of adding a new connection to a genome.
+    pub add_connection_rate: f32,
+
+    /// Probability of splitting an existing connection with a new
+    /// neuron.
+    pub add_node_rate: f32,
+
+    /// Probability of a child being created by crossover (as opposed to
+    /// by cloning a single parent).
+    pub crossover_rate: f32,
+
+    /// Weights of excess genes, disjoint genes and the average weight
+    /// difference in `Genome::distance()` (c1, c2 and c3 in the paper).
+    pub excess_coeff: f32,
+    pub disjoint_coeff: f32,
+    pub weight_coeff: f32,
+
+    /// Maximum distance between a genome and a species' representative
+    /// for the genome to belong to that species.
+    pub compatibility_threshold: f32,
+
+    /// Fraction of each species (the fittest ones) allowed to reproduce.
+    pub survival_threshold: f32,
+
+    /// Number of generations a species can go without improving its
+    /// best fitness before it gets removed.
+    pub stagnation_limit: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            hidden_activation: Activation::Tanh,
+            output_activation: Activation::Tanh,
+            weight_mutation_rate: 0.8,
+            weight_replace_rate: 0.1,
+            weight_perturbation: 0.5,
+            add_connection_rate: 0.05,
+            add_node_rate: 0.03,
+            crossover_rate: 0.75,
+            excess_coeff: 1.0,
+            disjoint_coeff: 1.0,
+            weight_coeff: 0.4,
+            compatibility_threshold: 3.0,
+            survival_threshold: 0.2,
+            stagnation_limit: 15,
+        }
+    }
+}
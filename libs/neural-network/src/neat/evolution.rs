@@ -0,0 +1,368 @@
+use super::*;
+use rand::seq::SliceRandom;
+
+/// Species with fewer members than this don't get their champion
+/// copied unchanged into the next generation.
+const ELITISM_MIN_SPECIES_SIZE: usize = 5;
+
+/// The NEAT algorithm; keeps track of innovations and species across
+/// generations.
+///
+/// Similarly to `GeneticAlgorithm` from `lib-genetic-algorithm`, it
+/// doesn't evaluate genomes on its own - it's given genomes together
+/// with their fitness and returns the next generation.
+#[derive(Clone, Debug)]
+pub struct Neat {
+    config: Config,
+    inputs: usize,
+    outputs: usize,
+    innovations: Innovations,
+    species: Vec<Species>,
+    next_species_id: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Species {
+    id: usize,
+
+    /// Genome new candidates are compared against; it's the species'
+    /// champion from the previous generation.
+    representative: Genome,
+
+    /// Best fitness this species has ever achieved.
+    best_fitness: f32,
+
+    /// Number of generations since `best_fitness` has improved.
+    stagnation: usize,
+
+    /// Number of genomes that belonged to this species during the
+    /// last `Neat::evolve()`.
+    size: usize,
+}
+
+impl Neat {
+    pub fn new(config: Config, inputs: usize, outputs: usize) -> Self {
+        Self {
+            config,
+            inputs,
+            outputs,
+            innovations: Innovations::new(inputs, outputs),
+            species: Vec::new(),
+            next_species_id: 0,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Creates a minimal genome (see `Genome::minimal()`) that fits
+    /// this algorithm.
+    pub fn genome(&self, rng: &mut dyn RngCore) -> Genome {
+        Genome::minimal(rng, self.inputs, self.outputs, &self.config)
+    }
+
+    /// Species found during the last `Self::evolve()`.
+    pub fn species(&self) -> &[Species] {
+        &self.species
+    }
+
+    /// Creates the next generation out of given `(genome, fitness)`
+    /// pairs; the new generation has the same size as the old one.
+    ///
+    /// Fitness is expected to be non-negative.
+    pub fn evolve(&mut self, rng: &mut dyn RngCore, population: &[(&Genome, f32)]) -> Vec<Genome> {
+        assert!(!population.is_empty());
+
+        // Step 1: Assign each genome to a species
+        let mut members = self.speciate(population);
+
+        // Step 2: Sort each species from the fittest and update its
+        // stagnation counter
+        for (species, members) in self.species.iter_mut().zip(&mut members) {
+            members.sort_by(|&a, &b| population[b].1.total_cmp(&population[a].1));
+
+            let best_fitness = population[members[0]].1;
+
+            if best_fitness > species.best_fitness {
+                species.best_fitness = best_fitness;
+                species.stagnation = 0;
+            } else {
+                species.stagnation += 1;
+            }
+
+            species.size = members.len();
+            species.representative = population[members[0]].0.clone();
+        }
+
+        self.remove_stagnant_species(&mut members);
+
+        // Step 3: Decide how many children each species gets
+        let offspring = self.offspring(population, &members);
+
+        // Step 4: Reproduce
+        let mut children = Vec::with_capacity(population.len());
+
+        for (members, offspring) in members.iter().zip(offspring) {
+            if offspring == 0 {
+                continue;
+            }
+
+            let mut offspring = offspring;
+
+            if members.len() >= ELITISM_MIN_SPECIES_SIZE {
+                children.push(population[members[0]].0.clone());
+                offspring -= 1;
+            }
+
+            let parents_count =
+                ((members.len() as f32 * self.config.survival_threshold).ceil() as usize).max(1);
+
+            let parents = &members[..parents_count.min(members.len())];
+
+            for _ in 0..offspring {
+                let parent_a = population[*parents.choose(rng).unwrap()];
+
+                let mut child =
+                    if parents.len() > 1 && rng.gen_bool(self.config.crossover_rate as _) {
+                        let parent_b = population[*parents.choose(rng).unwrap()];
+
+                        Genome::crossover(rng, parent_a.0, parent_a.1, parent_b.0, parent_b.1)
+                    } else {
+                        parent_a.0.clone()
+                    };
+
+                child.mutate(rng, &self.config, &mut self.innovations);
+                children.push(child);
+            }
+        }
+
+        children
+    }
+
+    /// Returns indices of genomes belonging to each species (following
+    /// the order of `self.species`); species that got no members are
+    /// removed.
+    fn speciate(&mut self, population: &[(&Genome, f32)]) -> Vec<Vec<usize>> {
+        let mut members = vec![Vec::new(); self.species.len()];
+
+        for (index, (genome, _)) in population.iter().enumerate() {
+            let species = self.species.iter().position(|species| {
+                species.representative.distance(genome, &self.config)
+                    < self.config.compatibility_threshold
+            });
+
+            match species {
+                Some(species) => members[species].push(index),
+
+                None => {
+                    self.species.push(Species {
+                        id: self.next_species_id,
+                        representative: (*genome).clone(),
+                        best_fitness: f32::NEG_INFINITY,
+                        stagnation: 0,
+                        size: 0,
+                    });
+
+                    self.next_species_id += 1;
+                    members.push(vec![index]);
+                }
+            }
+        }
+
+        let mut species_members = members.iter().map(|members| !members.is_empty());
+        self.species.retain(|_| species_members.next().unwrap());
+        members.retain(|members| !members.is_empty());
+
+        members
+    }
+
+    /// Removes species that haven't improved for too long - except for
+    /// the best one, which always survives.
+    fn remove_stagnant_species(&mut self, members: &mut Vec<Vec<usize>>) {
+        let best = self
+            .species
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.best_fitness.total_cmp(&b.best_fitness))
+            .map(|(index, _)| index);
+
+        let keep: Vec<_> = self
+            .species
+            .iter()
+            .enumerate()
+            .map(|(index, species)| {
+                Some(index) == best || species.stagnation <= self.config.stagnation_limit
+            })
+            .collect();
+
+        let mut keep_species = keep.iter();
+        self.species.retain(|_| *keep_species.next().unwrap());
+
+        let mut keep_members = keep.iter();
+        members.retain(|_| *keep_members.next().unwrap());
+    }
+
+    /// Splits `population.len()` children between species, proportionally
+    /// to their average fitness (that is: with fitness shared among
+    /// members of each species).
+    ///
+    /// When no genome has any fitness, species get children according
+    /// to their sizes.
+    fn offspring(&self, population: &[(&Genome, f32)], members: &[Vec<usize>]) -> Vec<usize> {
+        let shares: Vec<f32> = members
+            .iter()
+            .map(|members| {
+                let total: f32 = members
+                    .iter()
+                    .map(|&index| population[index].1.max(0.0))
+                    .sum();
+
+                total / members.len() as f32
+            })
+            .collect();
+
+        let shares = if shares.iter().sum::<f32>() > 0.0 {
+            shares
+        } else {
+            members.iter().map(|members| members.len() as f32).collect()
+        };
+
+        apportion(&shares, population.len())
+    }
+}
+
+impl Species {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn best_fitness(&self) -> f32 {
+        self.best_fitness
+    }
+
+    pub fn stagnation(&self) -> usize {
+        self.stagnation
+    }
+}
+
+/// Splits `total` into integer parts proportional to `shares`, using
+/// the largest remainder method (so the parts always add up to
+/// `total`).
+fn apportion(shares: &[f32], total: usize) -> Vec<usize> {
+    let sum: f32 = shares.iter().sum();
+    let exact: Vec<_> = shares
+        .iter()
+        .map(|share| share / sum * total as f32)
+        .collect();
+    let mut parts: Vec<_> = exact.iter().map(|exact| exact.floor() as usize).collect();
+
+    let mut by_remainder: Vec<_> = (0..shares.len()).collect();
+
+    by_remainder.sort_by(|&a, &b| {
+        let remainder = |index: usize| exact[index] - exact[index].floor();
+        remainder(b).total_cmp(&remainder(a))
+    });
+
+    let missing = total - parts.iter().sum::<usize>();
+
+    for &index in by_remainder.iter().cycle().take(missing) {
+        parts[index] += 1;
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    mod apportion {
+        use super::*;
+
+        #[test]
+        fn test() {
+            assert_eq!(apportion(&[1.0, 1.0, 2.0], 8), vec![2, 2, 4]);
+            assert_eq!(apportion(&[1.0, 1.0, 1.0], 10), vec![4, 3, 3]);
+            assert_eq!(apportion(&[0.25, 0.0, 0.75], 5), vec![1, 0, 4]);
+        }
+    }
+
+    mod evolve {
+        use super::*;
+
+        /// Evolves genomes towards computing XOR, returning the best
+        /// fitness seen in each generation.
+        fn run(generations: usize) -> (Neat, Vec<f32>) {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut neat = Neat::new(Config::default(), 2, 1);
+            let mut genomes: Vec<_> = (0..50).map(|_| neat.genome(&mut rng)).collect();
+            let mut best = Vec::new();
+
+            for _ in 0..generations {
+                let population: Vec<_> = genomes
+                    .iter()
+                    .map(|genome| {
+                        let network = genome.network();
+
+                        let error: f32 = [
+                            ([0.0, 0.0], 0.0),
+                            ([0.0, 1.0], 1.0),
+                            ([1.0, 0.0], 1.0),
+                            ([1.0, 1.0], 0.0),
+                        ]
+                        .iter()
+                        .map(|(inputs, target)| {
+                            (network.propagate(inputs.to_vec())[0] - target).abs()
+                        })
+                        .sum();
+
+                        (genome, 4.0 - error)
+                    })
+                    .collect();
+
+                best.push(
+                    population
+                        .iter()
+                        .map(|(_, fitness)| *fitness)
+                        .fold(f32::MIN, f32::max),
+                );
+                genomes = neat.evolve(&mut rng, &population);
+
+                assert_eq!(genomes.len(), 50);
+            }
+
+            (neat, best)
+        }
+
+        #[test]
+        fn improves_fitness() {
+            let (neat, best) = run(30);
+
+            assert!(best[29] > best[0]);
+            assert!(!neat.species().is_empty());
+            assert!(neat.species().iter().all(|species| species.size() > 0));
+        }
+
+        #[test]
+        fn is_deterministic() {
+            assert_eq!(run(10).1, run(10).1);
+        }
+
+        #[test]
+        fn survives_zero_fitness() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut neat = Neat::new(Config::default(), 3, 2);
+            let genomes: Vec<_> = (0..20).map(|_| neat.genome(&mut rng)).collect();
+            let population: Vec<_> = genomes.iter().map(|genome| (genome, 0.0)).collect();
+
+            assert_eq!(neat.evolve(&mut rng, &population).len(), 20);
+        }
+    }
+}
@@ -0,0 +1,666 @@
+use super::*;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    Input,
+    Hidden,
+    Output,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeGene {
+    pub id: usize,
+    pub kind: NodeKind,
+
+    /// Ignored for inputs, whose value is given from the outside.
+    pub bias: f32,
+    pub activation: Activation,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConnectionGene {
+    pub innovation: usize,
+    pub from: usize,
+    pub to: usize,
+    pub weight: f32,
+
+    /// Disabled connections don't take part in propagation, but they
+    /// are still inherited (and can get re-enabled by crossover).
+    pub enabled: bool,
+}
+
+/// Node and connection genes of a single NEAT network.
+///
+/// Nodes are sorted by their ids (inputs go first, then outputs, then
+/// hidden nodes) and connections are sorted by their innovation
+/// numbers.
+///
+/// Genomes grow only feed-forward connections, so that the resulting
+/// `Network` can be propagated in a single pass.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Genome {
+    inputs: usize,
+    outputs: usize,
+    nodes: Vec<NodeGene>,
+    connections: Vec<ConnectionGene>,
+}
+
+impl Genome {
+    /// Creates a genome with every input connected to every output and
+    /// no hidden nodes, with random weights and biases.
+    ///
+    /// Innovation numbers follow `Innovations::new()`.
+    pub fn minimal(rng: &mut dyn RngCore, inputs: usize, outputs: usize, config: &Config) -> Self {
+        assert!(inputs > 0);
+        assert!(outputs > 0);
+
+        let inputs_nodes = (0..inputs).map(|id| NodeGene {
+            id,
+            kind: NodeKind::Input,
+            bias: 0.0,
+            activation: Activation::Identity,
+        });
+
+        let output_nodes: Vec<_> = (0..outputs)
+            .map(|output| NodeGene {
+                id: inputs + output,
+                kind: NodeKind::Output,
+                bias: rng.gen_range(-1.0..=1.0),
+                activation: config.output_activation,
+            })
+            .collect();
+
+        let nodes = inputs_nodes.chain(output_nodes).collect();
+
+        let connections = (0..inputs)
+            .flat_map(|input| (0..outputs).map(move |output| (input, output)))
+            .map(|(input, output)| ConnectionGene {
+                innovation: input * outputs + output,
+                from: input,
+                to: inputs + output,
+                weight: rng.gen_range(-1.0..=1.0),
+                enabled: true,
+            })
+            .collect();
+
+        Self {
+            inputs,
+            outputs,
+            nodes,
+            connections,
+        }
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.outputs
+    }
+
+    pub fn nodes(&self) -> &[NodeGene] {
+        &self.nodes
+    }
+
+    pub fn connections(&self) -> &[ConnectionGene] {
+        &self.connections
+    }
+
+    /// Compiles this genome into a network.
+    pub fn network(&self) -> Network {
+        Network::from_genome(self)
+    }
+
+    /// Applies the weight and structural mutations, each with the
+    /// probability given in `config`.
+    pub fn mutate(
+        &mut self,
+        rng: &mut dyn RngCore,
+        config: &Config,
+        innovations: &mut Innovations,
+    ) {
+        if rng.gen_bool(config.weight_mutation_rate as _) {
+            self.mutate_weights(rng, config);
+        }
+
+        if rng.gen_bool(config.add_connection_rate as _) {
+            self.add_connection(rng, innovations);
+        }
+
+        if rng.gen_bool(config.add_node_rate as _) {
+            self.add_node(rng, config, innovations);
+        }
+    }
+
+    /// Nudges (or, rarely, replaces) every weight and bias.
+    pub fn mutate_weights(&mut self, rng: &mut dyn RngCore, config: &Config) {
+        let biases = self
+            .nodes
+            .iter_mut()
+            .filter(|node| node.kind != NodeKind::Input)
+            .map(|node| &mut node.bias);
+
+        let weights = self
+            .connections
+            .iter_mut()
+            .map(|connection| &mut connection.weight);
+
+        for value in biases.chain(weights) {
+            if rng.gen_bool(config.weight_replace_rate as _) {
+                *value = rng.gen_range(-1.0..=1.0);
+            } else {
+                *value += rng.gen_range(-config.weight_perturbation..=config.weight_perturbation);
+            }
+        }
+    }
+
+    /// Connects two so-far unconnected nodes with a random weight.
+    ///
+    /// Returns `false` if there's no pair of nodes that could be
+    /// connected without creating a cycle.
+    pub fn add_connection(&mut self, rng: &mut dyn RngCore, innovations: &mut Innovations) -> bool {
+        let candidates: Vec<_> = self
+            .nodes
+            .iter()
+            .filter(|from| from.kind != NodeKind::Output)
+            .flat_map(|from| {
+                self.nodes
+                    .iter()
+                    .filter(|to| to.kind != NodeKind::Input)
+                    .map(move |to| (from.id, to.id))
+            })
+            .filter(|&(from, to)| {
+                from != to
+                    && !self
+                        .connections
+                        .iter()
+                        .any(|connection| connection.from == from && connection.to == to)
+                    && !self.reaches(to, from)
+            })
+            .collect();
+
+        let (from, to) = match candidates.choose(rng) {
+            Some(&candidate) => candidate,
+            None => return false,
+        };
+
+        self.push_connection(ConnectionGene {
+            innovation: innovations.connection(from, to),
+            from,
+            to,
+            weight: rng.gen_range(-1.0..=1.0),
+            enabled: true,
+        });
+
+        true
+    }
+
+    /// Splits a random enabled connection with a new hidden node.
+    ///
+    /// The old connection gets disabled and replaced with two new ones:
+    /// one leading into the new node (with weight of 1.0) and one
+    /// leading out of it (with the old connection's weight), so that
+    /// the network's behavior changes as little as possible.
+    ///
+    /// Returns `false` if there's no connection that could be split.
+    pub fn add_node(
+        &mut self,
+        rng: &mut dyn RngCore,
+        config: &Config,
+        innovations: &mut Innovations,
+    ) -> bool {
+        let candidates: Vec<_> = self
+            .connections
+            .iter()
+            .enumerate()
+            .filter(|(_, connection)| connection.enabled)
+            .map(|(index, _)| index)
+            .collect();
+
+        let index = match candidates.choose(rng) {
+            Some(&index) => index,
+            None => return false,
+        };
+
+        let ConnectionGene {
+            innovation,
+            from,
+            to,
+            weight,
+            ..
+        } = self.connections[index];
+
+        let id = innovations.split(innovation);
+
+        // This genome has already split this connection once (and the
+        // connection got re-enabled since then)
+        if self.node(id).is_some() {
+            return false;
+        }
+
+        self.connections[index].enabled = false;
+
+        let position = self.nodes.partition_point(|node| node.id < id);
+
+        self.nodes.insert(
+            position,
+            NodeGene {
+                id,
+                kind: NodeKind::Hidden,
+                bias: 0.0,
+                activation: config.hidden_activation,
+            },
+        );
+
+        self.push_connection(ConnectionGene {
+            innovation: innovations.connection(from, id),
+            from,
+            to: id,
+            weight: 1.0,
+            enabled: true,
+        });
+
+        self.push_connection(ConnectionGene {
+            innovation: innovations.connection(id, to),
+            from: id,
+            to,
+            weight,
+            enabled: true,
+        });
+
+        true
+    }
+
+    /// Creates a child out of two parents, lining up their genes by
+    /// innovation numbers.
+    ///
+    /// Matching genes are inherited randomly from either parent, while
+    /// disjoint and excess genes are inherited only from the fitter one
+    /// (so the child has the same structure as the fitter parent).
+    ///
+    /// A gene disabled in either parent stays disabled with the
+    /// probability of 75%.
+    pub fn crossover(
+        rng: &mut dyn RngCore,
+        parent_a: &Self,
+        fitness_a: f32,
+        parent_b: &Self,
+        fitness_b: f32,
+    ) -> Self {
+        let (fitter, other) = if fitness_a >= fitness_b {
+            (parent_a, parent_b)
+        } else {
+            (parent_b, parent_a)
+        };
+
+        let nodes = fitter
+            .nodes
+            .iter()
+            .map(|node| match other.node(node.id) {
+                Some(other) if rng.gen_bool(0.5) => NodeGene {
+                    bias: other.bias,
+                    ..node.clone()
+                },
+                _ => node.clone(),
+            })
+            .collect();
+
+        let connections = fitter
+            .connections
+            .iter()
+            .map(|connection| match other.connection(connection.innovation) {
+                Some(other) => {
+                    let weight = if rng.gen_bool(0.5) {
+                        connection.weight
+                    } else {
+                        other.weight
+                    };
+
+                    let enabled = if connection.enabled && other.enabled {
+                        true
+                    } else {
+                        !rng.gen_bool(0.75)
+                    };
+
+                    ConnectionGene {
+                        weight,
+                        enabled,
+                        ..connection.clone()
+                    }
+                }
+
+                None => connection.clone(),
+            })
+            .collect();
+
+        Self {
+            inputs: fitter.inputs,
+            outputs: fitter.outputs,
+            nodes,
+            connections,
+        }
+    }
+
+    /// Compatibility distance between two genomes (δ in the paper):
+    ///
+    /// `c1 * excess / n + c2 * disjoint / n + c3 * avg weight difference`
+    ///
+    /// ... where `n` is the number of genes of the larger genome (or 1,
+    /// for genomes smaller than 20 genes).
+    pub fn distance(&self, other: &Self, config: &Config) -> f32 {
+        let (mut a, mut b) = (
+            self.connections.iter().peekable(),
+            other.connections.iter().peekable(),
+        );
+
+        let mut disjoint = 0;
+        let mut excess = 0;
+        let mut matching = 0;
+        let mut weight_difference = 0.0;
+
+        loop {
+            match (a.peek(), b.peek()) {
+                (Some(gene_a), Some(gene_b)) => {
+                    if gene_a.innovation == gene_b.innovation {
+                        matching += 1;
+                        weight_difference += (gene_a.weight - gene_b.weight).abs();
+                        a.next();
+                        b.next();
+                    } else {
+                        disjoint += 1;
+
+                        if gene_a.innovation < gene_b.innovation {
+                            a.next();
+                        } else {
+                            b.next();
+                        }
+                    }
+                }
+
+                (Some(_), None) => {
+                    excess += 1;
+                    a.next();
+                }
+
+                (None, Some(_)) => {
+                    excess += 1;
+                    b.next();
+                }
+
+                (None, None) => break,
+            }
+        }
+
+        let genes = self.connections.len().max(other.connections.len());
+        let n = if genes < 20 { 1.0 } else { genes as f32 };
+
+        let weight_difference = if matching > 0 {
+            weight_difference / matching as f32
+        } else {
+            0.0
+        };
+
+        config.excess_coeff * excess as f32 / n
+            + config.disjoint_coeff * disjoint as f32 / n
+            + config.weight_coeff * weight_difference
+    }
+
+    fn node(&self, id: usize) -> Option<&NodeGene> {
+        self.nodes
+            .binary_search_by_key(&id, |node| node.id)
+            .ok()
+            .map(|index| &self.nodes[index])
+    }
+
+    fn connection(&self, innovation: usize) -> Option<&ConnectionGene> {
+        self.connections
+            .binary_search_by_key(&innovation, |connection| connection.innovation)
+            .ok()
+            .map(|index| &self.connections[index])
+    }
+
+    fn push_connection(&mut self, connection: ConnectionGene) {
+        let position = self
+            .connections
+            .partition_point(|other| other.innovation < connection.innovation);
+
+        self.connections.insert(position, connection);
+    }
+
+    /// Returns whether there's a path (through enabled or disabled
+    /// connections) leading from `from` to `to`.
+    fn reaches(&self, from: usize, to: usize) -> bool {
+        let mut pending = vec![from];
+        let mut visited = vec![from];
+
+        while let Some(node) = pending.pop() {
+            if node == to {
+                return true;
+            }
+
+            for connection in &self.connections {
+                if connection.from == node && !visited.contains(&connection.to) {
+                    visited.push(connection.to);
+                    pending.push(connection.to);
+                }
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn genome() -> Genome {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Genome::minimal(&mut rng, 2, 1, &Config::default())
+    }
+
+    fn innovation_numbers(genome: &Genome) -> Vec<usize> {
+        genome
+            .connections()
+            .iter()
+            .map(|connection| connection.innovation)
+            .collect()
+    }
+
+    mod minimal {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let genome = genome();
+
+            assert_eq!(genome.nodes().len(), 3);
+            assert_eq!(genome.nodes()[2].kind, NodeKind::Output);
+            assert_eq!(genome.nodes()[2].activation, Activation::Tanh);
+
+            let connections: Vec<_> = genome
+                .connections()
+                .iter()
+                .map(|connection| (connection.innovation, connection.from, connection.to))
+                .collect();
+
+            assert_eq!(connections, vec![(0, 0, 2), (1, 1, 2)]);
+        }
+    }
+
+    mod add_node {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut innovations = Innovations::new(2, 1);
+            let mut genome = genome();
+
+            let expected = genome.network().propagate(vec![0.5, -0.5]);
+
+            assert!(genome.add_node(&mut rng, &Config::default(), &mut innovations));
+
+            assert_eq!(genome.nodes().len(), 4);
+            assert_eq!(genome.nodes()[3].id, 3);
+            assert_eq!(genome.nodes()[3].kind, NodeKind::Hidden);
+            assert_eq!(innovation_numbers(&genome), vec![0, 1, 2, 3]);
+
+            let split = genome
+                .connections()
+                .iter()
+                .find(|connection| !connection.enabled)
+                .unwrap();
+
+            assert_eq!(genome.connections()[2].from, split.from);
+            assert_eq!(genome.connections()[2].to, 3);
+            assert_eq!(genome.connections()[3].from, 3);
+            assert_eq!(genome.connections()[3].to, split.to);
+            assert_eq!(genome.connections()[3].weight, split.weight);
+
+            // The network changes, but it's still able to propagate
+            assert_ne!(genome.network().propagate(vec![0.5, -0.5]), expected);
+        }
+
+        #[test]
+        fn reuses_innovations() {
+            let mut innovations = Innovations::new(2, 1);
+            let mut genome_a = genome();
+            let mut genome_b = genome();
+
+            // Same seeds, so both genomes split the same connection
+            genome_a.add_node(
+                &mut ChaCha8Rng::from_seed(Default::default()),
+                &Config::default(),
+                &mut innovations,
+            );
+
+            genome_b.add_node(
+                &mut ChaCha8Rng::from_seed(Default::default()),
+                &Config::default(),
+                &mut innovations,
+            );
+
+            assert_eq!(genome_a, genome_b);
+        }
+    }
+
+    mod add_connection {
+        use super::*;
+
+        #[test]
+        fn never_creates_cycles() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut innovations = Innovations::new(2, 1);
+            let mut genome = genome();
+
+            for _ in 0..5 {
+                genome.add_node(&mut rng, &Config::default(), &mut innovations);
+            }
+
+            while genome.add_connection(&mut rng, &mut innovations) {
+                //
+            }
+
+            for connection in genome.connections() {
+                assert!(!genome.reaches(connection.to, connection.from));
+            }
+
+            // Network is still propagatable
+            assert_eq!(genome.network().propagate(vec![0.5, -0.5]).len(), 1);
+        }
+
+        #[test]
+        fn fails_for_fully_connected_genome() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut innovations = Innovations::new(2, 1);
+            let mut genome = genome();
+
+            assert!(!genome.add_connection(&mut rng, &mut innovations));
+        }
+    }
+
+    mod crossover {
+        use super::*;
+
+        #[test]
+        fn inherits_structure_from_fitter_parent() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut innovations = Innovations::new(2, 1);
+
+            let parent_a = genome();
+            let mut parent_b = genome();
+
+            parent_b.add_node(&mut rng, &Config::default(), &mut innovations);
+
+            let child = Genome::crossover(&mut rng, &parent_a, 1.0, &parent_b, 2.0);
+
+            assert_eq!(innovation_numbers(&child), innovation_numbers(&parent_b));
+            assert_eq!(child.nodes().len(), parent_b.nodes().len());
+
+            let child = Genome::crossover(&mut rng, &parent_a, 2.0, &parent_b, 1.0);
+
+            assert_eq!(innovation_numbers(&child), innovation_numbers(&parent_a));
+        }
+
+        #[test]
+        fn picks_matching_weights_from_both_parents() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let config = Config::default();
+
+            let parent_a = Genome::minimal(&mut rng, 10, 10, &config);
+            let parent_b = Genome::minimal(&mut rng, 10, 10, &config);
+            let child = Genome::crossover(&mut rng, &parent_a, 1.0, &parent_b, 1.0);
+
+            let from_a = child
+                .connections()
+                .iter()
+                .zip(parent_a.connections())
+                .filter(|(child, parent)| child.weight == parent.weight)
+                .count();
+
+            let from_b = child
+                .connections()
+                .iter()
+                .zip(parent_b.connections())
+                .filter(|(child, parent)| child.weight == parent.weight)
+                .count();
+
+            assert_eq!(from_a + from_b, 100);
+            assert!(from_a > 30 && from_b > 30);
+        }
+    }
+
+    mod distance {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut innovations = Innovations::new(2, 1);
+            let config = Config::default();
+
+            let genome_a = genome();
+            let mut genome_b = genome();
+
+            approx::assert_relative_eq!(genome_a.distance(&genome_b, &config), 0.0);
+
+            genome_b.add_node(&mut rng, &config, &mut innovations);
+
+            // Two excess genes (the split connection still matches)
+            approx::assert_relative_eq!(genome_a.distance(&genome_b, &config), 2.0);
+            approx::assert_relative_eq!(genome_b.distance(&genome_a, &config), 2.0);
+
+            genome_b.connections[0].weight += 1.0;
+
+            approx::assert_relative_eq!(genome_a.distance(&genome_b, &config), 2.0 + 0.4 * 0.5);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+/// Historical markings shared by all genomes of a single run.
+///
+/// Whenever a genome grows a connection (or splits one with a new
+/// node) that some other genome has already grown, it gets the same
+/// innovation number (or node id) - that's what allows crossover to
+/// tell which genes of two different genomes correspond to each other.
+#[derive(Clone, Debug)]
+pub struct Innovations {
+    next_node: usize,
+
+    /// (from node, to node) -> innovation number
+    connections: HashMap<(usize, usize), usize>,
+
+    /// Innovation number of split connection -> id of the node that
+    /// split it
+    splits: HashMap<usize, usize>,
+}
+
+impl Innovations {
+    /// Creates markings for genomes with given number of inputs and
+    /// outputs; connections of `Genome::minimal()` are pre-registered.
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        let connections = (0..inputs)
+            .flat_map(|input| (0..outputs).map(move |output| (input, output)))
+            .map(|(input, output)| ((input, inputs + output), input * outputs + output))
+            .collect();
+
+        Self {
+            next_node: inputs + outputs,
+            connections,
+            splits: Default::default(),
+        }
+    }
+
+    /// Returns innovation number of connection between given nodes.
+    pub fn connection(&mut self, from: usize, to: usize) -> usize {
+        let next = self.connections.len();
+
+        *self.connections.entry((from, to)).or_insert(next)
+    }
+
+    /// Returns id of the node splitting connection of given innovation
+    /// number.
+    pub fn split(&mut self, innovation: usize) -> usize {
+        let next_node = &mut self.next_node;
+
+        *self.splits.entry(innovation).or_insert_with(|| {
+            *next_node += 1;
+            *next_node - 1
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let mut innovations = Innovations::new(2, 1);
+
+        assert_eq!(innovations.connection(0, 2), 0);
+        assert_eq!(innovations.connection(1, 2), 1);
+        assert_eq!(innovations.connection(0, 3), 2);
+        assert_eq!(innovations.connection(0, 3), 2);
+        assert_eq!(innovations.connection(3, 2), 3);
+
+        assert_eq!(innovations.split(1), 3);
+        assert_eq!(innovations.split(0), 4);
+        assert_eq!(innovations.split(1), 3);
+    }
+}
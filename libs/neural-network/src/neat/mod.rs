@@ -0,0 +1,30 @@
+//! NEAT - NeuroEvolution of Augmenting Topologies (Stanley & Miikkulainen,
+//! 2002).
+//!
+//! Contrary to `crate::Network`, whose shape is fixed up front and only
+//! its weights get evolved, NEAT genomes start minimal (inputs wired
+//! straight into outputs) and grow new neurons and connections as the
+//! evolution goes:
+//!
+//! - `Genome` is the genotype - a list of node and connection genes,
+//!   where each connection is tagged with an *innovation number*
+//!   (see `Innovations`), which allows to line up genes of genomes of
+//!   different shapes during crossover,
+//!
+//! - `Network` is the phenotype - a genome compiled into something that
+//!   can be propagated,
+//!
+//! - `Neat` drives the evolution - it groups genomes into species, so
+//!   that fresh structural innovations get a chance to mature instead
+//!   of being outcompeted right away.
+
+pub use self::{config::*, evolution::*, genome::*, innovations::*, network::*};
+
+use crate::Activation;
+use rand::{Rng, RngCore};
+
+mod config;
+mod evolution;
+mod genome;
+mod innovations;
+mod network;
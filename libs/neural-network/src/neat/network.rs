@@ -0,0 +1,185 @@
+use super::*;
+
+/// `Genome` compiled into a form that can be propagated.
+///
+/// Nodes are evaluated in topological order, so each node's value is
+/// known before any node that depends on it gets evaluated.
+#[derive(Clone, Debug)]
+pub struct Network {
+    inputs: usize,
+
+    /// Indices (into the values buffer) of the output nodes.
+    outputs: Vec<usize>,
+
+    /// Non-input nodes, in the order of evaluation.
+    nodes: Vec<Node>,
+
+    /// Number of nodes, including the inputs.
+    len: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    index: usize,
+    bias: f32,
+    activation: Activation,
+
+    /// (index of the source node, weight)
+    incoming: Vec<(usize, f32)>,
+}
+
+impl Network {
+    pub fn from_genome(genome: &Genome) -> Self {
+        let index_of = |id: usize| {
+            genome
+                .nodes()
+                .binary_search_by_key(&id, |node| node.id)
+                .expect("got connection to an unknown node")
+        };
+
+        let mut incoming = vec![Vec::new(); genome.nodes().len()];
+
+        for connection in genome.connections() {
+            if connection.enabled {
+                incoming[index_of(connection.to)]
+                    .push((index_of(connection.from), connection.weight));
+            }
+        }
+
+        // Depth-first topological sort - genomes never contain cycles,
+        // so there's no need to check for them
+        let mut order = Vec::with_capacity(genome.nodes().len());
+        let mut visited = vec![false; genome.nodes().len()];
+
+        fn visit(
+            index: usize,
+            incoming: &[Vec<(usize, f32)>],
+            visited: &mut [bool],
+            order: &mut Vec<usize>,
+        ) {
+            if visited[index] {
+                return;
+            }
+
+            visited[index] = true;
+
+            for &(source, _) in &incoming[index] {
+                visit(source, incoming, visited, order);
+            }
+
+            order.push(index);
+        }
+
+        for index in 0..genome.nodes().len() {
+            visit(index, &incoming, &mut visited, &mut order);
+        }
+
+        let nodes = order
+            .into_iter()
+            .filter(|&index| genome.nodes()[index].kind != NodeKind::Input)
+            .map(|index| Node {
+                index,
+                bias: genome.nodes()[index].bias,
+                activation: genome.nodes()[index].activation,
+                incoming: std::mem::take(&mut incoming[index]),
+            })
+            .collect();
+
+        let outputs = genome
+            .nodes()
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.kind == NodeKind::Output)
+            .map(|(index, _)| index)
+            .collect();
+
+        Self {
+            inputs: genome.inputs(),
+            outputs,
+            nodes,
+            len: genome.nodes().len(),
+        }
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.inputs
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.outputs.len()
+    }
+
+    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        assert_eq!(inputs.len(), self.inputs);
+
+        let mut values = inputs;
+        values.resize(self.len, 0.0);
+
+        for node in &self.nodes {
+            let sum = node
+                .incoming
+                .iter()
+                .map(|&(source, weight)| values[source] * weight)
+                .sum::<f32>();
+
+            values[node.index] = node.activation.apply(node.bias + sum);
+        }
+
+        self.outputs.iter().map(|&index| values[index]).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    mod propagate {
+        use super::*;
+
+        #[test]
+        fn minimal() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let genome = Genome::minimal(&mut rng, 2, 1, &Config::default());
+
+            let bias = genome.nodes()[2].bias;
+            let weights = (
+                genome.connections()[0].weight,
+                genome.connections()[1].weight,
+            );
+
+            let actual = genome.network().propagate(vec![0.5, -0.5]);
+            let expected = vec![(bias + 0.5 * weights.0 - 0.5 * weights.1).tanh()];
+
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+
+        #[test]
+        fn with_hidden_node() {
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut innovations = Innovations::new(2, 1);
+            let config = Config::default();
+
+            let mut genome = Genome::minimal(&mut rng, 2, 1, &config);
+            genome.add_node(&mut rng, &config, &mut innovations);
+
+            let output = &genome.nodes()[2];
+            let split = genome.connections().iter().find(|c| !c.enabled).unwrap();
+            let direct = genome
+                .connections()
+                .iter()
+                .find(|c| c.enabled && c.to == 2 && c.from < 2)
+                .unwrap();
+
+            let inputs = [0.5f32, -0.5];
+            let hidden = (inputs[split.from]).tanh();
+
+            let expected =
+                (output.bias + inputs[direct.from] * direct.weight + hidden * split.weight).tanh();
+            let actual = genome.network().propagate(inputs.to_vec());
+
+            approx::assert_relative_eq!(actual.as_slice(), [expected].as_slice());
+        }
+    }
+}
@@ -8,32 +8,80 @@ pub struct Neuron {
 
 impl Neuron {
     pub fn new(bias: f32, weights: Vec<f32>) -> Self {
-        assert!(!weights.is_empty());
+        Self::try_new(bias, weights).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(bias: f32, weights: Vec<f32>) -> Result<Self, NetworkError> {
+        if weights.is_empty() {
+            return Err(NetworkError::EmptyNeuron {
+                layer: 0,
+                neuron: 0,
+            });
+        }
 
-        Self { bias, weights }
+        Ok(Self { bias, weights })
     }
 
-    pub fn random(rng: &mut dyn RngCore, output_neurons: usize) -> Self {
-        let bias = rng.gen_range(-1.0..=1.0);
+    /// Creates a neuron with `fan_in` weights, belonging to a layer
+    /// with `fan_out` outputs.
+    pub fn random(
+        rng: &mut dyn RngCore,
+        fan_in: usize,
+        fan_out: usize,
+        initializer: Initializer,
+    ) -> Self {
+        let bias = initializer.bias(rng);
 
-        let weights = (0..output_neurons)
-            .map(|_| rng.gen_range(-1.0..=1.0))
+        let weights = (0..fan_in)
+            .map(|_| initializer.weight(rng, fan_in, fan_out))
             .collect();
 
         Self::new(bias, weights)
     }
 
-    pub fn from_weights(output_neurons: usize, weights: &mut dyn Iterator<Item = f32>) -> Self {
-        let bias = weights.next().expect("got not enough weights");
+    pub fn from_weights(
+        output_neurons: usize,
+        weights: &mut dyn Iterator<Item = f32>,
+    ) -> Result<Self, NetworkError> {
+        let mut next_weight = || {
+            weights.next().ok_or(NetworkError::NotEnoughWeights {
+                layer: 0,
+                neuron: 0,
+            })
+        };
+
+        let bias = next_weight()?;
 
         let weights = (0..output_neurons)
-            .map(|_| weights.next().expect("got not enough weights"))
-            .collect();
+            .map(|_| next_weight())
+            .collect::<Result<_, _>>()?;
+
+        Self::try_new(bias, weights)
+    }
 
-        Self { bias, weights }
+    pub fn propagate(&self, inputs: &[f32], activation: Activation) -> f32 {
+        self.try_propagate(inputs, activation)
+            .unwrap_or_else(|err| panic!("{}", err))
     }
 
-    pub fn propagate(&self, inputs: &[f32]) -> f32 {
+    pub fn try_propagate(
+        &self,
+        inputs: &[f32],
+        activation: Activation,
+    ) -> Result<f32, NetworkError> {
+        if inputs.len() != self.weights.len() {
+            return Err(NetworkError::InputsMismatch {
+                layer: 0,
+                expected: self.weights.len(),
+                actual: inputs.len(),
+            });
+        }
+
+        Ok(activation.apply(self.sum(inputs)))
+    }
+
+    /// Returns neuron's output *before* the activation function.
+    pub(crate) fn sum(&self, inputs: &[f32]) -> f32 {
         assert_eq!(inputs.len(), self.weights.len());
 
         let output = inputs
@@ -42,7 +90,37 @@ impl Neuron {
             .map(|(input, weight)| input * weight)
             .sum::<f32>();
 
-        (self.bias + output).max(0.0)
+        self.bias + output
+    }
+
+    /// Backpropagates `delta` (derivative of the loss with respect to
+    /// this neuron's `sum()`).
+    ///
+    /// Gradients are *accumulated*, so that the same buffers can be
+    /// reused across a whole batch:
+    ///
+    /// - `gradients` follows the `[bias, weights...]` layout,
+    /// - `input_gradients` receives derivatives with respect to inputs.
+    pub(crate) fn backward(
+        &self,
+        inputs: &[f32],
+        delta: f32,
+        gradients: &mut [f32],
+        input_gradients: &mut [f32],
+    ) {
+        assert_eq!(gradients.len(), self.weights.len() + 1);
+
+        gradients[0] += delta;
+
+        for (((gradient, input_gradient), input), weight) in gradients[1..]
+            .iter_mut()
+            .zip(input_gradients.iter_mut())
+            .zip(inputs)
+            .zip(&self.weights)
+        {
+            *gradient += delta * input;
+            *input_gradient += delta * weight;
+        }
     }
 }
 
@@ -58,7 +136,7 @@ mod tests {
         #[test]
         fn test() {
             let mut rng = ChaCha8Rng::from_seed(Default::default());
-            let neuron = Neuron::random(&mut rng, 4);
+            let neuron = Neuron::random(&mut rng, 4, 1, Initializer::Uniform);
 
             approx::assert_relative_eq!(neuron.bias, -0.6255188);
 
@@ -74,7 +152,8 @@ mod tests {
 
         #[test]
         fn returns_propagated_input() {
-            let actual = Neuron::new(0.1, vec![-0.3, 0.6, 0.9]).propagate(&[0.5, -0.6, 0.7]);
+            let actual = Neuron::new(0.1, vec![-0.3, 0.6, 0.9])
+                .propagate(&[0.5, -0.6, 0.7], Activation::Relu);
             let expected: f32 = 0.1 + (0.5 * -0.3) + (-0.6 * 0.6) + (0.7 * 0.9);
 
             approx::assert_relative_eq!(actual, expected.max(0.0));
@@ -83,17 +162,79 @@ mod tests {
         #[test]
         fn restricts_output() {
             let neuron = Neuron::new(0.0, vec![0.5]);
-            let v1 = neuron.propagate(&[-1.0]);
-            let v2 = neuron.propagate(&[-0.5]);
-            let v3 = neuron.propagate(&[0.0]);
-            let v4 = neuron.propagate(&[0.5]);
-            let v5 = neuron.propagate(&[1.0]);
+            let v1 = neuron.propagate(&[-1.0], Activation::Relu);
+            let v2 = neuron.propagate(&[-0.5], Activation::Relu);
+            let v3 = neuron.propagate(&[0.0], Activation::Relu);
+            let v4 = neuron.propagate(&[0.5], Activation::Relu);
+            let v5 = neuron.propagate(&[1.0], Activation::Relu);
 
             approx::assert_relative_eq!(v1, v2);
             approx::assert_relative_eq!(v2, v3);
             approx::assert_relative_ne!(v3, v4);
             approx::assert_relative_ne!(v4, v5);
         }
+
+        #[test]
+        fn applies_given_activation() {
+            let neuron = Neuron::new(0.0, vec![0.5]);
+
+            approx::assert_relative_eq!(
+                neuron.propagate(&[-1.0], Activation::Tanh),
+                (-0.5f32).tanh()
+            );
+            approx::assert_relative_eq!(neuron.propagate(&[-1.0], Activation::Identity), -0.5);
+        }
+    }
+
+    mod try_propagate {
+        use super::*;
+
+        #[test]
+        fn rejects_mismatched_inputs() {
+            let neuron = Neuron::new(0.1, vec![-0.3, 0.6, 0.9]);
+
+            assert_eq!(
+                neuron
+                    .try_propagate(&[0.5, -0.6], Activation::Relu)
+                    .unwrap_err(),
+                NetworkError::InputsMismatch {
+                    layer: 0,
+                    expected: 3,
+                    actual: 2
+                }
+            );
+        }
+    }
+
+    mod try_new {
+        use super::*;
+
+        #[test]
+        fn rejects_empty_weights() {
+            assert_eq!(
+                Neuron::try_new(0.5, vec![]).unwrap_err(),
+                NetworkError::EmptyNeuron {
+                    layer: 0,
+                    neuron: 0
+                }
+            );
+        }
+    }
+
+    mod backward {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let neuron = Neuron::new(0.1, vec![-0.3, 0.6]);
+            let mut gradients = vec![1.0, 1.0, 1.0];
+            let mut input_gradients = vec![0.0, 0.0];
+
+            neuron.backward(&[0.5, -2.0], 0.5, &mut gradients, &mut input_gradients);
+
+            approx::assert_relative_eq!(gradients.as_slice(), [1.5, 1.25, 0.0].as_slice());
+            approx::assert_relative_eq!(input_gradients.as_slice(), [-0.15, 0.3].as_slice());
+        }
     }
 
     mod from_weights {
@@ -101,11 +242,25 @@ mod tests {
 
         #[test]
         fn test() {
-            let actual = Neuron::from_weights(3, &mut vec![0.1, 0.2, 0.3, 0.4].into_iter());
+            let actual =
+                Neuron::from_weights(3, &mut vec![0.1, 0.2, 0.3, 0.4].into_iter()).unwrap();
             let expected = Neuron::new(0.1, vec![0.2, 0.3, 0.4]);
 
             approx::assert_relative_eq!(actual.bias, expected.bias);
             approx::assert_relative_eq!(actual.weights.as_slice(), expected.weights.as_slice());
         }
+
+        #[test]
+        fn rejects_not_enough_weights() {
+            let actual = Neuron::from_weights(3, &mut vec![0.1, 0.2, 0.3].into_iter());
+
+            assert_eq!(
+                actual.unwrap_err(),
+                NetworkError::NotEnoughWeights {
+                    layer: 0,
+                    neuron: 0
+                }
+            );
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,195 @@
+/// Updates network's weights given their gradients.
+///
+/// Both slices follow the layout of `Network::weights()`; optimizers
+/// that keep per-weight state (e.g. momentum) allocate it on the first
+/// call to `step()`.
+pub trait Optimizer {
+    fn step(&mut self, weights: &mut [f32], gradients: &[f32]);
+}
+
+/// Plain stochastic gradient descent.
+#[derive(Clone, Debug)]
+pub struct Sgd {
+    learning_rate: f32,
+}
+
+impl Sgd {
+    pub fn new(learning_rate: f32) -> Self {
+        assert!(learning_rate > 0.0);
+
+        Self { learning_rate }
+    }
+}
+
+impl Optimizer for Sgd {
+    fn step(&mut self, weights: &mut [f32], gradients: &[f32]) {
+        assert_eq!(weights.len(), gradients.len());
+
+        for (weight, gradient) in weights.iter_mut().zip(gradients) {
+            *weight -= self.learning_rate * gradient;
+        }
+    }
+}
+
+/// Gradient descent with (classical) momentum.
+#[derive(Clone, Debug)]
+pub struct Momentum {
+    learning_rate: f32,
+
+    /// How much of the previous update gets carried over:
+    /// - 0.0 = behaves like `Sgd`
+    /// - 0.9 = typical value
+    momentum: f32,
+
+    velocities: Vec<f32>,
+}
+
+impl Momentum {
+    pub fn new(learning_rate: f32, momentum: f32) -> Self {
+        assert!(learning_rate > 0.0);
+        assert!((0.0..1.0).contains(&momentum));
+
+        Self {
+            learning_rate,
+            momentum,
+            velocities: Vec::new(),
+        }
+    }
+}
+
+impl Optimizer for Momentum {
+    fn step(&mut self, weights: &mut [f32], gradients: &[f32]) {
+        assert_eq!(weights.len(), gradients.len());
+
+        self.velocities.resize(weights.len(), 0.0);
+
+        for ((weight, gradient), velocity) in
+            weights.iter_mut().zip(gradients).zip(&mut self.velocities)
+        {
+            *velocity = self.momentum * *velocity - self.learning_rate * gradient;
+            *weight += *velocity;
+        }
+    }
+}
+
+/// Adam, as described in https://arxiv.org/abs/1412.6980.
+#[derive(Clone, Debug)]
+pub struct Adam {
+    learning_rate: f32,
+    beta1: f32,
+    beta2: f32,
+    epsilon: f32,
+    moments: Vec<(f32, f32)>,
+    steps: i32,
+}
+
+impl Adam {
+    /// Creates Adam with the defaults suggested by the paper
+    /// (beta1 = 0.9, beta2 = 0.999, epsilon = 1e-8).
+    pub fn new(learning_rate: f32) -> Self {
+        Self::with_betas(learning_rate, 0.9, 0.999)
+    }
+
+    pub fn with_betas(learning_rate: f32, beta1: f32, beta2: f32) -> Self {
+        assert!(learning_rate > 0.0);
+        assert!((0.0..1.0).contains(&beta1));
+        assert!((0.0..1.0).contains(&beta2));
+
+        Self {
+            learning_rate,
+            beta1,
+            beta2,
+            epsilon: 1e-8,
+            moments: Vec::new(),
+            steps: 0,
+        }
+    }
+}
+
+impl Optimizer for Adam {
+    fn step(&mut self, weights: &mut [f32], gradients: &[f32]) {
+        assert_eq!(weights.len(), gradients.len());
+
+        self.moments.resize(weights.len(), (0.0, 0.0));
+        self.steps += 1;
+
+        let bias_correction1 = 1.0 - self.beta1.powi(self.steps);
+        let bias_correction2 = 1.0 - self.beta2.powi(self.steps);
+
+        for ((weight, &gradient), (m, v)) in
+            weights.iter_mut().zip(gradients).zip(&mut self.moments)
+        {
+            *m = self.beta1 * *m + (1.0 - self.beta1) * gradient;
+            *v = self.beta2 * *v + (1.0 - self.beta2) * gradient * gradient;
+
+            let m = *m / bias_correction1;
+            let v = *v / bias_correction2;
+
+            *weight -= self.learning_rate * m / (v.sqrt() + self.epsilon);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod sgd {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut weights = vec![1.0, -1.0];
+
+            Sgd::new(0.5).step(&mut weights, &[0.2, -0.4]);
+
+            approx::assert_relative_eq!(weights.as_slice(), [0.9, -0.8].as_slice());
+        }
+    }
+
+    mod momentum {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let mut optimizer = Momentum::new(0.5, 0.9);
+            let mut weights = vec![1.0];
+
+            optimizer.step(&mut weights, &[0.2]);
+            approx::assert_relative_eq!(weights[0], 0.9);
+
+            // velocity = 0.9 * -0.1 - 0.5 * 0.2 = -0.19
+            optimizer.step(&mut weights, &[0.2]);
+            approx::assert_relative_eq!(weights[0], 0.71);
+        }
+    }
+
+    mod adam {
+        use super::*;
+
+        #[test]
+        fn first_step_moves_by_learning_rate() {
+            // After bias correction, the first step is (almost exactly)
+            // `learning_rate * sign(gradient)`, whatever the magnitude.
+            let mut weights = vec![1.0, 1.0];
+
+            Adam::new(0.1).step(&mut weights, &[0.001, -50.0]);
+
+            approx::assert_relative_eq!(weights.as_slice(), [0.9, 1.1].as_slice(), epsilon = 1e-4);
+        }
+
+        #[test]
+        fn minimizes_quadratic() {
+            let mut optimizer = Adam::new(0.05);
+            let mut weights = vec![3.0, -2.0];
+
+            // f(x, y) = x^2 + y^2
+            for _ in 0..500 {
+                let gradients: Vec<_> = weights.iter().map(|w| 2.0 * w).collect();
+                optimizer.step(&mut weights, &gradients);
+            }
+
+            approx::assert_relative_eq!(weights.as_slice(), [0.0, 0.0].as_slice(), epsilon = 1e-2);
+        }
+    }
+}
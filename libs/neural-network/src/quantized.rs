@@ -0,0 +1,349 @@
+use crate::*;
+
+/// `Network` with weights stored as `i8`, created by
+/// `Network::quantize()`.
+///
+/// Each layer keeps its own scale and zero point, mapping the range of
+/// its weights onto 256 integer values; inputs of each layer get
+/// quantized the same way (using range of the inputs at hand), so that
+/// neurons can sum them using integer math only. Biases and activation
+/// functions stay in `f32`.
+///
+/// This takes about a quarter of the memory of `Network`, at the cost
+/// of some precision - see `Self::report()`.
+#[derive(Clone, Debug)]
+pub struct QuantizedNetwork {
+    layers: Vec<QuantizedLayer>,
+}
+
+#[derive(Clone, Debug)]
+struct QuantizedLayer {
+    input_size: usize,
+    activation: Activation,
+    quantization: Quantization,
+
+    /// Weights of all neurons, one neuron after another
+    weights: Vec<i8>,
+    biases: Vec<f32>,
+}
+
+/// Affine mapping between `f32` and `i8`:
+///
+/// `value = scale * (quantized - zero_point)`
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Quantization {
+    scale: f32,
+    zero_point: i32,
+}
+
+/// How far `QuantizedNetwork` strays from the network it's been
+/// created from; see `QuantizedNetwork::report()`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantizationReport {
+    /// Largest difference between an original and a quantized weight,
+    /// for each layer (following `Network::topology()`, without the
+    /// input layer).
+    ///
+    /// It's roughly half of the layer's scale - that is: `(max weight -
+    /// min weight) / 510`.
+    pub weight_errors: Vec<f32>,
+
+    /// Largest difference between outputs of both networks, across all
+    /// the samples.
+    pub max_output_error: f32,
+
+    /// Average difference between outputs of both networks, across all
+    /// the samples.
+    pub mean_output_error: f32,
+}
+
+impl Network {
+    /// Converts this network into its quantized version.
+    ///
+    /// Only feed-forward networks can be quantized.
+    pub fn quantize(&self) -> Result<QuantizedNetwork, NetworkError> {
+        let layers = self
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                QuantizedLayer::from_layer(layer).map_err(|err| err.in_layer(index + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(QuantizedNetwork { layers })
+    }
+}
+
+impl QuantizedNetwork {
+    pub fn input_size(&self) -> usize {
+        self.layers[0].input_size
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.layers[self.layers.len() - 1].biases.len()
+    }
+
+    /// Propagates `inputs` through the network; same as
+    /// `Network::propagate()`, give or take the quantization error.
+    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        self.layers
+            .iter()
+            .fold(inputs, |inputs, layer| layer.propagate(&inputs))
+    }
+
+    /// Compares this network with the `original` one (i.e. the one it's
+    /// been quantized from), by propagating given samples through both.
+    pub fn report(&self, original: &Network, samples: &[Vec<f32>]) -> QuantizationReport {
+        assert_eq!(original.layers.len(), self.layers.len());
+
+        let weight_errors = original
+            .layers
+            .iter()
+            .zip(&self.layers)
+            .map(|(original, layer)| {
+                original
+                    .neurons
+                    .iter()
+                    .flat_map(|neuron| &neuron.weights)
+                    .zip(&layer.weights)
+                    .map(|(&weight, &quantized)| {
+                        (weight - layer.quantization.dequantize(quantized)).abs()
+                    })
+                    .fold(0.0, f32::max)
+            })
+            .collect();
+
+        let mut max_output_error = 0.0f32;
+        let mut total_output_error = 0.0;
+        let mut outputs = 0;
+
+        for sample in samples {
+            let expected = original.propagate(sample.clone());
+            let actual = self.propagate(sample.clone());
+
+            for (expected, actual) in expected.iter().zip(&actual) {
+                let error = (expected - actual).abs();
+
+                max_output_error = max_output_error.max(error);
+                total_output_error += error;
+                outputs += 1;
+            }
+        }
+
+        QuantizationReport {
+            weight_errors,
+            max_output_error,
+            mean_output_error: if outputs > 0 {
+                total_output_error / outputs as f32
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+impl QuantizedLayer {
+    fn from_layer(layer: &Layer) -> Result<Self, NetworkError> {
+        if layer.kind != LayerKind::Dense {
+            return Err(NetworkError::UnsupportedLayer {
+                layer: 0,
+                kind: layer.kind,
+            });
+        }
+
+        let weights = || layer.neurons.iter().flat_map(|neuron| &neuron.weights);
+        let quantization = Quantization::new(weights().copied());
+
+        Ok(Self {
+            input_size: layer.input_size(),
+            activation: layer.activation,
+            quantization,
+            weights: weights()
+                .map(|&weight| quantization.quantize(weight))
+                .collect(),
+            biases: layer.neurons.iter().map(|neuron| neuron.bias).collect(),
+        })
+    }
+
+    fn propagate(&self, inputs: &[f32]) -> Vec<f32> {
+        assert_eq!(inputs.len(), self.input_size);
+
+        let input_quantization = Quantization::new(inputs.iter().copied());
+
+        let inputs: Vec<_> = inputs
+            .iter()
+            .map(|&input| input_quantization.quantize(input) as i32 - input_quantization.zero_point)
+            .collect();
+
+        let scale = self.quantization.scale * input_quantization.scale;
+
+        self.weights
+            .chunks_exact(self.input_size)
+            .zip(&self.biases)
+            .map(|(weights, bias)| {
+                let sum: i32 = weights
+                    .iter()
+                    .zip(&inputs)
+                    .map(|(&weight, input)| (weight as i32 - self.quantization.zero_point) * input)
+                    .sum();
+
+                self.activation.apply(bias + scale * sum as f32)
+            })
+            .collect()
+    }
+}
+
+impl Quantization {
+    /// Creates quantization that covers all of `values` (and zero, so
+    /// that zero is always represented exactly).
+    fn new(values: impl Iterator<Item = f32>) -> Self {
+        let (min, max) = values.fold((0.0f32, 0.0f32), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+
+        if max - min <= f32::EPSILON {
+            return Self {
+                scale: 1.0,
+                zero_point: 0,
+            };
+        }
+
+        let scale = (max - min) / 255.0;
+        let zero_point = (i8::MIN as f32 - min / scale).round() as i32;
+
+        Self {
+            scale,
+            zero_point: zero_point.clamp(i8::MIN as i32, i8::MAX as i32),
+        }
+    }
+
+    fn quantize(self, value: f32) -> i8 {
+        let quantized = (value / self.scale).round() as i32 + self.zero_point;
+
+        quantized.clamp(i8::MIN as i32, i8::MAX as i32) as i8
+    }
+
+    fn dequantize(self, quantized: i8) -> f32 {
+        self.scale * (quantized as i32 - self.zero_point) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn network(hidden: LayerKind) -> Network {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        Network::random(
+            &mut rng,
+            &[
+                LayerTopology {
+                    neurons: 8,
+                    activation: Activation::Relu,
+                    kind: LayerKind::Dense,
+                },
+                LayerTopology {
+                    neurons: 16,
+                    activation: Activation::Tanh,
+                    kind: hidden,
+                },
+                LayerTopology {
+                    neurons: 2,
+                    activation: Activation::Tanh,
+                    kind: LayerKind::Dense,
+                },
+            ],
+        )
+    }
+
+    fn samples() -> Vec<Vec<f32>> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        (0..50)
+            .map(|_| (0..8).map(|_| rng.gen_range(0.0..=1.0)).collect())
+            .collect()
+    }
+
+    mod quantization {
+        use super::*;
+
+        #[test]
+        fn represents_zero_exactly() {
+            let quantization = Quantization::new([-0.3, 0.7, 1.2].into_iter());
+
+            assert_eq!(quantization.dequantize(quantization.quantize(0.0)), 0.0);
+        }
+
+        #[test]
+        fn covers_whole_range() {
+            let quantization = Quantization::new([-0.3, 0.7, 1.2].into_iter());
+
+            assert_eq!(quantization.quantize(-0.3), i8::MIN);
+            assert_eq!(quantization.quantize(1.2), i8::MAX);
+            assert_eq!(quantization.quantize(5.0), i8::MAX);
+        }
+
+        #[test]
+        fn survives_constant_values() {
+            let quantization = Quantization::new([0.0, 0.0].into_iter());
+
+            assert_eq!(quantization.dequantize(quantization.quantize(0.0)), 0.0);
+        }
+    }
+
+    mod propagate {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let network = network(LayerKind::Dense);
+            let quantized = network.quantize().unwrap();
+
+            assert_eq!(quantized.input_size(), 8);
+            assert_eq!(quantized.output_size(), 2);
+
+            for sample in samples() {
+                let expected = network.propagate(sample.clone());
+                let actual = quantized.propagate(sample);
+
+                approx::assert_abs_diff_eq!(actual.as_slice(), expected.as_slice(), epsilon = 0.05);
+            }
+        }
+
+        #[test]
+        fn rejects_recurrent_networks() {
+            assert_eq!(
+                network(LayerKind::Gru).quantize().unwrap_err(),
+                NetworkError::UnsupportedLayer {
+                    layer: 1,
+                    kind: LayerKind::Gru
+                }
+            );
+        }
+    }
+
+    mod report {
+        use super::*;
+
+        #[test]
+        fn test() {
+            let network = network(LayerKind::Dense);
+            let quantized = network.quantize().unwrap();
+            let report = quantized.report(&network, &samples());
+
+            assert_eq!(report.weight_errors.len(), 2);
+
+            for (error, layer) in report.weight_errors.iter().zip(&quantized.layers) {
+                assert!(*error <= layer.quantization.scale / 2.0 + f32::EPSILON);
+            }
+
+            assert!(report.max_output_error > 0.0);
+            assert!(report.max_output_error < 0.05);
+            assert!(report.mean_output_error <= report.max_output_error);
+        }
+    }
+}
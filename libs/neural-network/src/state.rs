@@ -0,0 +1,24 @@
+/// Hidden state of a network's recurrent layers, carried from one
+/// `Network::step()` to the next.
+///
+/// Each individual (e.g. each bird) should have a state of its own;
+/// it's created with `Network::state()` and starts zeroed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkState {
+    /// One entry per layer; empty for feed-forward layers.
+    pub(crate) hidden: Vec<Vec<f32>>,
+}
+
+impl NetworkState {
+    /// Forgets everything remembered so far.
+    pub fn reset(&mut self) {
+        for hidden in &mut self.hidden {
+            hidden.fill(0.0);
+        }
+    }
+
+    /// Returns hidden state of all layers, laid one after another.
+    pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.hidden.iter().flatten().copied()
+    }
+}
@@ -1,3 +1,4 @@
+use lib_neural_network as nn;
 use lib_simulation as sim;
 use rand::prelude::*;
 use serde::Serialize;
@@ -33,6 +34,35 @@ impl Simulation {
         self.sim.step(&mut self.rng);
     }
 
+    /// Returns brain of the `index`-th bird, serialized into JSON.
+    pub fn save_brain(&self, index: usize) -> Result<String, JsValue> {
+        let animal = self
+            .sim
+            .world()
+            .animals()
+            .get(index)
+            .ok_or_else(|| JsValue::from_str("no such bird"))?;
+
+        let network = animal
+            .brain()
+            .network()
+            .ok_or_else(|| JsValue::from_str("NEAT brains can't be saved"))?;
+
+        Ok(network.to_json())
+    }
+
+    /// Gives every bird a copy of the brain serialized by `save_brain()`.
+    pub fn load_brain(&mut self, json: &str) -> Result<(), JsValue> {
+        let network =
+            nn::Network::from_json(json).map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+        if self.sim.load_brain(&network) {
+            Ok(())
+        } else {
+            Err(JsValue::from_str("brain doesn't fit the birds' eyes"))
+        }
+    }
+
     /// min = minimum amount of food eaten by any bird
     ///
     /// max = maximum amount of food eaten by any bird
@@ -40,15 +70,24 @@ impl Simulation {
     /// avg = sum of all the food eaten by all the birds,
     ///       divided by the number of birds
     ///
-    /// Median could also come useful!
+    /// median = amount of food eaten by the "middle" bird - half of
+    ///          the birds ate less, half of them ate more
+    ///
+    /// std_dev = how much the amounts of food eaten differ between birds
+    ///
+    /// diversity = how much the birds' brains differ from each other;
+    ///             when it drops close to zero, all birds think alike
     pub fn train(&mut self) -> String {
         let stats = self.sim.train(&mut self.rng);
 
         format!(
-            "min={:.2}, max={:.2}, avg={:.2}",
+            "min={:.2}, max={:.2}, avg={:.2}, median={:.2}, std_dev={:.2}, diversity={:.4}",
             stats.min_fitness(),
             stats.max_fitness(),
-            stats.avg_fitness()
+            stats.avg_fitness(),
+            stats.median_fitness(),
+            stats.std_dev_fitness(),
+            stats.diversity()
         )
     }
 }
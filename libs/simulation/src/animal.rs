@@ -12,9 +12,9 @@ pub struct Animal {
 }
 
 impl Animal {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
+    pub fn random(rng: &mut dyn RngCore, brain: BrainKind) -> Self {
         let eye = Eye::default();
-        let brain = Brain::random(rng, &eye);
+        let brain = Brain::random(rng, &eye, brain);
 
         Self::new(eye, brain, rng)
     }
@@ -27,10 +27,20 @@ impl Animal {
     /// (so it's stuff that wouldn't make sense to keep in the genome.)
     pub(crate) fn from_chromosome(
         chromosome: ga::Chromosome,
+        brain: BrainKind,
         rng: &mut dyn RngCore,
     ) -> Self {
         let eye = Eye::default();
-        let brain = Brain::from_chromosome(chromosome, &eye);
+        let brain = Brain::from_chromosome(chromosome, &eye, brain);
+
+        Self::new(eye, brain, rng)
+    }
+
+    /// Ditto, but for birds with NEAT brains - whose genomes don't fit
+    /// into `ga::Chromosome`.
+    pub(crate) fn from_genome(genome: nn::neat::Genome, rng: &mut dyn RngCore) -> Self {
+        let eye = Eye::default();
+        let brain = Brain::from_genome(genome);
 
         Self::new(eye, brain, rng)
     }
@@ -66,6 +76,10 @@ impl Animal {
         self.rotation
     }
 
+    pub fn brain(&self) -> &Brain {
+        &self.brain
+    }
+
     fn new(eye: Eye, brain: Brain, rng: &mut dyn RngCore) -> Self {
         Self {
             position: rng.gen(),
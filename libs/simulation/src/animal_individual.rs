@@ -13,12 +13,14 @@ impl AnimalIndividual {
         }
     }
 
-    pub fn into_animal(self, rng: &mut dyn RngCore) -> Animal {
-        Animal::from_chromosome(self.chromosome, rng)
+    pub fn into_animal(self, brain: BrainKind, rng: &mut dyn RngCore) -> Animal {
+        Animal::from_chromosome(self.chromosome, brain, rng)
     }
 }
 
 impl ga::Individual for AnimalIndividual {
+    type Gene = f32;
+
     fn create(chromosome: ga::Chromosome) -> Self {
         Self {
             fitness: 0.0,
@@ -1,42 +1,176 @@
 use crate::*;
 
+/// What kind of network birds think with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BrainKind {
+    /// Birds react only to what they see at the moment
+    #[default]
+    FeedForward,
+
+    /// Birds remember what they've seen, using a simple recurrent
+    /// hidden layer
+    Rnn,
+
+    /// Ditto, but using a GRU hidden layer - slower, but better at
+    /// remembering things for longer
+    Gru,
+
+    /// Birds start with eyes wired straight into their "muscles", and
+    /// the evolution grows new neurons and connections as it goes (see
+    /// `nn::neat`)
+    Neat,
+}
+
+/// Number of brain's outputs - speed and rotation.
+pub(crate) const BRAIN_OUTPUTS: usize = 2;
+
 #[derive(Debug)]
 pub struct Brain {
-    pub(crate) nn: nn::Network,
+    pub(crate) nn: BrainNetwork,
+}
+
+#[derive(Debug)]
+pub(crate) enum BrainNetwork {
+    /// Network of fixed shape, given by `Brain::topology()`
+    Layered(nn::Network),
+
+    /// Network grown by NEAT; the compiled network is kept next to its
+    /// genome, so that we don't have to rebuild it on each step
+    Neat {
+        genome: nn::neat::Genome,
+        network: nn::neat::Network,
+    },
 }
 
 impl Brain {
-    pub fn random(rng: &mut dyn RngCore, eye: &Eye) -> Self {
+    pub fn random(rng: &mut dyn RngCore, eye: &Eye, kind: BrainKind) -> Self {
+        if kind == BrainKind::Neat {
+            return Self::from_genome(nn::neat::Genome::minimal(
+                rng,
+                eye.cells(),
+                BRAIN_OUTPUTS,
+                &nn::neat::Config::default(),
+            ));
+        }
+
+        Self {
+            nn: BrainNetwork::Layered(nn::Network::random(rng, &Self::topology(eye, kind))),
+        }
+    }
+
+    pub(crate) fn from_genome(genome: nn::neat::Genome) -> Self {
         Self {
-            nn: nn::Network::random(rng, &Self::topology(eye)),
+            nn: BrainNetwork::Neat {
+                network: genome.network(),
+                genome,
+            },
         }
     }
 
+    /// Creates the algorithm that evolves `BrainKind::Neat` brains.
+    pub(crate) fn neat(eye: &Eye) -> nn::neat::Neat {
+        nn::neat::Neat::new(nn::neat::Config::default(), eye.cells(), BRAIN_OUTPUTS)
+    }
+
     pub(crate) fn from_chromosome(
         chromosome: ga::Chromosome,
         eye: &Eye,
+        kind: BrainKind,
     ) -> Self {
         Self {
-            nn: nn::Network::from_weights(
-                &Self::topology(eye),
+            nn: BrainNetwork::Layered(nn::Network::from_weights(
+                &Self::topology(eye, kind),
                 chromosome,
-            ),
+            )),
+        }
+    }
+
+    /// Wraps an already-trained network (e.g. one loaded from a file),
+    /// provided its topology fits given eye and kind.
+    ///
+    /// NEAT brains don't have a fixed topology, so they can't be
+    /// created this way.
+    pub fn from_network(nn: nn::Network, eye: &Eye, kind: BrainKind) -> Option<Self> {
+        if kind != BrainKind::Neat && nn.topology() == Self::topology(eye, kind) {
+            Some(Self {
+                nn: BrainNetwork::Layered(nn),
+            })
+        } else {
+            None
         }
     }
 
+    /// For NEAT brains, the chromosome contains just the connections'
+    /// weights - it's useful for statistics, but it doesn't describe
+    /// the brain's structure.
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
-        self.nn.weights().collect()
+        match &self.nn {
+            BrainNetwork::Layered(nn) => nn.weights().collect(),
+
+            BrainNetwork::Neat { genome, .. } => genome
+                .connections()
+                .iter()
+                .map(|connection| connection.weight)
+                .collect(),
+        }
+    }
+
+    /// Returns the brain's network, unless it's a NEAT brain.
+    pub fn network(&self) -> Option<&nn::Network> {
+        match &self.nn {
+            BrainNetwork::Layered(nn) => Some(nn),
+            BrainNetwork::Neat { .. } => None,
+        }
+    }
+
+    /// Returns the brain's genome, if it's a NEAT brain.
+    pub fn genome(&self) -> Option<&nn::neat::Genome> {
+        match &self.nn {
+            BrainNetwork::Layered(_) => None,
+            BrainNetwork::Neat { genome, .. } => Some(genome),
+        }
     }
 
-    fn topology(eye: &Eye) -> [nn::LayerTopology; 3] {
+    fn topology(eye: &Eye, kind: BrainKind) -> [nn::LayerTopology; 3] {
+        let hidden = match kind {
+            BrainKind::FeedForward => nn::LayerTopology {
+                neurons: 2 * eye.cells(),
+                activation: nn::Activation::Relu,
+                kind: nn::LayerKind::Dense,
+            },
+
+            // Recurrent layers feed their outputs back into themselves,
+            // so they need an activation that doesn't let those outputs
+            // grow without bounds
+            BrainKind::Rnn => nn::LayerTopology {
+                neurons: 2 * eye.cells(),
+                activation: nn::Activation::Tanh,
+                kind: nn::LayerKind::Rnn,
+            },
+            BrainKind::Gru => nn::LayerTopology {
+                neurons: 2 * eye.cells(),
+                activation: nn::Activation::Tanh,
+                kind: nn::LayerKind::Gru,
+            },
+
+            BrainKind::Neat => unreachable!("NEAT brains don't have a fixed topology"),
+        };
+
         [
             nn::LayerTopology {
                 neurons: eye.cells(),
+                activation: nn::Activation::Relu,
+                kind: nn::LayerKind::Dense,
             },
+            hidden,
+            // Speed and rotation are both relative, so the output layer
+            // has to be able to go below zero - otherwise our birds
+            // could never slow down or turn left.
             nn::LayerTopology {
-                neurons: 2 * eye.cells(),
+                neurons: BRAIN_OUTPUTS,
+                activation: nn::Activation::Tanh,
+                kind: nn::LayerKind::Dense,
             },
-            nn::LayerTopology { neurons: 2 },
         ]
     }
 }
\ No newline at end of file
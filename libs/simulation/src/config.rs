@@ -0,0 +1,178 @@
+use crate::*;
+use thiserror::Error as ThisError;
+
+/// Describes why `Config::validate()` rejected settings.
+#[derive(ThisError, Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    #[error("weight limit must be non-negative and finite, got {0}")]
+    InvalidWeightLimit(f32),
+
+    #[error("weight limit is not supported by CMA-ES")]
+    UnsupportedWeightLimit,
+}
+
+/// Settings for `Simulation::with_config()`.
+///
+/// `Config::default()` gives the original simulation - the one that
+/// `Simulation::random()` creates.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Config {
+    pub brain: BrainKind,
+
+    /// What evolves birds' brains; ignored for NEAT brains, which evolve
+    /// on their own.
+    pub optimizer: OptimizerKind,
+
+    /// How the genetic algorithm mixes parents' chromosomes; used only
+    /// with `OptimizerKind::GeneticAlgorithm`.
+    pub crossover: CrossoverKind,
+
+    /// How the genetic algorithm replaces birds between generations;
+    /// used only with `OptimizerKind::GeneticAlgorithm`.
+    pub replacement: ga::Replacement,
+
+    /// How the genetic algorithm keeps birds from all converging onto
+    /// the same strategy; used only with `OptimizerKind::GeneticAlgorithm`.
+    pub niching: ga::Niching,
+
+    /// Keeps weights of birds' brains from drifting to huge magnitudes
+    /// over many generations; supported only by `OptimizerKind::GeneticAlgorithm`
+    /// and `OptimizerKind::EvolutionStrategy` (`ga::CmaEs` doesn't support
+    /// bounds).
+    pub weight_limit: Option<WeightLimit>,
+}
+
+impl Config {
+    /// Checks whether the settings go together.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(WeightLimit { limit, .. }) = self.weight_limit {
+            // Bounds span `2 * limit`, which has to stay finite, too
+            if !(limit >= 0.0 && (2.0 * limit).is_finite()) {
+                return Err(ConfigError::InvalidWeightLimit(limit));
+            }
+
+            if matches!(self.optimizer, OptimizerKind::CmaEs { .. }) {
+                return Err(ConfigError::UnsupportedWeightLimit);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Which `ga::Optimizer` evolves birds' brains.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OptimizerKind {
+    /// See `ga::GeneticAlgorithm`
+    #[default]
+    GeneticAlgorithm,
+
+    /// See `ga::EvolutionStrategy`; each gene of a child gets nudged by
+    /// a value sampled from `N(0, sigma^2)`
+    EvolutionStrategy { parents: usize, plus: bool, sigma: f32 },
+
+    /// See `ga::CmaEs`
+    CmaEs { sigma: f32 },
+}
+
+/// Bounds each weight within `[-limit, limit]`; see `ga::Bounds`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightLimit {
+    pub limit: f32,
+    pub repair: ga::Repair,
+}
+
+impl WeightLimit {
+    pub(crate) fn bounds(self) -> ga::Bounds {
+        ga::Bounds::uniform(-self.limit, self.limit).with_repair(self.repair)
+    }
+}
+
+/// Which `ga::CrossoverMethod` the genetic algorithm uses.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CrossoverKind {
+    /// See `ga::UniformCrossover`
+    #[default]
+    Uniform,
+
+    /// See `ga::SinglePointCrossover`
+    SinglePoint,
+
+    /// See `ga::KPointCrossover`
+    KPoint { points: usize },
+
+    /// See `ga::ArithmeticCrossover`
+    Arithmetic { alpha: f32 },
+
+    /// See `ga::BlendCrossover`
+    Blend { alpha: f32 },
+
+    /// See `ga::SimulatedBinaryCrossover`
+    SimulatedBinary { eta: f32 },
+}
+
+impl CrossoverKind {
+    pub(crate) fn method(self) -> Box<dyn ga::CrossoverMethod> {
+        match self {
+            Self::Uniform => Box::new(ga::UniformCrossover::new()),
+            Self::SinglePoint => Box::new(ga::SinglePointCrossover::new()),
+            Self::KPoint { points } => Box::new(ga::KPointCrossover::new(points)),
+            Self::Arithmetic { alpha } => Box::new(ga::ArithmeticCrossover::new(alpha)),
+            Self::Blend { alpha } => Box::new(ga::BlendCrossover::new(alpha)),
+            Self::SimulatedBinary { eta } => Box::new(ga::SimulatedBinaryCrossover::new(eta)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod validate {
+        use super::*;
+
+        fn weight_limit(limit: f32) -> Option<WeightLimit> {
+            Some(WeightLimit {
+                limit,
+                repair: ga::Repair::Clamp,
+            })
+        }
+
+        #[test]
+        fn test() {
+            let config = Config {
+                weight_limit: weight_limit(4.0),
+                ..Default::default()
+            };
+
+            assert_eq!(Config::default().validate(), Ok(()));
+            assert_eq!(config.validate(), Ok(()));
+        }
+
+        #[test]
+        fn rejects_weight_limit_with_cma_es() {
+            let config = Config {
+                optimizer: OptimizerKind::CmaEs { sigma: 0.5 },
+                weight_limit: weight_limit(4.0),
+                ..Default::default()
+            };
+
+            assert_eq!(config.validate(), Err(ConfigError::UnsupportedWeightLimit));
+        }
+
+        #[test]
+        fn rejects_invalid_weight_limit() {
+            for limit in [-1.0, f32::INFINITY, f32::MAX, f32::NAN] {
+                let config = Config {
+                    weight_limit: weight_limit(limit),
+                    ..Default::default()
+                };
+
+                assert!(matches!(
+                    config.validate(),
+                    Err(ConfigError::InvalidWeightLimit(_))
+                ));
+            }
+        }
+    }
+}
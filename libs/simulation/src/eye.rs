@@ -129,6 +129,24 @@ impl Eye {
     ) -> Vec<f32> {
         let mut cells = vec![0.0; self.cells];
 
+        self.process_vision_into(position, rotation, foods, &mut cells);
+
+        cells
+    }
+
+    /// Same as `process_vision()`, but writes into a caller-provided
+    /// buffer (of `self.cells()` elements) instead of allocating one.
+    pub fn process_vision_into(
+        &self,
+        position: na::Point2<f32>,
+        rotation: na::Rotation2<f32>,
+        foods: &[Food],
+        cells: &mut [f32],
+    ) {
+        assert_eq!(cells.len(), self.cells);
+
+        cells.fill(0.0);
+
         for food in foods {
             let vec = food.position - position;
 
@@ -295,8 +313,6 @@ impl Eye {
 
             cells[cell] += energy;
         }
-
-        cells
     }
 }
 
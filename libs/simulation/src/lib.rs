@@ -1,10 +1,11 @@
 #![allow(dead_code)]
 
-pub use self::{animal::*, brain::*, eye::*, food::*, world::*};
+pub use self::{animal::*, brain::*, config::*, eye::*, food::*, world::*};
 
 mod animal;
 mod animal_individual;
 mod brain;
+mod config;
 mod eye;
 mod food;
 mod world;
@@ -67,31 +68,119 @@ const ROTATION_ACCEL: f32 = FRAC_PI_2;
 const GENERATION_LENGTH: usize = 2500;
 
 pub struct Simulation {
+    config: Config,
     world: World,
-    ga: ga::GeneticAlgorithm<ga::RouletteWheelSelection>,
+    optimizer: Box<dyn ga::Optimizer<AnimalIndividual>>,
     age: usize,
+
+    /// Evolves birds' brains instead of `ga`, if they are NEAT brains.
+    neat: Option<nn::neat::Neat>,
+
+    /// All the birds' brains, packed together so that they can be
+    /// evaluated in one go; rebuilt after each evolution.
+    ///
+    /// NEAT brains all have different shapes, so they can't be packed
+    /// together - for them this is `None`, and each brain is evaluated
+    /// on its own.
+    brains: Option<BrainBatch>,
+
+    /// What each bird sees (one row per bird), fed into `brains`.
+    vision: Vec<f32>,
+
+    /// What each bird's brain responded with (one row per bird).
+    responses: Vec<f32>,
+}
+
+struct BrainBatch {
+    networks: nn::NetworkBatch,
+    buffer: nn::BatchBuffer,
+
+    /// What the birds' brains remember (if they're recurrent); starts
+    /// empty for each new bird.
+    state: nn::BatchState,
 }
 
 impl Simulation {
     pub fn random(rng: &mut dyn RngCore) -> Self {
-        let world = World::random(rng);
-
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection::new(),
-            ga::UniformCrossover::new(),
-            ga::GaussianMutation::new(0.01, 0.3),
-            // ---------------------- ^--^ -^-^
-            // | Chosen with a bit of experimentation.
-            // |
-            // | Higher values can make the simulation more chaotic,
-            // | which - a bit counterintuitively - might allow for
-            // | it to discover *better* solutions; but the trade-off
-            // | is that higher values might also cause current, good
-            // | enough solutions to be discarded.
-            // ---
-        );
+        Self::with_config(rng, Config::default()).expect("default config is always valid")
+    }
+
+    /// Creates a simulation with given settings; fails if they don't go
+    /// together (see `Config::validate()`).
+    pub fn with_config(rng: &mut dyn RngCore, config: Config) -> Result<Self, ConfigError> {
+        config.validate()?;
+
+        let world = World::random(rng, config.brain);
+
+        let bounds = config.weight_limit.map(WeightLimit::bounds);
+
+        let optimizer: Box<dyn ga::Optimizer<AnimalIndividual>> = match config.optimizer {
+            OptimizerKind::GeneticAlgorithm => {
+                let mut ga = ga::GeneticAlgorithm::new(
+                    ga::RouletteWheelSelection::new(),
+                    config.crossover.method(),
+                    ga::GaussianMutation::new(0.01, 0.3),
+                    // ---------------------- ^--^ -^-^
+                    // | Chosen with a bit of experimentation.
+                    // |
+                    // | Higher values can make the simulation more chaotic,
+                    // | which - a bit counterintuitively - might allow for
+                    // | it to discover *better* solutions; but the trade-off
+                    // | is that higher values might also cause current, good
+                    // | enough solutions to be discarded.
+                    // ---
+                )
+                .with_replacement(config.replacement)
+                .with_niching(config.niching);
+
+                if let Some(bounds) = bounds {
+                    ga = ga.with_bounds(bounds);
+                }
+
+                Box::new(ga)
+            }
+
+            OptimizerKind::EvolutionStrategy {
+                parents,
+                plus,
+                sigma,
+            } => {
+                let mutation = ga::GaussianMutation::new(1.0, sigma);
+
+                let mut es = if plus {
+                    ga::EvolutionStrategy::plus(parents, mutation)
+                } else {
+                    ga::EvolutionStrategy::comma(parents, mutation)
+                };
+
+                if let Some(bounds) = bounds {
+                    es = es.with_bounds(bounds);
+                }
 
-        Self { world, ga, age: 0 }
+                Box::new(es)
+            }
+
+            OptimizerKind::CmaEs { sigma } => Box::new(ga::CmaEs::new(sigma)),
+        };
+
+        let eye = Eye::default();
+
+        let neat = if config.brain == BrainKind::Neat {
+            Some(Brain::neat(&eye))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            config,
+            neat,
+            brains: Self::brains(&world),
+            vision: vec![0.0; world.animals.len() * eye.cells()],
+            responses: vec![0.0; world.animals.len() * BRAIN_OUTPUTS],
+            world,
+            optimizer,
+            age: 0,
+        })
     }
 
     /// Performs a single step - a single second, so to say - of our
@@ -123,15 +212,108 @@ impl Simulation {
         &self.world
     }
 
+    /// Returns what each layer of the `index`-th bird's brain has
+    /// computed for what the bird has seen during the last step (see
+    /// `nn::Network::activations()`); hidden state of recurrent brains
+    /// is not taken into account.
+    ///
+    /// Returns `None` for NEAT brains and for birds that don't exist.
+    pub fn brain_activations(&self, index: usize) -> Option<Vec<Vec<f32>>> {
+        let network = self.world.animals.get(index)?.brain.network()?;
+        let cells = self.vision.len() / self.world.animals.len();
+        let vision = &self.vision[index * cells..][..cells];
+
+        Some(network.activations(vision.to_vec()))
+    }
+
+    /// Replaces every bird's brain with a copy of given network.
+    ///
+    /// Returns `false` (leaving the birds intact) if network's topology
+    /// doesn't match the birds' eyes or the configured brain kind.
+    pub fn load_brain(&mut self, network: &nn::Network) -> bool {
+        let brains: Option<Vec<_>> = self
+            .world
+            .animals
+            .iter()
+            .map(|animal| Brain::from_network(network.clone(), &animal.eye, self.config.brain))
+            .collect();
+
+        let brains = match brains {
+            Some(brains) => brains,
+            None => return false,
+        };
+
+        for (animal, brain) in self.world.animals.iter_mut().zip(brains) {
+            animal.brain = brain;
+        }
+
+        self.brains = Self::brains(&self.world);
+
+        true
+    }
+
+    fn brains(world: &World) -> Option<BrainBatch> {
+        let networks: Option<Vec<_>> = world
+            .animals
+            .iter()
+            .map(|animal| animal.brain.network())
+            .collect();
+
+        let networks = nn::NetworkBatch::from_networks(networks?);
+
+        Some(BrainBatch {
+            buffer: networks.buffer(),
+            state: networks.state(),
+            networks,
+        })
+    }
+
     fn process_brains(&mut self) {
-        for animal in &mut self.world.animals {
-            let vision =
-                animal
-                    .eye
-                    .process_vision(animal.position, animal.rotation, &self.world.foods);
+        let cells = self.vision.len() / self.world.animals.len();
+
+        for (animal, vision) in self
+            .world
+            .animals
+            .iter()
+            .zip(self.vision.chunks_exact_mut(cells))
+        {
+            animal.eye.process_vision_into(
+                animal.position,
+                animal.rotation,
+                &self.world.foods,
+                vision,
+            );
+        }
 
-            let response = animal.brain.nn.propagate(vision);
+        match &mut self.brains {
+            Some(brains) => brains.networks.step(
+                &self.vision,
+                &mut self.responses,
+                &mut brains.buffer,
+                &mut brains.state,
+            ),
+
+            None => {
+                for ((animal, vision), response) in self
+                    .world
+                    .animals
+                    .iter()
+                    .zip(self.vision.chunks_exact(cells))
+                    .zip(self.responses.chunks_exact_mut(BRAIN_OUTPUTS))
+                {
+                    if let BrainNetwork::Neat { network, .. } = &animal.brain.nn {
+                        response.copy_from_slice(&network.propagate(vision.to_vec()));
+                    }
+                }
+            }
+        }
 
+        for (animal, response) in self
+            .world
+            .animals
+            .iter_mut()
+            .zip(self.responses.chunks_exact(BRAIN_OUTPUTS))
+        {
             // ---
             // | Limits number to given range.
             // -------------------- v---v
@@ -195,15 +377,40 @@ impl Simulation {
             .map(AnimalIndividual::from_animal)
             .collect();
 
-        // Step 2: Evolve birdies
-        //let evolved_population = self.ga.evolve(rng, &current_population);
-        let (evolved_population, stats) = self.ga.evolve(rng, &current_population);
+        // Step 2 & 3: Evolve birdies and bring them back from the
+        // genetic algorithm
+        let stats = if let Some(neat) = &mut self.neat {
+            let stats = ga::Statistics::new(&current_population);
+
+            let genomes: Vec<_> = self
+                .world
+                .animals
+                .iter()
+                .map(|animal| {
+                    let genome = animal.brain.genome().expect("got a non-NEAT brain");
+
+                    (genome, animal.satiation as f32)
+                })
+                .collect();
+
+            self.world.animals = neat
+                .evolve(rng, &genomes)
+                .into_iter()
+                .map(|genome| Animal::from_genome(genome, rng))
+                .collect();
+
+            stats
+        } else {
+            //let evolved_population = self.optimizer.evolve(rng, &current_population);
+            let (evolved_population, stats) = self.optimizer.evolve(rng, &current_population);
 
-        // Step 3: Bring birdies back from the genetic algorithm
-        self.world.animals = evolved_population
-            .into_iter()
-            .map(|individual| individual.into_animal(rng))
-            .collect();
+            self.world.animals = evolved_population
+                .into_iter()
+                .map(|individual| individual.into_animal(self.config.brain, rng))
+                .collect();
+
+            stats
+        };
 
         // Step 4: Restart foods
         //
@@ -212,6 +419,9 @@ impl Simulation {
         for food in &mut self.world.foods {
             food.position = rng.gen();
         }
+
+        self.brains = Self::brains(&self.world);
+
         stats
     }
 }
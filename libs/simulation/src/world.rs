@@ -7,9 +7,9 @@ pub struct World {
 }
 
 impl World {
-    pub fn random(rng: &mut dyn RngCore) -> Self {
+    pub fn random(rng: &mut dyn RngCore, brain: BrainKind) -> Self {
         let animals = (0..40)
-            .map(|_| Animal::random(rng))
+            .map(|_| Animal::random(rng, brain))
             .collect();
 
         let foods = (0..60)
@@ -1,7 +1,7 @@
 use gloo::console;
 use lib_simulation as sim;
 use rand::prelude::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::f64;
 use std::f64::consts::PI;
 use std::ops::DerefMut;
@@ -14,12 +14,17 @@ use yew::{html, Component, Context, Html, NodeRef};
 
 pub struct App {
     node_ref: NodeRef,
+    brain_ref: NodeRef,
     sim: Rc<RefCell<sim::Simulation>>,
     rng: Rc<RefCell<ThreadRng>>,
+
+    /// Index of the bird whose brain is drawn next to the world
+    selected: Rc<Cell<usize>>,
 }
 
 pub enum Msg {
     Train,
+    SelectNext,
 }
 
 impl Component for App {
@@ -32,8 +37,10 @@ impl Component for App {
 
         Self {
             node_ref: NodeRef::default(),
+            brain_ref: NodeRef::default(),
             rng: Rc::new(RefCell::new(rng)),
             sim: Rc::new(RefCell::new(sim)),
+            selected: Rc::new(Cell::new(0)),
         }
     }
 
@@ -49,6 +56,13 @@ impl Component for App {
                 // Return true to cause the displayed change to update
                 true
             }
+            Msg::SelectNext => {
+                let animals = self.sim.borrow().world().animals().len();
+                self.selected.set((self.selected.get() + 1) % animals);
+
+                // The render loop picks the new bird up on its own
+                false
+            }
         }
     }
 
@@ -56,7 +70,9 @@ impl Component for App {
         html! {
             <div>
                 <canvas id="viewport" width="800" height="800" ref={self.node_ref.clone()} />
+                <canvas id="brain" width="400" height="800" ref={self.brain_ref.clone()} />
                 <button class="train-button" onclick={ctx.link().callback(|_| Msg::Train)}>{ "Train" }</button>
+                <button class="select-button" onclick={ctx.link().callback(|_| Msg::SelectNext)}>{ "Next bird" }</button>
             </div>
         }
     }
@@ -94,7 +110,23 @@ impl Component for App {
             .dyn_into::<CanvasRenderingContext2d>()
             .unwrap();
 
-        self.render(context, viewport_width as f64, viewport_height as f64);
+        let brain_canvas = self.brain_ref.cast::<HtmlCanvasElement>().unwrap();
+
+        let brain_context = brain_canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into::<CanvasRenderingContext2d>()
+            .unwrap();
+
+        self.render(
+            context,
+            viewport_width as f64,
+            viewport_height as f64,
+            brain_context,
+            brain_canvas.width() as f64,
+            brain_canvas.height() as f64,
+        );
     }
 }
 
@@ -107,7 +139,14 @@ impl App {
     }
 
     /// draws a triangle on the canvas
-    fn draw_triangle(context: &CanvasRenderingContext2d, x: f64, y: f64, size: f64, rotation: f64) {
+    fn draw_triangle(
+        context: &CanvasRenderingContext2d,
+        x: f64,
+        y: f64,
+        size: f64,
+        rotation: f64,
+        color: &str,
+    ) {
         context.begin_path();
 
         context.move_to(
@@ -130,7 +169,7 @@ impl App {
             y + rotation.sin() * size * 1.5,
         );
 
-        context.set_fill_style(&JsValue::from_str("rgb(255, 255, 255)"));
+        context.set_fill_style(&JsValue::from_str(color));
         context.fill();
     }
 
@@ -145,11 +184,102 @@ impl App {
         context.fill();
     }
 
-    
+    /// draws brain of the `index`-th bird: one column of neurons per
+    /// layer (brighter = more active), connected by lines that are
+    /// green for positive weights and red for negative ones
+    fn draw_brain(
+        context: &CanvasRenderingContext2d,
+        sim: &sim::Simulation,
+        index: usize,
+        width: f64,
+        height: f64,
+    ) {
+        context.clear_rect(0.0, 0.0, width, height);
+
+        let network = sim
+            .world()
+            .animals()
+            .get(index)
+            .and_then(|animal| animal.brain().network());
+
+        // NEAT brains don't have layers, so there's nothing to draw
+        let (network, activations) = match (network, sim.brain_activations(index)) {
+            (Some(network), Some(activations)) => (network, activations),
+            _ => return,
+        };
+
+        let position = |layer: usize, neuron: usize| {
+            (
+                (layer as f64 + 0.5) / activations.len() as f64 * width,
+                (neuron as f64 + 0.5) / activations[layer].len() as f64 * height,
+            )
+        };
+
+        // Connections go first, so that neurons get drawn on top of them
+        for layer in 1..activations.len() {
+            for neuron in 0..activations[layer].len() {
+                let (x, y) = position(layer, neuron);
+                let (_, weights) = network.neuron(layer, neuron).unwrap();
+
+                // Recurrent neurons also have weights for their hidden
+                // state, which are skipped here (and for GRU layers,
+                // these are the update gate's weights)
+                for (input, weight) in weights
+                    .iter()
+                    .take(activations[layer - 1].len())
+                    .enumerate()
+                {
+                    let (from_x, from_y) = position(layer - 1, input);
+                    let color = if *weight > 0.0 { "0, 255, 128" } else { "255, 64, 64" };
+
+                    context.begin_path();
+                    context.move_to(from_x, from_y);
+                    context.line_to(x, y);
+                    context.set_stroke_style(&JsValue::from_str(&format!(
+                        "rgba({}, {})",
+                        color,
+                        weight.abs().min(1.0)
+                    )));
+                    context.stroke();
+                }
+            }
+        }
+
+        for (layer, activations) in activations.iter().enumerate() {
+            let radius = (height / activations.len() as f64 / 3.0).min(10.0);
+
+            for (neuron, activation) in activations.iter().enumerate() {
+                let (x, y) = position(layer, neuron);
+                let brightness = (activation.abs().min(1.0) * 255.0) as u8;
+
+                context.begin_path();
+
+                let _ = context.arc(x, y, radius, 0.0, 2.0 * std::f64::consts::PI);
+
+                context.set_fill_style(&JsValue::from_str(&format!(
+                    "rgb({0}, {0}, {0})",
+                    brightness
+                )));
+                context.fill();
+                context.set_stroke_style(&JsValue::from_str("rgb(255, 255, 255)"));
+                context.stroke();
+            }
+        }
+    }
+
     /// render scene 
-    fn render(&self, context: CanvasRenderingContext2d, view_width: f64, view_height: f64) {
+    fn render(
+        &self,
+        context: CanvasRenderingContext2d,
+        view_width: f64,
+        view_height: f64,
+        brain_context: CanvasRenderingContext2d,
+        brain_width: f64,
+        brain_height: f64,
+    ) {
         let sim_ref = Rc::clone(&self.sim);
         let rng_ref = Rc::clone(&self.rng);
+        let selected = Rc::clone(&self.selected);
         let cb = Rc::new(RefCell::new(None));
 
         // render closure that gets called from request_animation_frame 
@@ -176,18 +306,33 @@ impl App {
                     )
                 }
 
-                for animal in world.animals() {
+                for (index, animal) in world.animals().iter().enumerate() {
                     let pos = animal.position();
 
+                    let color = if index == selected.get() {
+                        "rgb(255, 200, 0)"
+                    } else {
+                        "rgb(255, 255, 255)"
+                    };
+
                     Self::draw_triangle(
                         &context,
                         pos.x as f64 * view_width,
                         pos.y as f64 * view_height,
                         0.01 * view_width,
                         animal.rotation().angle() as f64,
+                        color,
                     )
                 }
 
+                Self::draw_brain(
+                    &brain_context,
+                    &sim,
+                    selected.get(),
+                    brain_width,
+                    brain_height,
+                );
+
                 Self::request_animation_frame(cb.borrow().as_ref().unwrap());
             }
         }) as Box<dyn FnMut()>));